@@ -1,9 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::fs;
 use std::io::{self, BufRead, Write};
 use std::net::{TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::OnceLock;
 use std::thread;
 
@@ -12,67 +13,240 @@ use regex::Regex;
 use crate::types::CommandRef;
 use serde_json::json;
 
-use crate::args::{preset_ignore_symbols, ParsedArgs};
+use crate::args::ParsedArgs;
+use crate::barrels::{analyze_barrels, BarrelReport};
+use crate::cache::{cache_base, fingerprint, manifest_hashes, AnalysisCache, FileStamp};
+use crate::capabilities::{check_capabilities, CapabilityReport};
+use crate::clones::{
+    clone_tokens, clones_html, clones_json, detect_clones, print_clones, CloneGroup,
+};
+use crate::config::load_config;
+use crate::dependencies::{build_dependency_inventory, DependencyInventory};
+use crate::duplicates::{reported_kinds, DuplicateExports, SymbolFilter};
+use crate::dynamic_imports::{dynamic_import_json, js_dynamic_imports, DynamicImports};
+use crate::electron::{main_handlers, preload_bridge, IpcCoverage, IpcMatcher};
+use crate::entrypoints::{reachability, Reachability};
 use crate::fs_utils::{gather_files, normalise_ignore_patterns, GitIgnoreChecker};
+use crate::graph_export::{build_file_graph, render_graph};
+use crate::layers::{
+    check_dependency_rules, print_violations, violations_html, violations_json, RuleViolation,
+};
+use crate::python::{classify_block, definition_name, dunder_all_names, logical_lines, PyBlock};
+use crate::report::{escape_html, HtmlContext};
+use crate::routes::{
+    js_route_mounts, js_routes, py_route_mounts, py_routes, rust_route_mounts, rust_routes,
+    RouteCoverage,
+};
+use crate::rust_syntax::{parse_rust, path_roots, reexport_parts};
+use crate::tauri::{
+    command_rename, command_rename_all, handler_args_from_source, handler_registrations,
+    rust_events, ts_events, CommandCoverage, EventCoverage, InvokeMatcher,
+};
 use crate::types::{
-    ExportIndex, ExportSymbol, FileAnalysis, ImportEntry, ImportKind, Options, OutputMode,
-    ReexportEntry, ReexportKind,
+    DiagnosticKind, DynamicImport, DynamicImportKind, ExportCategory, ExportSymbol, FileAnalysis,
+    FileDiagnostic, ImportEntry, ImportKind, ImportSymbol, Options, OutputMode, ReexportEntry,
+    ReexportKind,
 };
 
 static OPEN_SERVER_BASE: OnceLock<String> = OnceLock::new();
 
-type RankedDup = (
-    String,
-    Vec<String>,
-    usize,
-    usize,
-    usize,
-    String,
-    Vec<String>,
-);
-
-struct ReportSection {
-    root: String,
-    files_analyzed: usize,
-    ranked_dups: Vec<RankedDup>,
-    cascades: Vec<(String, String)>,
-    dynamic: Vec<(String, Vec<String>)>,
-    analyze_limit: usize,
-    missing_handlers: Vec<CommandGap>,
-    unused_handlers: Vec<CommandGap>,
+/// Everything reported for one analysed root.
+struct RootReport {
+    root: PathBuf,
+    analyses: Vec<FileAnalysis>,
+    /// Files that were skipped or only partially analysed.
+    diagnostics: Vec<FileDiagnostic>,
+    /// Resolved `(from, to, kind)` edges between analysed files.
+    graph_edges: Vec<(String, String, String)>,
+    duplicates: DuplicateExports,
+    barrels: BarrelReport,
+    clones: Vec<CloneGroup>,
+    dynamic: DynamicImports,
+    commands: CommandCoverage,
+    ipc: IpcCoverage,
+    routes: RouteCoverage,
+    events: EventCoverage,
+    capabilities: Option<CapabilityReport>,
+    rule_violations: Vec<RuleViolation>,
+    dependencies: DependencyInventory,
+    reachability: Option<Reachability>,
+    /// Base URL of the `--serve` open server for source links in HTML.
     open_base: Option<String>,
-    graph: Option<GraphData>,
-}
-
-#[derive(Clone)]
-struct CommandGap {
-    name: String,
-    locations: Vec<(String, usize)>,
-}
-
-#[derive(Clone)]
-struct GraphData {
-    nodes: Vec<String>,
-    edges: Vec<(String, String, String)>, // from, to, kind
 }
 
-fn escape_html(raw: &str) -> String {
-    raw.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-}
+const GRAPH_RENDERER_JS: &str = r#"(function(){
+  const SVG_NS = 'http://www.w3.org/2000/svg';
+  const EDGE_COLORS = { import: '#888', reexport: '#e07b39', dynamic: '#4f81e1' };
+  const RADIUS = 6;
+
+  function svg(name, attrs) {
+    const el = document.createElementNS(SVG_NS, name);
+    for (const key in attrs) el.setAttribute(key, attrs[key]);
+    return el;
+  }
+
+  // Fruchterman-Reingold force layout; small enough for a few hundred files.
+  function layout(nodes, edges, width, height) {
+    const n = nodes.length;
+    const k = Math.sqrt((width * height) / Math.max(n, 1)) * 0.75;
+    nodes.forEach((node, i) => {
+      const angle = (2 * Math.PI * i) / Math.max(n, 1);
+      node.x = width / 2 + Math.cos(angle) * width / 3;
+      node.y = height / 2 + Math.sin(angle) * height / 3;
+    });
+    let temp = width / 8;
+    const iterations = n > 400 ? 80 : 250;
+    for (let it = 0; it < iterations; it++) {
+      nodes.forEach(v => { v.dx = 0; v.dy = 0; });
+      for (let i = 0; i < n; i++) {
+        for (let j = i + 1; j < n; j++) {
+          const a = nodes[i], b = nodes[j];
+          const dx = a.x - b.x, dy = a.y - b.y;
+          const d = Math.sqrt(dx * dx + dy * dy) || 0.01;
+          const f = (k * k) / d;
+          a.dx += (dx / d) * f; a.dy += (dy / d) * f;
+          b.dx -= (dx / d) * f; b.dy -= (dy / d) * f;
+        }
+      }
+      edges.forEach(e => {
+        if (e.s === e.t) return;
+        const dx = e.s.x - e.t.x, dy = e.s.y - e.t.y;
+        const d = Math.sqrt(dx * dx + dy * dy) || 0.01;
+        const f = (d * d) / k;
+        e.s.dx -= (dx / d) * f; e.s.dy -= (dy / d) * f;
+        e.t.dx += (dx / d) * f; e.t.dy += (dy / d) * f;
+      });
+      nodes.forEach(v => {
+        v.dx += (width / 2 - v.x) * 0.02;
+        v.dy += (height / 2 - v.y) * 0.02;
+        const d = Math.sqrt(v.dx * v.dx + v.dy * v.dy) || 0.01;
+        const step = Math.min(d, temp);
+        v.x += (v.dx / d) * step;
+        v.y += (v.dy / d) * step;
+      });
+      temp = Math.max(temp * 0.97, 0.5);
+    }
+  }
+
+  function draw(container, data) {
+    const width = container.clientWidth || 800;
+    const height = container.clientHeight || 520;
+    const byId = new Map();
+    const nodes = (data.nodes || []).map(id => {
+      const node = { id, edges: [] };
+      byId.set(id, node);
+      return node;
+    });
+    const edges = [];
+    (data.edges || []).forEach(([from, to, kind]) => {
+      const s = byId.get(from), t = byId.get(to);
+      if (!s || !t) return;
+      const edge = { s, t, kind };
+      s.edges.push(edge);
+      t.edges.push(edge);
+      edges.push(edge);
+    });
+    layout(nodes, edges, width, height);
+
+    const root = svg('svg', { width: '100%', height: '100%', viewBox: `0 0 ${width} ${height}` });
+    const defs = svg('defs', {});
+    Object.keys(EDGE_COLORS).forEach(kind => {
+      const marker = svg('marker', { id: `${container.id}-arrow-${kind}`, viewBox: '0 0 10 10', refX: 10 + RADIUS, refY: 5, markerWidth: 6, markerHeight: 6, orient: 'auto' });
+      marker.appendChild(svg('path', { d: 'M0,0 L10,5 L0,10 z', fill: EDGE_COLORS[kind] }));
+      defs.appendChild(marker);
+    });
+    root.appendChild(defs);
+    const scene = svg('g', {});
+    root.appendChild(scene);
+
+    edges.forEach(e => {
+      const color = EDGE_COLORS[e.kind] || EDGE_COLORS.import;
+      e.el = svg('line', { stroke: color, 'stroke-width': 1.2, 'marker-end': `url(#${container.id}-arrow-${EDGE_COLORS[e.kind] ? e.kind : 'import'})` });
+      if (e.kind !== 'import') e.el.setAttribute('stroke-dasharray', e.kind === 'dynamic' ? '4 3' : '');
+      const title = svg('title', {});
+      title.textContent = `${e.s.id} -> ${e.t.id} (${e.kind})`;
+      e.el.appendChild(title);
+      scene.appendChild(e.el);
+    });
+    nodes.forEach(node => {
+      node.el = svg('g', { class: 'node' });
+      node.el.appendChild(svg('circle', { r: RADIUS, fill: '#4f81e1', stroke: '#fff', 'stroke-width': 1.5 }));
+      const label = svg('text', { x: RADIUS + 3, y: 4, 'font-size': 10, fill: '#333' });
+      label.textContent = node.id.split('/').pop();
+      node.el.appendChild(label);
+      const title = svg('title', {});
+      title.textContent = node.id;
+      node.el.appendChild(title);
+      scene.appendChild(node.el);
+    });
 
-fn url_encode_component(input: &str) -> String {
-    input
-        .bytes()
-        .map(|b| match b {
-            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
-                (b as char).to_string()
-            }
-            _ => format!("%{:02X}", b),
-        })
-        .collect()
+    function place(node) {
+      node.el.setAttribute('transform', `translate(${node.x},${node.y})`);
+      node.edges.forEach(e => {
+        e.el.setAttribute('x1', e.s.x); e.el.setAttribute('y1', e.s.y);
+        e.el.setAttribute('x2', e.t.x); e.el.setAttribute('y2', e.t.y);
+      });
+    }
+    nodes.forEach(place);
+
+    // Pan (drag background), zoom (wheel) and node dragging.
+    const view = { x: 0, y: 0, scale: 1 };
+    const applyView = () => scene.setAttribute('transform', `translate(${view.x},${view.y}) scale(${view.scale})`);
+    const toScene = evt => {
+      const box = root.getBoundingClientRect();
+      const px = ((evt.clientX - box.left) / box.width) * width;
+      const py = ((evt.clientY - box.top) / box.height) * height;
+      return { px, py, x: (px - view.x) / view.scale, y: (py - view.y) / view.scale };
+    };
+    let drag = null;
+    nodes.forEach(node => node.el.addEventListener('mousedown', evt => {
+      evt.stopPropagation();
+      drag = { node };
+    }));
+    root.addEventListener('mousedown', evt => {
+      const p = toScene(evt);
+      drag = { pan: true, px: p.px - view.x, py: p.py - view.y };
+    });
+    window.addEventListener('mousemove', evt => {
+      if (!drag) return;
+      const p = toScene(evt);
+      if (drag.node) {
+        drag.node.x = p.x;
+        drag.node.y = p.y;
+        place(drag.node);
+      } else {
+        view.x = p.px - drag.px;
+        view.y = p.py - drag.py;
+        applyView();
+      }
+    });
+    window.addEventListener('mouseup', () => { drag = null; });
+    root.addEventListener('wheel', evt => {
+      evt.preventDefault();
+      const p = toScene(evt);
+      const factor = evt.deltaY < 0 ? 1.1 : 1 / 1.1;
+      view.scale = Math.min(Math.max(view.scale * factor, 0.2), 5);
+      view.x = p.px - p.x * view.scale;
+      view.y = p.py - p.y * view.scale;
+      applyView();
+    }, { passive: false });
+
+    container.appendChild(root);
+  }
+
+  document.querySelectorAll('script[type="application/json"][data-loctree-graph]').forEach(script => {
+    const container = document.getElementById(script.dataset.loctreeGraph);
+    if (!container) return;
+    draw(container, JSON.parse(script.textContent));
+  });
+})();"#;
+
+/// Serializes JSON for embedding inside a `<script>` element: `</` is escaped
+/// so file paths cannot terminate the element early.
+fn json_for_script(value: &serde_json::Value) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_else(|_| "null".into())
+        .replace("</", "<\\/")
 }
 
 fn url_decode_component(input: &str) -> Option<String> {
@@ -93,16 +267,157 @@ fn url_decode_component(input: &str) -> Option<String> {
     Some(out)
 }
 
-fn linkify(base: Option<&str>, file: &str, line: usize) -> String {
-    if let Some(base) = base {
-        let href = format!("{}/open?f={}&l={}", base, url_encode_component(file), line);
-        format!("<a href=\"{}\">{}:{}</a>", href, file, line)
-    } else {
-        format!("{}:{}", file, line)
+impl RootReport {
+    fn json(&self) -> serde_json::Value {
+        let mut commands = self.commands.json();
+        commands["electron"] = self.ipc.json();
+        let mut payload = json!({
+            "root": self.root,
+            "filesAnalyzed": self.analyses.len(),
+            "diagnostics": self.diagnostics
+                .iter()
+                .map(|d| json!({"file": d.path, "kind": d.kind.as_str(), "message": d.message}))
+                .collect::<Vec<_>>(),
+            "commands": commands,
+            "capabilities": self.capabilities.as_ref().map(CapabilityReport::json),
+            "routes": self.routes.json(),
+            "events": self.events.json(),
+            "externalDependencies": self.dependencies.json(),
+            "reachability": self.reachability.as_ref().map(Reachability::json),
+            "files": self.analyses.iter().map(file_json).collect::<Vec<_>>(),
+        });
+        // Sections that report under several top-level keys.
+        for section in [
+            self.duplicates.json(),
+            self.barrels.json(),
+            clones_json(&self.clones),
+            self.dynamic.json(),
+            violations_json(&self.rule_violations),
+        ] {
+            if let (Some(payload), serde_json::Value::Object(keys)) =
+                (payload.as_object_mut(), section)
+            {
+                payload.extend(keys);
+            }
+        }
+        payload
+    }
+
+    fn print(&self, limit: usize) {
+        println!("Import/export analysis for {}/", self.root.display());
+        println!("  Files analyzed: {}", self.analyses.len());
+        println!("  Duplicate exports: {}", self.duplicates.count());
+        println!("  Files with re-exports: {}", self.barrels.reexport_files);
+        println!("  Dynamic imports: {}", self.dynamic.file_count());
+
+        if !self.diagnostics.is_empty() {
+            println!("\nWarnings ({}):", self.diagnostics.len());
+            for diagnostic in &self.diagnostics {
+                println!(
+                    "  - {} [{}] {}",
+                    diagnostic.path,
+                    diagnostic.kind.as_str(),
+                    diagnostic.message
+                );
+            }
+        }
+
+        self.duplicates.print(limit);
+        self.barrels.print(limit);
+        print_clones(&self.clones, limit);
+        self.dynamic.print(limit);
+        self.commands.print();
+        self.ipc.print();
+        self.routes.print();
+        if let Some(capabilities) = &self.capabilities {
+            capabilities.print();
+        }
+        self.events.print();
+        print_violations(&self.rule_violations);
+        self.dependencies.print(limit);
+        if let Some(reachability) = &self.reachability {
+            reachability.print(limit);
+        }
+
+        println!("\nTip: rerun with --json for machine-readable output.");
+    }
+
+    fn html(&self, out: &mut String, limit: usize, graph: bool) {
+        let ctx = &HtmlContext {
+            open_base: self.open_base.clone(),
+            limit,
+        };
+        out.push_str(&format!(
+            "<h2>{}</h2><p class=\"muted\">Files analyzed: {}</p>",
+            escape_html(&self.root.display().to_string()),
+            self.analyses.len()
+        ));
+        if !self.diagnostics.is_empty() {
+            out.push_str(
+                "<h3>Warnings</h3><table><tr><th>File</th><th>Problem</th><th>Details</th></tr>",
+            );
+            for diagnostic in &self.diagnostics {
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&diagnostic.path),
+                    diagnostic.kind.as_str(),
+                    escape_html(&diagnostic.message)
+                ));
+            }
+            out.push_str("</table>");
+        }
+
+        self.duplicates.html(out, ctx);
+        self.barrels.html(out, ctx);
+        clones_html(&self.clones, out, ctx);
+        self.dynamic.html(out, ctx);
+        self.commands.html(out, ctx);
+        self.ipc.html(out, ctx);
+        self.routes.html(out, ctx);
+        if let Some(capabilities) = &self.capabilities {
+            capabilities.html(out, ctx);
+        }
+        self.events.html(out, ctx);
+        violations_html(&self.rule_violations, out, ctx);
+        self.dependencies.html(out, ctx);
+        if let Some(reachability) = &self.reachability {
+            reachability.html(out, ctx);
+        }
+
+        if graph && !self.graph_edges.is_empty() {
+            let graph_id = format!(
+                "graph-{}",
+                self.root
+                    .display()
+                    .to_string()
+                    .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+            );
+            let nodes: HashSet<&String> = self
+                .graph_edges
+                .iter()
+                .flat_map(|(from, to, _)| [from, to])
+                .collect();
+            out.push_str("<h3>Import graph</h3>");
+            out.push_str(&format!(
+                "<p class=\"muted\">Drag to pan, scroll to zoom, drag nodes to rearrange. Edges: <span style=\"color:#888\">import</span>, <span style=\"color:#e07b39\">re-export</span>, <span style=\"color:#4f81e1\">dynamic</span>.</p><div class=\"graph\" id=\"{}\"></div>",
+                escape_html(&graph_id)
+            ));
+            let data = json!({"nodes": nodes, "edges": self.graph_edges});
+            out.push_str(&format!(
+                "<script type=\"application/json\" data-loctree-graph=\"{}\">{}</script>",
+                escape_html(&graph_id),
+                json_for_script(&data)
+            ));
+        }
     }
 }
 
-fn render_html_report(path: &Path, sections: &[ReportSection]) -> io::Result<()> {
+fn render_html_report(
+    path: &Path,
+    sections: &[RootReport],
+    limit: usize,
+    graph: bool,
+) -> io::Result<()> {
     let mut out = String::new();
     out.push_str(
         r#"<!DOCTYPE html>
@@ -126,165 +441,13 @@ code{background:#f6f8fa;padding:2px 4px;border-radius:4px;}
     );
 
     for section in sections {
-        out.push_str(&format!(
-            "<h2>{}</h2><p class=\"muted\">Files analyzed: {}</p>",
-            escape_html(&section.root),
-            section.files_analyzed
-        ));
-
-        // Duplicate exports
-        out.push_str("<h3>Top duplicate exports</h3>");
-        if section.ranked_dups.is_empty() {
-            out.push_str("<p class=\"muted\">None</p>");
-        } else {
-            out.push_str("<table><tr><th>Symbol</th><th>Files</th><th>Prod</th><th>Dev</th><th>Canonical</th><th>Refactor targets</th></tr>");
-            for (name, files, _score, prod, dev, canonical, refactors) in
-                section.ranked_dups.iter().take(section.analyze_limit)
-            {
-                out.push_str(&format!(
-                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td><code>{}</code></td><td>{}</td></tr>",
-                    escape_html(name),
-                    files.len(),
-                    prod,
-                    dev,
-                    escape_html(canonical),
-                    escape_html(&refactors.join(", "))
-                ));
-            }
-            out.push_str("</table>");
-        }
-
-        // Cascades
-        out.push_str("<h3>Re-export cascades</h3>");
-        if section.cascades.is_empty() {
-            out.push_str("<p class=\"muted\">None</p>");
-        } else {
-            out.push_str("<ul>");
-            for (from, to) in &section.cascades {
-                out.push_str(&format!(
-                    "<li><code>{}</code> → <code>{}</code></li>",
-                    escape_html(from),
-                    escape_html(to)
-                ));
-            }
-            out.push_str("</ul>");
-        }
-
-        // Dynamic imports
-        out.push_str("<h3>Dynamic imports</h3>");
-        if section.dynamic.is_empty() {
-            out.push_str("<p class=\"muted\">None</p>");
-        } else {
-            out.push_str("<table><tr><th>File</th><th>Sources</th></tr>");
-            for (file, sources) in section.dynamic.iter().take(section.analyze_limit) {
-                out.push_str(&format!(
-                    "<tr><td><code>{}</code></td><td>{}</td></tr>",
-                    escape_html(file),
-                    escape_html(&sources.join(", "))
-                ));
-            }
-            out.push_str("</table>");
-        }
-
-        // Command coverage
-        out.push_str("<h3>Tauri command coverage</h3>");
-        if section.missing_handlers.is_empty() && section.unused_handlers.is_empty() {
-            out.push_str("<p class=\"muted\">All frontend calls have matching handlers.</p>");
-        } else {
-            out.push_str("<table><tr><th>Missing handlers (FE→BE)</th><th>Handlers unused by FE</th></tr><tr><td>");
-            if section.missing_handlers.is_empty() {
-                out.push_str("<span class=\"muted\">None</span>");
-            } else {
-                let lines: Vec<String> = section
-                    .missing_handlers
-                    .iter()
-                    .map(|g| {
-                        let locs: Vec<String> = g
-                            .locations
-                            .iter()
-                            .map(|(f, l)| linkify(section.open_base.as_deref(), f, *l))
-                            .collect();
-                        format!("{} ({})", g.name, locs.join("; "))
-                    })
-                    .collect();
-                out.push_str(&escape_html(&lines.join(" · ")));
-            }
-            out.push_str("</td><td>");
-            if section.unused_handlers.is_empty() {
-                out.push_str("<span class=\"muted\">None</span>");
-            } else {
-                let lines: Vec<String> = section
-                    .unused_handlers
-                    .iter()
-                    .map(|g| {
-                        let locs: Vec<String> = g
-                            .locations
-                            .iter()
-                            .map(|(f, l)| linkify(section.open_base.as_deref(), f, *l))
-                            .collect();
-                        format!("{} ({})", g.name, locs.join("; "))
-                    })
-                    .collect();
-                out.push_str(&escape_html(&lines.join(" · ")));
-            }
-            out.push_str("</td></tr></table>");
-        }
-
-        if let Some(graph) = &section.graph {
-            out.push_str("<h3>Import graph</h3>");
-            out.push_str(&format!(
-                "<div class=\"graph\" id=\"graph-{}\"></div>",
-                escape_html(
-                    &section
-                        .root
-                        .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
-                )
-            ));
-            let nodes_json = serde_json::to_string(&graph.nodes).unwrap_or("[]".into());
-            let edges_json = serde_json::to_string(&graph.edges).unwrap_or("[]".into());
-            out.push_str("<script>");
-            out.push_str("window.__LOCTREE_GRAPHS = window.__LOCTREE_GRAPHS || [];");
-            out.push_str("window.__LOCTREE_GRAPHS.push({");
-            out.push_str(&format!(
-                "id:\"graph-{}\",nodes:{},edges:{}",
-                escape_html(
-                    &section
-                        .root
-                        .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
-                ),
-                nodes_json,
-                edges_json
-            ));
-            out.push_str("});</script>");
-        }
+        section.html(&mut out, limit, graph);
     }
 
-    // Graph bootstrap (Cytoscape via CDN)
-    out.push_str(
-        r#"<script src="https://unpkg.com/cytoscape@3.26.0/dist/cytoscape.min.js"></script>
-<script>
-(function(){
-  const graphs = window.__LOCTREE_GRAPHS || [];
-  graphs.forEach(g => {
-    const container = document.getElementById(g.id);
-    if (!container) return;
-    const nodes = Array.from(new Set([].concat(g.nodes || []))).map(n => ({ data: { id: n, label: n }}));
-    const edges = (g.edges || []).map((e, idx) => ({
-      data: { id: 'e'+idx, source: e[0], target: e[1], label: e[2] }
-    }));
-    cytoscape({
-      container,
-      elements: { nodes, edges },
-      style: [
-        { selector: 'node', style: { 'label': 'data(label)', 'font-size': 10, 'text-wrap': 'wrap', 'text-max-width': 120, 'background-color': '#4f81e1', 'color': '#fff', 'width': 22, 'height': 22 } },
-        { selector: 'edge', style: { 'curve-style': 'bezier', 'width': 1.5, 'line-color': '#888', 'target-arrow-color': '#888', 'target-arrow-shape': 'triangle', 'arrow-scale': 0.8, 'label': 'data(label)', 'font-size': 9, 'text-background-color': '#fff', 'text-background-opacity': 0.8, 'text-background-padding': 2 } }
-      ],
-      layout: { name: 'cose', idealEdgeLength: 120, nodeOverlap: 8, padding: 20 }
-    });
-  });
-})();
-</script>"#,
-    );
+    // Graph renderer is inlined so the report works offline and from file://.
+    out.push_str("<script>");
+    out.push_str(GRAPH_RENDERER_JS);
+    out.push_str("</script>");
 
     out.push_str("</body></html>");
     fs::write(path, out)
@@ -489,20 +652,15 @@ fn regex_reexport_star() -> &'static Regex {
 fn regex_reexport_named() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| {
-        Regex::new(r#"(?m)^\s*export\s+\{([^}]+)\}\s+from\s+["']([^"']+)["']"#).unwrap()
+        Regex::new(r#"(?m)^\s*export\s+(type\s+)?\{([^}]+)\}\s+from\s+["']([^"']+)["']"#).unwrap()
     })
 }
 
-fn regex_dynamic_import() -> &'static Regex {
-    static RE: OnceLock<Regex> = OnceLock::new();
-    RE.get_or_init(|| Regex::new(r#"import\s*\(\s*["']([^"']+)["']\s*\)"#).unwrap())
-}
-
 fn regex_export_named_decl() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| {
         Regex::new(
-            r#"(?m)^\s*export\s+(?:async\s+)?(?:function|const|let|var|class|interface|type|enum)\s+([A-Za-z0-9_.$]+)"#,
+            r#"(?m)^\s*export\s+(?:async\s+)?(function|const|let|var|class|interface|type|enum)\s+([A-Za-z0-9_.$]+)"#,
         )
         .unwrap()
     })
@@ -515,24 +673,16 @@ fn regex_export_default() -> &'static Regex {
 
 fn regex_export_brace() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
-    RE.get_or_init(|| Regex::new(r#"(?m)^\s*export\s+\{([^}]+)\}\s*;?"#).unwrap())
-}
-
-fn regex_safe_invoke() -> &'static Regex {
-    static RE: OnceLock<Regex> = OnceLock::new();
-    RE.get_or_init(|| Regex::new(r#"safeInvoke\(\s*["']([^"']+)["']"#).unwrap())
-}
-
-fn regex_invoke_snake() -> &'static Regex {
-    static RE: OnceLock<Regex> = OnceLock::new();
-    RE.get_or_init(|| Regex::new(r#"invokeSnake\(\s*["']([^"']+)["']"#).unwrap())
+    RE.get_or_init(|| Regex::new(r#"(?m)^\s*export\s+(type\s+)?\{([^}]+)\}"#).unwrap())
 }
 
 fn regex_tauri_command_fn() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| {
-        Regex::new(r#"(?m)#\s*\[\s*tauri::command[^\]]*\]\s*pub\s+async\s+fn\s+([A-Za-z0-9_]+)"#)
-            .unwrap()
+        Regex::new(
+            r#"(?m)#\s*\[\s*tauri::command([^\]]*)\]\s*(?:(?:#\s*\[[^\]]*\]|///[^\n]*)\s*)*(?:pub\s*(?:\([^)]*\))?\s+)?(?:async\s+)?fn\s+([A-Za-z0-9_]+)"#,
+        )
+        .unwrap()
     })
 }
 
@@ -549,6 +699,13 @@ fn regex_rust_use() -> &'static Regex {
     RE.get_or_init(|| Regex::new(r#"(?m)^\s*(?:pub\s*(?:\([^)]*\))?\s+)?use\s+([^;]+);"#).unwrap())
 }
 
+fn regex_rust_mod_decl() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?m)^\s*(?:pub\s*(?:\([^)]*\))?\s+)?mod\s+([A-Za-z0-9_]+)\s*;"#).unwrap()
+    })
+}
+
 fn regex_rust_pub_use() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| Regex::new(r#"(?m)^\s*pub\s*(?:\([^)]*\))?\s+use\s+([^;]+);"#).unwrap())
@@ -571,17 +728,17 @@ fn regex_rust_pub_const_like(kind: &str) -> Regex {
     Regex::new(&pattern).unwrap()
 }
 
-fn rust_pub_decl_regexes() -> &'static [Regex] {
-    static RE: OnceLock<Vec<Regex>> = OnceLock::new();
+fn rust_pub_decl_regexes() -> &'static [(Regex, ExportCategory)] {
+    static RE: OnceLock<Vec<(Regex, ExportCategory)>> = OnceLock::new();
     RE.get_or_init(|| {
         vec![
-            regex_rust_pub_item("fn"),
-            regex_rust_pub_item("struct"),
-            regex_rust_pub_item("enum"),
-            regex_rust_pub_item("trait"),
-            regex_rust_pub_item("type"),
-            regex_rust_pub_item("union"),
-            regex_rust_pub_item("mod"),
+            (regex_rust_pub_item("fn"), ExportCategory::Value),
+            (regex_rust_pub_item("struct"), ExportCategory::Type),
+            (regex_rust_pub_item("enum"), ExportCategory::Type),
+            (regex_rust_pub_item("trait"), ExportCategory::Type),
+            (regex_rust_pub_item("type"), ExportCategory::Type),
+            (regex_rust_pub_item("union"), ExportCategory::Type),
+            (regex_rust_pub_item("mod"), ExportCategory::Module),
         ]
     })
     .as_slice()
@@ -608,66 +765,69 @@ fn regex_py_dynamic_dunder() -> &'static Regex {
     RE.get_or_init(|| Regex::new(r#"__import__\(\s*["']([^"']+)["']"#).unwrap())
 }
 
-fn regex_py_all() -> &'static Regex {
+/// `importlib.import_module(name)` / `__import__(f"...")` with a non-literal argument.
+fn regex_py_dynamic_expr() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
-    RE.get_or_init(|| Regex::new(r#"(?s)__all__\s*=\s*\[([^\]]*)\]"#).unwrap())
+    RE.get_or_init(|| {
+        Regex::new(r#"(?:importlib\.import_module|__import__)\(\s*([^"'\s)][^,)]*)"#).unwrap()
+    })
 }
 
-fn regex_py_def() -> &'static Regex {
-    static RE: OnceLock<Regex> = OnceLock::new();
-    RE.get_or_init(|| Regex::new(r#"(?m)^\s*def\s+([A-Za-z_][A-Za-z0-9_]*)"#).unwrap())
-}
+const JS_RESOLVE_EXTS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs", "css"];
 
-fn regex_py_class() -> &'static Regex {
-    static RE: OnceLock<Regex> = OnceLock::new();
-    RE.get_or_init(|| Regex::new(r#"(?m)^\s*class\s+([A-Za-z_][A-Za-z0-9_]*)"#).unwrap())
+pub fn relative_to_root(path: &Path, root: &Path) -> Option<String> {
+    let canon = path.canonicalize().ok()?;
+    Some(
+        canon
+            .strip_prefix(root)
+            .map(|q| q.to_string_lossy().to_string())
+            .unwrap_or_else(|_| canon.to_string_lossy().to_string()),
+    )
 }
 
-fn resolve_reexport_target(
-    file_path: &Path,
-    root: &Path,
-    spec: &str,
-    exts: Option<&HashSet<String>>,
-) -> Option<String> {
-    if !spec.starts_with('.') {
-        return None;
+fn resolve_js_candidate(candidate: &Path, exts: Option<&HashSet<String>>) -> Option<PathBuf> {
+    if candidate.is_file() {
+        return Some(candidate.to_path_buf());
     }
-    let parent = file_path.parent()?;
-    let candidate = parent.join(spec);
-    if candidate.is_dir() {
-        return None;
+    let allowed: Vec<&str> = JS_RESOLVE_EXTS
+        .iter()
+        .copied()
+        .filter(|ext| exts.is_none_or(|set| set.contains(*ext)))
+        .collect();
+    // `./foo.service` keeps its dot segment, so append instead of replacing the extension.
+    for ext in &allowed {
+        let mut with_ext = candidate.as_os_str().to_owned();
+        with_ext.push(".");
+        with_ext.push(ext);
+        let with_ext = PathBuf::from(with_ext);
+        if with_ext.is_file() {
+            return Some(with_ext);
+        }
     }
-    if candidate.extension().is_none() {
-        if let Some(set) = exts {
-            for ext in set {
-                let with_ext = candidate.with_extension(ext);
-                if with_ext.exists() {
-                    return with_ext.canonicalize().ok().map(|p| {
-                        p.strip_prefix(root)
-                            .map(|q| q.to_string_lossy().to_string())
-                            .unwrap_or_else(|_| p.to_string_lossy().to_string())
-                    });
-                }
+    if candidate.is_dir() {
+        for ext in &allowed {
+            let index = candidate.join(format!("index.{ext}"));
+            if index.is_file() {
+                return Some(index);
             }
         }
     }
-    if candidate.exists() {
-        candidate.canonicalize().ok().map(|p| {
-            p.strip_prefix(root)
-                .map(|q| q.to_string_lossy().to_string())
-                .unwrap_or_else(|_| p.to_string_lossy().to_string())
-        })
-    } else {
-        None
+    None
+}
+
+fn resolve_python_candidate(joined: &Path) -> Option<PathBuf> {
+    let file = joined.with_extension("py");
+    if file.is_file() {
+        return Some(file);
     }
+    let init = joined.join("__init__.py");
+    if init.is_file() {
+        return Some(init);
+    }
+    None
 }
 
-fn resolve_python_relative(
-    module: &str,
-    file_path: &Path,
-    root: &Path,
-    exts: Option<&HashSet<String>>,
-) -> Option<String> {
+fn resolve_python_relative(module: &str, file_path: &Path, root: &Path) -> Option<String> {
     if !module.starts_with('.') {
         return None;
     }
@@ -693,55 +853,61 @@ fn resolve_python_relative(
         base.join(remainder)
     };
 
-    if joined.is_dir() {
-        return None;
-    }
+    resolve_python_candidate(&joined).and_then(|p| relative_to_root(&p, root))
+}
 
-    if joined.extension().is_none() {
-        if let Some(set) = exts {
-            for ext in set {
-                let candidate = joined.with_extension(ext);
-                if candidate.exists() {
-                    return candidate
-                        .canonicalize()
-                        .ok()
-                        .and_then(|p| {
-                            p.strip_prefix(root)
-                                .ok()
-                                .map(|q| q.to_string_lossy().to_string())
-                        })
-                        .or_else(|| {
-                            candidate
-                                .canonicalize()
-                                .ok()
-                                .map(|p| p.to_string_lossy().to_string())
-                        });
-                }
-            }
+/// Files that mark the directory of a Python project inside the root.
+const PYTHON_PROJECT_FILES: &[&str] = &[
+    "pyproject.toml",
+    "setup.py",
+    "setup.cfg",
+    "requirements.txt",
+    "requirements-dev.txt",
+];
+
+/// Directories an absolute Python import is looked up in, most specific first:
+/// the nearest project directory above the importing file (flat, then `src/`
+/// layout), the file's own directory (where a script runs from), then the root.
+pub fn python_search_bases(file_path: Option<&Path>, root: &Path) -> Vec<PathBuf> {
+    let mut bases = Vec::new();
+    if let Some(dir) = file_path.and_then(Path::parent) {
+        let project = dir
+            .ancestors()
+            .take_while(|d| d.starts_with(root))
+            .find(|d| PYTHON_PROJECT_FILES.iter().any(|f| d.join(f).is_file()));
+        if let Some(project) = project {
+            bases.push(project.to_path_buf());
+            bases.push(project.join("src"));
         }
+        bases.push(dir.to_path_buf());
     }
+    bases.push(root.to_path_buf());
+    bases.push(root.join("src"));
+    bases.dedup();
+    bases
+}
 
-    if joined.exists() {
-        joined
-            .canonicalize()
-            .ok()
-            .and_then(|p| {
-                p.strip_prefix(root)
-                    .ok()
-                    .map(|q| q.to_string_lossy().to_string())
-            })
-            .or_else(|| {
-                joined
-                    .canonicalize()
-                    .ok()
-                    .map(|p| p.to_string_lossy().to_string())
-            })
+/// Resolves `a.b.c` against the bases from [`python_search_bases`].
+pub fn resolve_python_absolute(module: &str, from: Option<&Path>, root: &Path) -> Option<String> {
+    if module.is_empty() || module.starts_with('.') {
+        return None;
+    }
+    let remainder = module.replace('.', "/");
+    python_search_bases(from, root)
+        .iter()
+        .find_map(|base| resolve_python_candidate(&base.join(&remainder)))
+        .and_then(|p| relative_to_root(&p, root))
+}
+
+fn resolve_python_import(module: &str, file_path: &Path, root: &Path) -> Option<String> {
+    if module.starts_with('.') {
+        resolve_python_relative(module, file_path, root)
     } else {
-        None
+        resolve_python_absolute(module, Some(file_path), root)
     }
 }
 
-fn resolve_js_relative(
+pub fn resolve_js_relative(
     file_path: &Path,
     root: &Path,
     spec: &str,
@@ -751,116 +917,175 @@ fn resolve_js_relative(
         return None;
     }
     let parent = file_path.parent()?;
-    let mut candidate = parent.join(spec);
-    if candidate.extension().is_none() {
-        if let Some(set) = exts {
-            for ext in set {
-                let with_ext = candidate.with_extension(ext);
-                if with_ext.exists() {
-                    candidate = with_ext;
-                    break;
-                }
-            }
-        }
+    let candidate = parent.join(spec);
+    resolve_js_candidate(&candidate, exts).and_then(|p| relative_to_root(&p, root))
+}
+
+fn resolve_css_import(file_path: &Path, root: &Path, spec: &str) -> Option<String> {
+    if spec.contains("://") || spec.starts_with("data:") {
+        return None;
     }
-    if candidate.exists() {
-        candidate
-            .canonicalize()
-            .ok()
-            .and_then(|p| {
-                p.strip_prefix(root)
-                    .ok()
-                    .map(|q| q.to_string_lossy().to_string())
-            })
-            .or_else(|| {
-                candidate
-                    .canonicalize()
-                    .ok()
-                    .map(|p| p.to_string_lossy().to_string())
-            })
+    let candidate = file_path.parent()?.join(spec);
+    if candidate.is_file() {
+        relative_to_root(&candidate, root)
     } else {
         None
     }
 }
 
+fn resolve_rust_mod(file_path: &Path, root: &Path, name: &str) -> Option<String> {
+    let parent = file_path.parent()?;
+    let stem = file_path.file_stem()?.to_str()?;
+    // `mod foo;` in main.rs/lib.rs/mod.rs lives next to the file; elsewhere in a
+    // directory named after the declaring module (falling back to siblings for bin targets).
+    let mut bases = Vec::new();
+    if !matches!(stem, "main" | "lib" | "mod") {
+        bases.push(parent.join(stem));
+    }
+    bases.push(parent.to_path_buf());
+    bases.iter().find_map(|base| {
+        [
+            base.join(format!("{name}.rs")),
+            base.join(name).join("mod.rs"),
+        ]
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+        .and_then(|candidate| relative_to_root(&candidate, root))
+    })
+}
+
 fn analyze_js_file(
     content: &str,
     path: &Path,
     root: &Path,
     extensions: Option<&HashSet<String>>,
+    invoke: &InvokeMatcher,
+    ipc: &IpcMatcher,
     relative: String,
 ) -> FileAnalysis {
     let mut imports = Vec::new();
-    let mut command_calls = Vec::new();
     for caps in regex_import().captures_iter(content) {
         let source = caps.get(2).map(|m| m.as_str()).unwrap_or("").to_string();
+        let (line, column) = caps
+            .get(2)
+            .map(|m| offset_to_line_col(content, m.start()))
+            .unwrap_or((1, 1));
+        let resolved = resolve_js_relative(path, root, &source, extensions);
+        let (type_only, symbols) = parse_js_import_clause(caps.get(1).map_or("", |m| m.as_str()));
         imports.push(ImportEntry {
             source,
             kind: ImportKind::Static,
+            resolved,
+            line,
+            column,
+            symbols,
+            type_only,
+            conditional: false,
         });
     }
     for caps in regex_side_effect_import().captures_iter(content) {
         let source = caps.get(1).map(|m| m.as_str()).unwrap_or("").to_string();
+        let (line, column) = caps
+            .get(1)
+            .map(|m| offset_to_line_col(content, m.start()))
+            .unwrap_or((1, 1));
+        let resolved = resolve_js_relative(path, root, &source, extensions);
         imports.push(ImportEntry {
             source,
             kind: ImportKind::SideEffect,
+            resolved,
+            line,
+            column,
+            symbols: Vec::new(),
+            type_only: false,
+            conditional: false,
         });
     }
 
-    for caps in regex_safe_invoke().captures_iter(content) {
-        if let Some(cmd) = caps.get(1) {
-            let line = offset_to_line(content, cmd.start());
-            command_calls.push(CommandRef {
-                name: cmd.as_str().to_string(),
-                line,
-            });
-        }
-    }
-    for caps in regex_invoke_snake().captures_iter(content) {
-        if let Some(cmd) = caps.get(1) {
-            let line = offset_to_line(content, cmd.start());
-            command_calls.push(CommandRef {
-                name: cmd.as_str().to_string(),
-                line,
-            });
-        }
-    }
+    let ipc_calls = ipc.renderer_calls(content);
+    // `window.electron.invoke("x")` is an Electron send, not a Tauri command.
+    let command_calls: Vec<CommandRef> = invoke
+        .command_calls(content)
+        .into_iter()
+        .filter(|c| {
+            !ipc_calls
+                .iter()
+                .any(|i| i.line == c.line && i.name == c.name)
+        })
+        .collect();
+    let (event_emits, event_listens) = ts_events(content);
+    let (route_defs, route_calls) = js_routes(content);
+    let ipc_handlers = main_handlers(content);
 
     let mut reexports = Vec::new();
     for caps in regex_reexport_star().captures_iter(content) {
         let source = caps.get(1).map(|m| m.as_str()).unwrap_or("").to_string();
-        let resolved = resolve_reexport_target(path, root, &source, extensions);
+        let (line, column) = caps
+            .get(1)
+            .map(|m| offset_to_line_col(content, m.start()))
+            .unwrap_or((1, 1));
+        let resolved = resolve_js_relative(path, root, &source, extensions);
         reexports.push(ReexportEntry {
             source,
             kind: ReexportKind::Star,
             resolved,
+            line,
+            column,
         });
     }
+    let mut reexported = Vec::new();
     for caps in regex_reexport_named().captures_iter(content) {
-        let raw_names = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-        let source = caps.get(2).map(|m| m.as_str()).unwrap_or("").to_string();
+        let type_only = caps.get(1).is_some();
+        let raw_names = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+        let source = caps.get(3).map(|m| m.as_str()).unwrap_or("").to_string();
+        let (line, column) = caps
+            .get(3)
+            .map(|m| offset_to_line_col(content, m.start()))
+            .unwrap_or((1, 1));
         let names = brace_list_to_names(raw_names);
-        let resolved = resolve_reexport_target(path, root, &source, extensions);
+        for (_, name, type_name) in &names {
+            reexported.push(ExportSymbol {
+                name: name.clone(),
+                kind: "reexport".to_string(),
+                category: if type_only || *type_name {
+                    ExportCategory::Type
+                } else {
+                    ExportCategory::Value
+                },
+                line,
+                column,
+            });
+        }
+        let resolved = resolve_js_relative(path, root, &source, extensions);
         reexports.push(ReexportEntry {
             source,
-            kind: ReexportKind::Named(names.clone()),
+            kind: ReexportKind::Named(
+                names
+                    .into_iter()
+                    .map(|(original, name, _)| (original, name))
+                    .collect(),
+            ),
             resolved,
+            line,
+            column,
         });
     }
 
-    let mut dynamic_imports = Vec::new();
-    for caps in regex_dynamic_import().captures_iter(content) {
-        let source = caps.get(1).map(|m| m.as_str()).unwrap_or("").to_string();
-        dynamic_imports.push(source);
-    }
+    let dynamic_imports = js_dynamic_imports(content, path, root, extensions);
 
     let mut exports = Vec::new();
     for caps in regex_export_named_decl().captures_iter(content) {
-        let name = caps.get(1).map(|m| m.as_str()).unwrap_or("").to_string();
-        if !name.is_empty() {
+        if let Some(m) = caps.get(2).filter(|m| !m.as_str().is_empty()) {
+            let (line, column) = offset_to_line_col(content, m.start());
             exports.push(ExportSymbol {
-                name,
+                name: m.as_str().to_string(),
                 kind: "decl".to_string(),
+                category: match &caps[1] {
+                    "interface" | "type" => ExportCategory::Type,
+                    _ => ExportCategory::Value,
+                },
+                line,
+                column,
             });
         }
     }
@@ -869,177 +1094,340 @@ fn analyze_js_file(
             .get(1)
             .map(|m| m.as_str().to_string())
             .unwrap_or_else(|| "default".to_string());
+        let (line, column) = offset_to_line_col(content, statement_start(caps.get(0).unwrap()));
         exports.push(ExportSymbol {
             name,
             kind: "default".to_string(),
+            category: ExportCategory::Default,
+            line,
+            column,
         });
     }
     for caps in regex_export_brace().captures_iter(content) {
-        let raw = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-        for name in brace_list_to_names(raw) {
+        let (Some(whole), Some(raw)) = (caps.get(0), caps.get(2)) else {
+            continue;
+        };
+        // `export { a } from "./x"` re-exports; the names are not declared here.
+        if content[whole.end()..].trim_start().starts_with("from") {
+            continue;
+        }
+        let type_only = caps.get(1).is_some();
+        for (_, name, type_name) in brace_list_to_names(raw.as_str()) {
+            let offset = raw.start() + raw.as_str().find(&name).unwrap_or(0);
+            let (line, column) = offset_to_line_col(content, offset);
             exports.push(ExportSymbol {
                 name,
                 kind: "named".to_string(),
+                category: if type_only || type_name {
+                    ExportCategory::Type
+                } else {
+                    ExportCategory::Value
+                },
+                line,
+                column,
             });
         }
     }
-    for re in &reexports {
-        if let ReexportKind::Named(names) = &re.kind {
-            for name in names {
-                exports.push(ExportSymbol {
-                    name: name.clone(),
-                    kind: "reexport".to_string(),
-                });
-            }
-        }
-    }
+    exports.extend(reexported);
 
     FileAnalysis {
         path: relative,
+        loc: 0,
         imports,
         reexports,
         dynamic_imports,
         exports,
         command_calls,
         command_handlers: Vec::new(),
+        command_registrations: Vec::new(),
+        event_emits,
+        event_listens,
+        ipc_calls,
+        ipc_handlers,
+        route_defs,
+        route_calls,
+        route_mounts: js_route_mounts(content),
+        diagnostics: Vec::new(),
+        clone_tokens: Vec::new(),
+        bridge_forwarders: Vec::new(),
+        path_roots: Vec::new(),
     }
 }
 
-fn analyze_css_file(content: &str, relative: String) -> FileAnalysis {
+fn analyze_css_file(content: &str, path: &Path, root: &Path, relative: String) -> FileAnalysis {
     let mut imports = Vec::new();
     for caps in regex_css_import().captures_iter(content) {
         let source = caps.get(1).map(|m| m.as_str()).unwrap_or("").to_string();
+        let (line, column) = caps
+            .get(1)
+            .map(|m| offset_to_line_col(content, m.start()))
+            .unwrap_or((1, 1));
+        let resolved = resolve_css_import(path, root, &source);
         imports.push(ImportEntry {
             source,
             kind: ImportKind::Static,
+            resolved,
+            line,
+            column,
+            symbols: Vec::new(),
+            type_only: false,
+            conditional: false,
         });
     }
 
     FileAnalysis {
         path: relative,
+        loc: 0,
         imports,
         reexports: Vec::new(),
         dynamic_imports: Vec::new(),
         exports: Vec::new(),
         command_calls: Vec::new(),
         command_handlers: Vec::new(),
+        command_registrations: Vec::new(),
+        event_emits: Vec::new(),
+        event_listens: Vec::new(),
+        ipc_calls: Vec::new(),
+        ipc_handlers: Vec::new(),
+        route_defs: Vec::new(),
+        route_calls: Vec::new(),
+        route_mounts: Vec::new(),
+        diagnostics: Vec::new(),
+        clone_tokens: Vec::new(),
+        bridge_forwarders: Vec::new(),
+        path_roots: Vec::new(),
     }
 }
 
-fn analyze_py_file(
-    content: &str,
-    path: &Path,
-    root: &Path,
-    extensions: Option<&HashSet<String>>,
-    relative: String,
-) -> FileAnalysis {
+fn analyze_py_file(content: &str, path: &Path, root: &Path, relative: String) -> FileAnalysis {
     let mut imports = Vec::new();
     let mut reexports = Vec::new();
     let mut dynamic_imports = Vec::new();
     let mut exports = Vec::new();
 
-    for line in content.lines() {
-        let without_comment = line.split('#').next().unwrap_or("").trim_end();
-        let trimmed = without_comment.trim_start();
-        if let Some(rest) = trimmed.strip_prefix("import ") {
-            for part in rest.split(',') {
-                let mut name = part.trim();
-                if let Some((lhs, _)) = name.split_once(" as ") {
-                    name = lhs.trim();
-                }
-                if !name.is_empty() {
-                    imports.push(ImportEntry {
-                        source: name.to_string(),
-                        kind: ImportKind::Static,
-                    });
-                }
+    let mut all_exports = Vec::new();
+    let mut definitions = Vec::new();
+    let lines = logical_lines(content);
+    // Enclosing block headers (indent, kind) for the current statement.
+    let mut blocks: Vec<(usize, PyBlock)> = Vec::new();
+    for (idx, logical) in lines.iter().enumerate() {
+        while blocks
+            .last()
+            .is_some_and(|(indent, _)| *indent >= logical.indent)
+        {
+            blocks.pop();
+        }
+        let trimmed = logical.text.as_str();
+        let line_no = logical.line;
+        let column = logical.indent + 1;
+        let module_level = !blocks.iter().any(|(_, b)| *b == PyBlock::Scope);
+        let type_only = blocks.iter().any(|(_, b)| *b == PyBlock::TypeChecking);
+        let conditional = type_only || blocks.iter().any(|(_, b)| *b == PyBlock::ImportGuard);
+        if let Some(block) = classify_block(&lines, idx) {
+            blocks.push((logical.indent, block));
+        }
+        // Docstrings and other bare string statements.
+        if trimmed
+            .trim_start_matches(['r', 'b', 'u', 'f', 'R', 'B', 'U', 'F'])
+            .starts_with(['"', '\''])
+        {
+            continue;
+        }
+
+        for caps in regex_py_dynamic_importlib()
+            .captures_iter(trimmed)
+            .chain(regex_py_dynamic_dunder().captures_iter(trimmed))
+        {
+            if let Some(m) = caps.get(1) {
+                dynamic_imports.push(DynamicImport {
+                    source: m.as_str().to_string(),
+                    kind: DynamicImportKind::Import,
+                    resolved: resolve_python_import(m.as_str(), path, root)
+                        .into_iter()
+                        .collect(),
+                    unresolvable: false,
+                    line: line_no,
+                    column,
+                });
+            }
+        }
+        for caps in regex_py_dynamic_expr().captures_iter(trimmed) {
+            if let Some(m) = caps.get(1) {
+                dynamic_imports.push(DynamicImport {
+                    source: m.as_str().trim().to_string(),
+                    kind: DynamicImportKind::Import,
+                    resolved: Vec::new(),
+                    unresolvable: true,
+                    line: line_no,
+                    column,
+                });
+            }
+        }
+
+        if module_level {
+            if let Some(names) = dunder_all_names(trimmed) {
+                all_exports.extend(names.into_iter().map(|name| ExportSymbol {
+                    name,
+                    kind: "__all__".to_string(),
+                    category: ExportCategory::Value,
+                    line: line_no,
+                    column,
+                }));
+                continue;
+            }
+            if let Some((name, kind)) = definition_name(trimmed) {
+                if !name.starts_with('_') {
+                    definitions.push(ExportSymbol {
+                        name: name.to_string(),
+                        kind: kind.to_string(),
+                        category: ExportCategory::Value,
+                        line: line_no,
+                        column: column + trimmed.find(name).unwrap_or(0),
+                    });
+                }
+                continue;
+            }
+        }
+
+        if let Some(rest) = trimmed.strip_prefix("import ") {
+            for part in rest.split(',') {
+                let (name, alias) = split_py_alias(part);
+                if !name.is_empty() {
+                    imports.push(ImportEntry {
+                        source: name.to_string(),
+                        kind: ImportKind::Static,
+                        resolved: resolve_python_import(name, path, root),
+                        line: line_no,
+                        column,
+                        symbols: vec![ImportSymbol {
+                            name: "*".to_string(),
+                            alias: alias.map(str::to_string),
+                            is_default: false,
+                            is_namespace: true,
+                            type_only,
+                        }],
+                        type_only,
+                        conditional,
+                    });
+                }
             }
         } else if let Some(rest) = trimmed.strip_prefix("from ") {
-            if let Some((module, names_raw)) = rest.split_once(" import ") {
-                let module = module.trim().trim_end_matches('.');
-                let names_clean = names_raw.trim().trim_matches('(').trim_matches(')');
+            if let Some((raw_module, names_raw)) = rest.split_once(" import ") {
+                let raw_module = raw_module.trim();
+                let module = raw_module.trim_end_matches('.');
+                let names_clean = names_raw.trim();
+                let names_clean = names_clean
+                    .strip_prefix('(')
+                    .and_then(|n| n.strip_suffix(')'))
+                    .unwrap_or(names_clean);
                 let names_clean = names_clean.split('#').next().unwrap_or("").trim();
+                let symbols: Vec<ImportSymbol> = if names_clean == "*" {
+                    vec![ImportSymbol {
+                        name: "*".to_string(),
+                        alias: None,
+                        is_default: false,
+                        is_namespace: true,
+                        type_only,
+                    }]
+                } else {
+                    names_clean
+                        .split(',')
+                        .map(split_py_alias)
+                        .filter(|(name, _)| !name.is_empty())
+                        .map(|(name, alias)| ImportSymbol {
+                            name: name.to_string(),
+                            alias: alias.map(str::to_string),
+                            is_default: false,
+                            is_namespace: false,
+                            type_only,
+                        })
+                        .collect()
+                };
                 if !module.is_empty() {
                     imports.push(ImportEntry {
                         source: module.to_string(),
                         kind: ImportKind::Static,
+                        resolved: resolve_python_import(module, path, root),
+                        line: line_no,
+                        column,
+                        symbols: symbols.clone(),
+                        type_only,
+                        conditional,
                     });
                 }
+                if names_clean != "*" {
+                    // `from pkg import sub` may pull in a submodule rather than a symbol.
+                    for symbol in &symbols {
+                        let submodule = if raw_module.ends_with('.') {
+                            format!("{}{}", raw_module, symbol.name)
+                        } else {
+                            format!("{}.{}", raw_module, symbol.name)
+                        };
+                        if let Some(resolved) = resolve_python_import(&submodule, path, root) {
+                            imports.push(ImportEntry {
+                                source: submodule,
+                                kind: ImportKind::Static,
+                                resolved: Some(resolved),
+                                line: line_no,
+                                column,
+                                symbols: vec![ImportSymbol {
+                                    name: "*".to_string(),
+                                    alias: Some(
+                                        symbol.alias.clone().unwrap_or(symbol.name.clone()),
+                                    ),
+                                    is_default: false,
+                                    is_namespace: true,
+                                    type_only,
+                                }],
+                                type_only,
+                                conditional,
+                            });
+                        }
+                    }
+                }
                 if names_clean == "*" {
-                    let resolved = resolve_python_relative(module, path, root, extensions);
+                    let resolved = resolve_python_relative(module, path, root);
                     reexports.push(ReexportEntry {
                         source: module.to_string(),
                         kind: ReexportKind::Star,
                         resolved,
+                        line: line_no,
+                        column,
                     });
                 }
             }
         }
     }
 
-    for caps in regex_py_dynamic_importlib().captures_iter(content) {
-        if let Some(m) = caps.get(1) {
-            dynamic_imports.push(m.as_str().to_string());
-        }
-    }
-    for caps in regex_py_dynamic_dunder().captures_iter(content) {
-        if let Some(m) = caps.get(1) {
-            dynamic_imports.push(m.as_str().to_string());
-        }
-    }
-
-    for caps in regex_py_all().captures_iter(content) {
-        let body = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-        for item in body.split(',') {
-            let trimmed = item.trim();
-            let name = trimmed
-                .trim_matches(|c| c == '\'' || c == '"')
-                .trim()
-                .to_string();
-            if !name.is_empty() {
-                exports.push(ExportSymbol {
-                    name,
-                    kind: "__all__".to_string(),
-                });
-            }
-        }
-    }
-
-    for caps in regex_py_def().captures_iter(content) {
-        if let Some(name) = caps.get(1) {
-            let n = name.as_str();
-            if !n.starts_with('_') {
-                exports.push(ExportSymbol {
-                    name: n.to_string(),
-                    kind: "def".to_string(),
-                });
-            }
-        }
-    }
-    for caps in regex_py_class().captures_iter(content) {
-        if let Some(name) = caps.get(1) {
-            let n = name.as_str();
-            if !n.starts_with('_') {
-                exports.push(ExportSymbol {
-                    name: n.to_string(),
-                    kind: "class".to_string(),
-                });
-            }
-        }
-    }
+    exports.extend(all_exports);
+    exports.extend(definitions);
 
     FileAnalysis {
         path: relative,
+        loc: 0,
         imports,
         reexports,
         dynamic_imports,
         exports,
         command_calls: Vec::new(),
         command_handlers: Vec::new(),
+        command_registrations: Vec::new(),
+        event_emits: Vec::new(),
+        event_listens: Vec::new(),
+        ipc_calls: Vec::new(),
+        ipc_handlers: Vec::new(),
+        route_defs: py_routes(content),
+        route_calls: Vec::new(),
+        route_mounts: py_route_mounts(content),
+        diagnostics: Vec::new(),
+        clone_tokens: Vec::new(),
+        bridge_forwarders: Vec::new(),
+        path_roots: Vec::new(),
     }
 }
 
-fn parse_rust_brace_names(raw: &str) -> Vec<String> {
+/// `(original, exported)` names of a `pub use a::{b, c as d}` list.
+fn parse_rust_brace_names(raw: &str) -> Vec<(String, String)> {
     raw.split(',')
         .filter_map(|item| {
             let trimmed = item.trim();
@@ -1049,23 +1437,155 @@ fn parse_rust_brace_names(raw: &str) -> Vec<String> {
             if trimmed == "self" {
                 return None;
             }
-            if let Some((_, alias)) = trimmed.split_once(" as ") {
-                Some(alias.trim().to_string())
-            } else {
-                Some(trimmed.to_string())
-            }
+            let (original, name) = match trimmed.split_once(" as ") {
+                Some((original, alias)) => (original.trim(), alias.trim()),
+                None => (trimmed, trimmed),
+            };
+            let original = original.rsplit("::").next().unwrap_or(original);
+            Some((original.to_string(), name.to_string()))
         })
         .collect()
 }
 
-fn analyze_rust_file(content: &str, relative: String) -> FileAnalysis {
+fn analyze_rust_file(content: &str, path: &Path, root: &Path, relative: String) -> FileAnalysis {
+    let syntax = match parse_rust(content) {
+        Ok(syntax) => syntax,
+        Err(err) => {
+            let mut analysis = analyze_rust_file_regex(content, path, root, relative);
+            analysis.diagnostics.push(FileDiagnostic {
+                path: analysis.path.clone(),
+                kind: DiagnosticKind::ParseFallback,
+                message: format!(
+                    "Rust parser failed at line {}: {}; used the regex scanner",
+                    err.span().start().line,
+                    err
+                ),
+            });
+            return analysis;
+        }
+    };
+    let mut imports = Vec::new();
+    let mut reexports = Vec::new();
+    let mut exports = Vec::new();
+    for item in &syntax.uses {
+        imports.push(ImportEntry {
+            source: item.source.clone(),
+            kind: ImportKind::Static,
+            resolved: None,
+            line: item.line,
+            column: item.column,
+            symbols: item.symbols.clone(),
+            type_only: false,
+            conditional: false,
+        });
+        if !item.reexported {
+            continue;
+        }
+        let (names, globs) = reexport_parts(item);
+        if !names.is_empty() {
+            exports.extend(names.iter().map(|(_, name)| ExportSymbol {
+                name: name.clone(),
+                kind: "reexport".to_string(),
+                category: ExportCategory::Value,
+                line: item.line,
+                column: item.column,
+            }));
+            reexports.push(ReexportEntry {
+                source: item.source.clone(),
+                kind: ReexportKind::Named(names),
+                resolved: None,
+                line: item.line,
+                column: item.column,
+            });
+        }
+        for glob in globs {
+            reexports.push(ReexportEntry {
+                source: glob,
+                kind: ReexportKind::Star,
+                resolved: None,
+                line: item.line,
+                column: item.column,
+            });
+        }
+    }
+    for module in &syntax.mods {
+        imports.push(ImportEntry {
+            source: module.name.clone(),
+            kind: ImportKind::Module,
+            resolved: resolve_rust_mod(path, root, &module.name),
+            line: module.line,
+            column: module.column,
+            symbols: Vec::new(),
+            type_only: false,
+            conditional: module.test_only,
+        });
+    }
+    exports.extend(syntax.exports);
+
+    let (event_emits, event_listens) = rust_events(content, Some(&syntax.tree));
+    FileAnalysis {
+        path: relative,
+        loc: 0,
+        imports,
+        reexports,
+        dynamic_imports: Vec::new(),
+        exports,
+        command_calls: Vec::new(),
+        command_handlers: syntax.commands,
+        command_registrations: handler_registrations(content, Some(&syntax.tree)),
+        event_emits,
+        event_listens,
+        ipc_calls: Vec::new(),
+        ipc_handlers: Vec::new(),
+        route_defs: rust_routes(content, Some(&syntax.tree)),
+        route_calls: Vec::new(),
+        route_mounts: rust_route_mounts(content, Some(&syntax.tree)),
+        diagnostics: Vec::new(),
+        clone_tokens: Vec::new(),
+        bridge_forwarders: Vec::new(),
+        path_roots: path_roots(content),
+    }
+}
+
+/// Regex scanner used when `syn` cannot parse the file (e.g. unfinished code).
+fn analyze_rust_file_regex(
+    content: &str,
+    path: &Path,
+    root: &Path,
+    relative: String,
+) -> FileAnalysis {
     let mut imports = Vec::new();
     for caps in regex_rust_use().captures_iter(content) {
-        let source = caps.get(1).map(|m| m.as_str()).unwrap_or("").trim();
+        let Some(m) = caps.get(1) else {
+            continue;
+        };
+        let source = m.as_str().trim();
         if !source.is_empty() {
+            let (line, column) = offset_to_line_col(content, m.start());
             imports.push(ImportEntry {
                 source: source.to_string(),
                 kind: ImportKind::Static,
+                resolved: None,
+                line,
+                column,
+                symbols: Vec::new(),
+                type_only: false,
+                conditional: false,
+            });
+        }
+    }
+    for caps in regex_rust_mod_decl().captures_iter(content) {
+        if let Some(name) = caps.get(1) {
+            let (line, column) = offset_to_line_col(content, name.start());
+            imports.push(ImportEntry {
+                source: name.as_str().to_string(),
+                kind: ImportKind::Module,
+                resolved: resolve_rust_mod(path, root, name.as_str()),
+                line,
+                column,
+                symbols: Vec::new(),
+                type_only: false,
+                conditional: false,
             });
         }
     }
@@ -1074,10 +1594,14 @@ fn analyze_rust_file(content: &str, relative: String) -> FileAnalysis {
     let mut exports = Vec::new();
 
     for caps in regex_rust_pub_use().captures_iter(content) {
-        let raw = caps.get(1).map(|m| m.as_str()).unwrap_or("").trim();
+        let Some(m) = caps.get(1) else {
+            continue;
+        };
+        let raw = m.as_str().trim();
         if raw.is_empty() {
             continue;
         }
+        let (line, column) = offset_to_line_col(content, m.start());
 
         if raw.contains('{') && raw.contains('}') {
             let mut parts = raw.splitn(2, '{');
@@ -1088,11 +1612,16 @@ fn analyze_rust_file(content: &str, relative: String) -> FileAnalysis {
                 source: raw.to_string(),
                 kind: ReexportKind::Named(names.clone()),
                 resolved: None,
+                line,
+                column,
             });
-            for name in names {
+            for (_, name) in names {
                 exports.push(ExportSymbol {
                     name,
                     kind: "reexport".to_string(),
+                    category: ExportCategory::Value,
+                    line,
+                    column,
                 });
             }
             let _ = prefix; // prefix retained for future resolution
@@ -1101,6 +1630,8 @@ fn analyze_rust_file(content: &str, relative: String) -> FileAnalysis {
                 source: raw.to_string(),
                 kind: ReexportKind::Star,
                 resolved: None,
+                line,
+                column,
             });
         } else {
             // pub use foo::bar as Baz;
@@ -1115,23 +1646,40 @@ fn analyze_rust_file(content: &str, relative: String) -> FileAnalysis {
 
             reexports.push(ReexportEntry {
                 source: path_part.to_string(),
-                kind: ReexportKind::Named(vec![export_name.to_string()]),
+                kind: ReexportKind::Named(vec![(
+                    path_part
+                        .rsplit("::")
+                        .next()
+                        .unwrap_or(path_part)
+                        .trim()
+                        .to_string(),
+                    export_name.to_string(),
+                )]),
                 resolved: None,
+                line,
+                column,
             });
             exports.push(ExportSymbol {
                 name: export_name.to_string(),
                 kind: "reexport".to_string(),
+                category: ExportCategory::Value,
+                line,
+                column,
             });
         }
     }
 
     // public items
-    for regex in rust_pub_decl_regexes() {
+    for (regex, category) in rust_pub_decl_regexes() {
         for caps in regex.captures_iter(content) {
             if let Some(name) = caps.get(1) {
+                let (line, column) = offset_to_line_col(content, name.start());
                 exports.push(ExportSymbol {
                     name: name.as_str().to_string(),
                     kind: "decl".to_string(),
+                    category: *category,
+                    line,
+                    column,
                 });
             }
         }
@@ -1140,105 +1688,391 @@ fn analyze_rust_file(content: &str, relative: String) -> FileAnalysis {
     for regex in rust_pub_const_regexes() {
         for caps in regex.captures_iter(content) {
             if let Some(name) = caps.get(1) {
+                let (line, column) = offset_to_line_col(content, name.start());
                 exports.push(ExportSymbol {
                     name: name.as_str().to_string(),
                     kind: "decl".to_string(),
+                    category: ExportCategory::Value,
+                    line,
+                    column,
                 });
             }
         }
     }
 
+    let (event_emits, event_listens) = rust_events(content, None);
+    FileAnalysis {
+        path: relative,
+        loc: 0,
+        imports,
+        reexports,
+        dynamic_imports: Vec::new(),
+        exports,
+        command_calls: Vec::new(),
+        command_handlers: rust_command_handlers(content),
+        command_registrations: handler_registrations(content, None),
+        event_emits,
+        event_listens,
+        ipc_calls: Vec::new(),
+        ipc_handlers: Vec::new(),
+        route_defs: rust_routes(content, None),
+        route_calls: Vec::new(),
+        route_mounts: rust_route_mounts(content, None),
+        diagnostics: Vec::new(),
+        clone_tokens: Vec::new(),
+        bridge_forwarders: Vec::new(),
+        path_roots: path_roots(content),
+    }
+}
+
+fn rust_command_handlers(content: &str) -> Vec<CommandRef> {
     let mut command_handlers = Vec::new();
     for caps in regex_tauri_command_fn().captures_iter(content) {
-        if let Some(name) = caps.get(1) {
+        if let Some(name) = caps.get(2) {
             let line = offset_to_line(content, name.start());
+            let rename = command_rename(&caps[1]);
             command_handlers.push(CommandRef {
-                name: name.as_str().to_string(),
+                name: rename.clone().unwrap_or_else(|| name.as_str().to_string()),
                 line,
+                rust_name: rename.map(|_| name.as_str().to_string()),
+                args: Some(handler_args_from_source(
+                    &content[name.end()..],
+                    command_rename_all(&caps[1]).as_deref(),
+                )),
             });
         }
     }
+    command_handlers
+}
 
-    FileAnalysis {
-        path: relative,
-        imports,
-        reexports,
-        dynamic_imports: Vec::new(),
-        exports,
-        command_calls: Vec::new(),
-        command_handlers,
+/// Files above this size are skipped; they are almost always generated or
+/// bundled, and scanning them would dominate the run.
+const MAX_ANALYZED_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// A file read for analysis; the same bytes are hashed for the cache.
+struct SourceFile {
+    bytes: Vec<u8>,
+    /// Taken before reading.
+    stamp: Option<FileStamp>,
+}
+
+fn read_source(path: &Path, relative: &str) -> Result<SourceFile, FileDiagnostic> {
+    let diagnostic = |kind, message: String| FileDiagnostic {
+        path: relative.to_string(),
+        kind,
+        message,
+    };
+    let meta = fs::metadata(path)
+        .map_err(|err| diagnostic(DiagnosticKind::Unreadable, err.to_string()))?;
+    if meta.len() > MAX_ANALYZED_FILE_BYTES {
+        return Err(diagnostic(
+            DiagnosticKind::TooLarge,
+            format!(
+                "{} bytes, over the {} byte limit; skipped",
+                meta.len(),
+                MAX_ANALYZED_FILE_BYTES
+            ),
+        ));
     }
+    let bytes =
+        fs::read(path).map_err(|err| diagnostic(DiagnosticKind::Unreadable, err.to_string()))?;
+    Ok(SourceFile {
+        bytes,
+        stamp: FileStamp::of(&meta),
+    })
+}
+
+fn file_ext(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|s| s.to_lowercase())
+        .unwrap_or_default()
 }
 
+/// Analyses one file read by `read_source`. Files that cannot be read at all
+/// never get here; recoverable problems are recorded in
+/// `FileAnalysis::diagnostics`.
 fn analyze_file(
     path: &Path,
     root: &Path,
+    source: &SourceFile,
     extensions: Option<&HashSet<String>>,
-) -> io::Result<FileAnalysis> {
-    let content = std::fs::read_to_string(path)?;
+    invoke: &InvokeMatcher,
+    ipc: &IpcMatcher,
+    clones: bool,
+) -> FileAnalysis {
     let relative = path
         .strip_prefix(root)
         .unwrap_or(path)
         .to_string_lossy()
         .to_string();
-    let ext = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|s| s.to_lowercase())
-        .unwrap_or_default();
+    let content = String::from_utf8_lossy(&source.bytes);
+    let encoding_error = std::str::from_utf8(&source.bytes).err().map(|err| {
+        let line = content[..err.valid_up_to()].matches('\n').count() + 1;
+        FileDiagnostic {
+            path: relative.clone(),
+            kind: DiagnosticKind::InvalidEncoding,
+            message: format!(
+                "invalid UTF-8 at line {}; analysed with invalid bytes replaced",
+                line
+            ),
+        }
+    });
+    let ext = file_ext(path);
 
-    let analysis = match ext.as_str() {
-        "rs" => analyze_rust_file(&content, relative),
-        "css" => analyze_css_file(&content, relative),
-        "py" => analyze_py_file(&content, path, root, extensions, relative),
-        _ => analyze_js_file(&content, path, root, extensions, relative),
+    let mut analysis = match ext.as_str() {
+        "rs" => analyze_rust_file(&content, path, root, relative),
+        "css" => analyze_css_file(&content, path, root, relative),
+        "py" => analyze_py_file(&content, path, root, relative),
+        _ => analyze_js_file(&content, path, root, extensions, invoke, ipc, relative),
     };
+    analysis.loc = content.lines().count();
+    analysis.diagnostics.extend(encoding_error);
+    if clones {
+        analysis.clone_tokens = clone_tokens(&content, &ext);
+    }
+    analysis.bridge_forwarders = preload_bridge(&ext, &content);
+
+    analysis
+}
 
-    Ok(analysis)
+/// Applies `f` to every item on all available cores; results keep the
+/// order of `items`.
+fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut slots: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(idx) else {
+                            break;
+                        };
+                        done.push((idx, f(item)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        for handle in handles {
+            for (idx, result) in handle.join().expect("analysis worker panicked") {
+                slots[idx] = Some(result);
+            }
+        }
+    });
+    slots
+        .into_iter()
+        .map(|slot| slot.expect("every item is processed"))
+        .collect()
 }
 
-fn is_dev_file(path: &str) -> bool {
+pub fn is_dev_file(path: &str) -> bool {
     path.contains("__tests__")
         || path.contains("stories")
         || path.contains(".stories.")
         || path.contains("story.")
 }
 
-pub fn run_import_analyzer(root_list: &[PathBuf], parsed: &ParsedArgs) -> io::Result<()> {
-    let mut json_results = Vec::new();
-    let mut report_sections: Vec<ReportSection> = Vec::new();
-    let mut server_handle = None;
+/// Analyses `files` under `root`. Cache hits are taken first; the remaining
+/// files are read and analysed in parallel and merged back in file order, so
+/// results do not depend on scheduling. Files that could not be analysed at
+/// all come back as diagnostics, next to those recorded per file.
+fn analyze_files(
+    root: &Path,
+    files: &[PathBuf],
+    options: &Options,
+    invoke_wrappers: &[String],
+    parsed: &ParsedArgs,
+) -> (Vec<FileAnalysis>, Vec<FileDiagnostic>) {
+    let invoke_matcher = InvokeMatcher::new(invoke_wrappers);
+    let relative_paths: Vec<String> = files
+        .iter()
+        .map(|file| {
+            file.strip_prefix(root)
+                .unwrap_or(file)
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    let cache_base = cache_base(parsed.cache_dir.as_deref()).filter(|_| !parsed.no_cache);
+    let mut cache = match cache_base {
+        Some(base) => {
+            let mut exts: Vec<String> = options.extensions.iter().flatten().cloned().collect();
+            exts.sort();
+            let mut parts = vec![
+                exts.join(","),
+                invoke_wrappers.join(","),
+                format!("clones={}", parsed.clones),
+            ];
+            parts.extend(manifest_hashes(root, files));
+            AnalysisCache::load(&base, root, fingerprint(&parts), &relative_paths)
+        }
+        None => AnalysisCache::disabled(),
+    };
+    let mut cached: Vec<Option<FileAnalysis>> = files
+        .iter()
+        .zip(&relative_paths)
+        .map(|(file, relative)| cache.lookup(file, relative))
+        .collect();
+    let read = |idx: &usize| read_source(&files[*idx], &relative_paths[*idx]);
+    let mut sources: Vec<Option<Result<SourceFile, FileDiagnostic>>> =
+        files.iter().map(|_| None).collect();
+    let uncached: Vec<usize> = (0..files.len()).filter(|&i| cached[i].is_none()).collect();
+    for (idx, source) in uncached.iter().zip(parallel_map(&uncached, read)) {
+        sources[*idx] = Some(source);
+    }
 
-    let mut ignore_exact: HashSet<String> = HashSet::new();
-    let mut ignore_prefixes: Vec<String> = Vec::new();
+    // Preload scripts define the bridge wrappers renderer files call; they
+    // come from cached analyses or the sources just read.
+    let bridge: Vec<(String, String)> = (0..files.len())
+        .flat_map(|idx| match (&cached[idx], &sources[idx]) {
+            (Some(analysis), _) => analysis.bridge_forwarders.clone(),
+            (None, Some(Ok(source))) => preload_bridge(
+                &file_ext(&files[idx]),
+                &String::from_utf8_lossy(&source.bytes),
+            ),
+            _ => Vec::new(),
+        })
+        .collect();
+    if cache.update_bridge(&bridge) {
+        // Cached renderer analyses were made with other wrappers.
+        let stale: Vec<usize> = (0..files.len())
+            .filter(|&idx| {
+                cached[idx].is_some()
+                    && !matches!(file_ext(&files[idx]).as_str(), "rs" | "css" | "py")
+            })
+            .collect();
+        for (idx, source) in stale.iter().zip(parallel_map(&stale, read)) {
+            cache.evict(&relative_paths[*idx]);
+            cached[*idx] = None;
+            sources[*idx] = Some(source);
+        }
+    }
+    let ipc_matcher = IpcMatcher::new(&bridge);
 
-    if let Some(preset_name) = parsed.ignore_symbols_preset.as_deref() {
-        if let Some(set) = preset_ignore_symbols(preset_name) {
-            for s in set {
-                if s.ends_with('*') {
-                    ignore_prefixes.push(s.trim_end_matches('*').to_string());
-                } else {
-                    ignore_exact.insert(s);
+    let readable: Vec<(usize, &SourceFile)> = sources
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, source)| match source {
+            Some(Ok(source)) => Some((idx, source)),
+            _ => None,
+        })
+        .collect();
+    let mut fresh = parallel_map(&readable, |(idx, source)| {
+        analyze_file(
+            &files[*idx],
+            root,
+            source,
+            options.extensions.as_ref(),
+            &invoke_matcher,
+            &ipc_matcher,
+            parsed.clones,
+        )
+    })
+    .into_iter();
+
+    let mut analyses = Vec::new();
+    let mut diagnostics = Vec::new();
+    for idx in 0..files.len() {
+        let analysis = match cached[idx].take() {
+            Some(analysis) => analysis,
+            None => match sources[idx].take().expect("uncached files are read") {
+                Ok(source) => {
+                    let analysis = fresh.next().expect("one analysis per readable file");
+                    cache.store(&relative_paths[idx], source.stamp, &source.bytes, &analysis);
+                    analysis
                 }
-            }
-        } else {
-            eprintln!(
-                "[loctree][warn] unknown --ignore-symbols-preset '{}', ignoring",
-                preset_name
-            );
-        }
+                Err(diagnostic) => {
+                    diagnostics.push(diagnostic);
+                    continue;
+                }
+            },
+        };
+        diagnostics.extend(analysis.diagnostics.iter().cloned());
+        analyses.push(analysis);
+    }
+    cache.save();
+    if parsed.verbose {
+        cache.print_stats();
     }
+    (analyses, diagnostics)
+}
 
-    if let Some(user_syms) = parsed.ignore_symbols.clone() {
-        for s in user_syms {
-            let lc = s.to_lowercase();
-            if lc.ends_with('*') {
-                ignore_prefixes.push(lc.trim_end_matches('*').to_string());
-            } else {
-                ignore_exact.insert(lc);
+/// Resolved edges between analysed files: `(from, to, kind)` with kind
+/// `import`, `reexport` or `dynamic`.
+fn file_edges(analyses: &[FileAnalysis]) -> Vec<(String, String, String)> {
+    let mut edges = Vec::new();
+    for analysis in analyses {
+        for re in &analysis.reexports {
+            if let Some(target) = &re.resolved {
+                edges.push((
+                    analysis.path.clone(),
+                    target.clone(),
+                    "reexport".to_string(),
+                ));
+            }
+        }
+        for dynamic in &analysis.dynamic_imports {
+            for target in &dynamic.resolved {
+                edges.push((analysis.path.clone(), target.clone(), "dynamic".to_string()));
+            }
+        }
+        for imp in &analysis.imports {
+            if let Some(target) = &imp.resolved {
+                edges.push((
+                    analysis.path.clone(),
+                    target.clone(),
+                    match imp.kind {
+                        ImportKind::Static | ImportKind::SideEffect | ImportKind::Module => {
+                            "import".to_string()
+                        }
+                    },
+                ));
             }
         }
     }
+    edges
+}
+
+/// Per-file section of the JSON report.
+fn file_json(a: &FileAnalysis) -> serde_json::Value {
+    json!({
+        "path": a.path,
+        "loc": a.loc,
+        "imports": a.imports.iter().map(|i| json!({"source": i.source, "kind": match i.kind { ImportKind::Static => "static", ImportKind::SideEffect => "side-effect", ImportKind::Module => "mod" }, "resolved": i.resolved, "line": i.line, "column": i.column, "typeOnly": i.type_only, "conditional": i.conditional, "symbols": i.symbols.iter().map(|sym| json!({"name": sym.name, "alias": sym.alias, "default": sym.is_default, "namespace": sym.is_namespace, "typeOnly": sym.type_only})).collect::<Vec<_>>()})).collect::<Vec<_>>(),
+        "reexports": a.reexports.iter().map(|r| {
+            match &r.kind {
+                ReexportKind::Star => json!({"source": r.source, "kind": "star", "resolved": r.resolved, "line": r.line, "column": r.column}),
+                ReexportKind::Named(names) => json!({"source": r.source, "kind": "named", "names": names.iter().map(|(_, name)| name).collect::<Vec<_>>(), "symbols": names.iter().map(|(original, name)| json!({"name": original, "alias": (original != name).then_some(name)})).collect::<Vec<_>>(), "resolved": r.resolved, "line": r.line, "column": r.column})
+            }
+        }).collect::<Vec<_>>(),
+        "dynamicImports": a.dynamic_imports.iter().map(dynamic_import_json).collect::<Vec<_>>(),
+        "exports": a.exports.iter().map(|e| json!({"name": e.name, "kind": e.kind, "line": e.line, "column": e.column})).collect::<Vec<_>>(),
+        "commandCalls": a.command_calls.iter().map(|c| json!({"name": c.name, "line": c.line})).collect::<Vec<_>>(),
+        "commandHandlers": a.command_handlers.iter().map(|c| json!({"name": c.name, "line": c.line})).collect::<Vec<_>>(),
+        "eventEmits": a.event_emits.iter().map(|e| json!({"name": e.name, "line": e.line})).collect::<Vec<_>>(),
+        "eventListens": a.event_listens.iter().map(|e| json!({"name": e.name, "line": e.line})).collect::<Vec<_>>(),
+    })
+}
+
+/// Analyses every root and prints the reports. Returns the number of dependency
+/// rule violations so the caller can pick the exit code.
+pub fn run_import_analyzer(root_list: &[PathBuf], parsed: &ParsedArgs) -> io::Result<usize> {
+    let mut json_results = Vec::new();
+    let mut report_sections: Vec<RootReport> = Vec::new();
+    let mut server_handle = None;
+    let mut total_violations = 0usize;
+    let mut file_graphs = Vec::new();
+    let symbol_filter = SymbolFilter::from_args(parsed);
 
     if parsed.serve {
         if let Some((port, handle)) =
@@ -1277,6 +2111,9 @@ pub fn run_import_analyzer(root_list: &[PathBuf], parsed: &ParsedArgs) -> io::Re
             editor_cmd: parsed.editor_cmd.clone(),
         };
 
+        let config = load_config(root_path, parsed.config_path.as_ref())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
         let git_checker = if options.use_gitignore {
             GitIgnoreChecker::new(root_path)
         } else {
@@ -1286,368 +2123,104 @@ pub fn run_import_analyzer(root_list: &[PathBuf], parsed: &ParsedArgs) -> io::Re
         let mut files = Vec::new();
         gather_files(root_path, &options, 0, git_checker.as_ref(), &mut files)?;
 
-        let mut analyses = Vec::new();
-        let mut export_index: ExportIndex = HashMap::new();
-        let mut reexport_edges: Vec<(String, Option<String>)> = Vec::new();
-        let mut dynamic_summary: Vec<(String, Vec<String>)> = Vec::new();
-        let mut fe_commands: HashMap<String, Vec<(String, usize)>> = HashMap::new();
-        let mut be_commands: HashMap<String, Vec<(String, usize)>> = HashMap::new();
-        let mut graph_edges: Vec<(String, String, String)> = Vec::new();
-
-        for file in files {
-            let analysis = analyze_file(&file, root_path, options.extensions.as_ref())?;
-            for exp in &analysis.exports {
-                let name_lc = exp.name.to_lowercase();
-                let ignored = ignore_exact.contains(&name_lc)
-                    || ignore_prefixes.iter().any(|p| name_lc.starts_with(p));
-                if ignored {
-                    continue;
-                }
-                export_index
-                    .entry(exp.name.clone())
-                    .or_default()
-                    .push(analysis.path.clone());
-            }
-            for re in &analysis.reexports {
-                reexport_edges.push((analysis.path.clone(), re.resolved.clone()));
-                if parsed.graph && options.report_path.is_some() {
-                    if let Some(target) = &re.resolved {
-                        graph_edges.push((
-                            analysis.path.clone(),
-                            target.clone(),
-                            "reexport".to_string(),
-                        ));
-                    }
-                }
-            }
-            if !analysis.dynamic_imports.is_empty() {
-                dynamic_summary.push((analysis.path.clone(), analysis.dynamic_imports.clone()));
-            }
-            if parsed.graph && options.report_path.is_some() {
-                let ext = file
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .map(|s| s.to_lowercase())
-                    .unwrap_or_default();
-                for imp in &analysis.imports {
-                    let resolved = match ext.as_str() {
-                        "py" => {
-                            if imp.source.starts_with('.') {
-                                resolve_python_relative(
-                                    &imp.source,
-                                    &file,
-                                    root_path,
-                                    options.extensions.as_ref(),
-                                )
-                            } else {
-                                None
-                            }
-                        }
-                        "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => {
-                            if imp.source.starts_with('.') {
-                                resolve_js_relative(
-                                    &file,
-                                    root_path,
-                                    &imp.source,
-                                    options.extensions.as_ref(),
-                                )
-                            } else {
-                                None
-                            }
-                        }
-                        _ => None,
-                    };
-                    if let Some(target) = resolved {
-                        graph_edges.push((
-                            analysis.path.clone(),
-                            target,
-                            match imp.kind {
-                                ImportKind::Static | ImportKind::SideEffect => "import".to_string(),
-                            },
-                        ));
-                    }
-                }
-            }
-            for call in &analysis.command_calls {
-                fe_commands
-                    .entry(call.name.clone())
-                    .or_default()
-                    .push((analysis.path.clone(), call.line));
-            }
-            for handler in &analysis.command_handlers {
-                be_commands
-                    .entry(handler.name.clone())
-                    .or_default()
-                    .push((analysis.path.clone(), handler.line));
-            }
-            analyses.push(analysis);
-        }
-        let duplicate_exports: Vec<_> = export_index
-            .into_iter()
-            .filter(|(_, files)| files.len() > 1)
-            .collect();
-
-        let reexport_files: HashSet<String> = analyses
-            .iter()
-            .filter(|a| !a.reexports.is_empty())
-            .map(|a| a.path.clone())
-            .collect();
-
-        let mut cascades = Vec::new();
-        for (from, resolved) in &reexport_edges {
-            if let Some(target) = resolved {
-                if reexport_files.contains(target) {
-                    cascades.push((from.clone(), target.clone()));
-                }
-            }
+        let (analyses, diagnostics) = analyze_files(
+            root_path,
+            &files,
+            &options,
+            config.invoke_wrappers.as_deref().unwrap_or(&[]),
+            parsed,
+        );
+        let graph_edges = file_edges(&analyses);
+
+        let mut commands = CommandCoverage::new(root_path);
+        let mut ipc = IpcCoverage::default();
+        let mut routes = RouteCoverage::default();
+        let mut events = EventCoverage::default();
+        for analysis in &analyses {
+            commands.add(analysis);
+            ipc.add(analysis);
+            routes.add(analysis);
+            events.add(analysis);
         }
+        let capabilities =
+            check_capabilities(root_path, &options, git_checker.as_ref(), &mut commands)?;
+        let duplicates = DuplicateExports::find(
+            &analyses,
+            &symbol_filter,
+            &reported_kinds(config.duplicate_kinds.as_deref()),
+        );
+        let barrels = analyze_barrels(&analyses, &graph_edges);
+        let clones = if parsed.clones {
+            detect_clones(&analyses, parsed.clone_tokens)
+        } else {
+            Vec::new()
+        };
+        let reachability = reachability(root_path, &parsed.entries, &graph_edges, &analyses);
+        let rule_violations = check_dependency_rules(&config.rules, &analyses);
+        total_violations += rule_violations.len();
+
+        let report = RootReport {
+            root: root_path.clone(),
+            dynamic: DynamicImports::collect(&analyses),
+            dependencies: build_dependency_inventory(root_path, &analyses),
+            analyses,
+            diagnostics,
+            graph_edges,
+            duplicates,
+            barrels,
+            clones,
+            commands,
+            ipc,
+            routes,
+            events,
+            capabilities,
+            rule_violations,
+            reachability,
+            open_base: options
+                .serve
+                .then(|| OPEN_SERVER_BASE.get().cloned())
+                .flatten(),
+        };
 
-        let mut ranked_dups = Vec::new();
-        for (name, files) in &duplicate_exports {
-            let dev_count = files.iter().filter(|f| is_dev_file(f)).count();
-            let prod_count = files.len().saturating_sub(dev_count);
-            let score = prod_count * 2 + dev_count;
-            let canonical = files
-                .iter()
-                .find(|f| !is_dev_file(f))
-                .cloned()
-                .unwrap_or_else(|| files[0].clone());
-            let mut refactors: Vec<String> =
-                files.iter().filter(|f| *f != &canonical).cloned().collect();
-            refactors.sort();
-            ranked_dups.push((
-                name.clone(),
-                files.clone(),
-                score,
-                prod_count,
-                dev_count,
-                canonical,
-                refactors,
+        if parsed.graph_format.is_some() {
+            file_graphs.push(build_file_graph(
+                &root_path.display().to_string(),
+                &report.analyses,
+                &report.graph_edges,
             ));
         }
-        ranked_dups.sort_by(|a, b| b.2.cmp(&a.2).then(b.1.len().cmp(&a.1.len())));
-
-        let missing_handlers: Vec<CommandGap> = fe_commands
-            .iter()
-            .filter(|(name, _)| !be_commands.contains_key(*name))
-            .map(|(name, locs)| CommandGap {
-                name: name.clone(),
-                locations: locs.clone(),
-            })
-            .collect();
-        let unused_handlers: Vec<CommandGap> = be_commands
-            .iter()
-            .filter(|(name, _)| !fe_commands.contains_key(*name))
-            .map(|(name, locs)| CommandGap {
-                name: name.clone(),
-                locations: locs.clone(),
-            })
-            .collect();
-
-        let mut section_open = None;
-        if options.report_path.is_some() && options.serve {
-            if let Some(base) = OPEN_SERVER_BASE.get() {
-                section_open = Some(base.clone());
-            }
-        }
-
-        if options.report_path.is_some() {
-            let mut sorted_dyn = dynamic_summary.clone();
-            sorted_dyn.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
-            report_sections.push(ReportSection {
-                root: root_path.display().to_string(),
-                files_analyzed: analyses.len(),
-                ranked_dups: ranked_dups.clone(),
-                cascades: cascades.clone(),
-                dynamic: sorted_dyn,
-                analyze_limit: options.analyze_limit,
-                missing_handlers: {
-                    let mut v = missing_handlers.clone();
-                    v.sort_by(|a, b| a.name.cmp(&b.name));
-                    v
-                },
-                unused_handlers: {
-                    let mut v = unused_handlers.clone();
-                    v.sort_by(|a, b| a.name.cmp(&b.name));
-                    v
-                },
-                open_base: section_open,
-                graph: if parsed.graph && options.report_path.is_some() && !graph_edges.is_empty() {
-                    let mut nodes: HashSet<String> = HashSet::new();
-                    for (a, b, _) in &graph_edges {
-                        nodes.insert(a.clone());
-                        nodes.insert(b.clone());
+        // A graph printed to stdout replaces the report.
+        if parsed.graph_format.is_none() || parsed.graph_out.is_some() {
+            match options.output {
+                OutputMode::Json => json_results.push(report.json()),
+                OutputMode::Jsonl => {
+                    println!("{}", serde_json::to_string(&report.json()).unwrap())
+                }
+                OutputMode::Human => {
+                    if idx > 0 {
+                        println!();
                     }
-                    Some(GraphData {
-                        nodes: nodes.into_iter().collect(),
-                        edges: graph_edges.clone(),
-                    })
-                } else {
-                    None
-                },
-            });
-        }
-
-        if matches!(options.output, OutputMode::Json | OutputMode::Jsonl) {
-            let files_json: Vec<_> = analyses
-                .iter()
-                .map(|a| {
-                    json!({
-                        "path": a.path,
-                        "imports": a.imports.iter().map(|i| json!({"source": i.source, "kind": match i.kind { ImportKind::Static => "static", ImportKind::SideEffect => "side-effect" }})).collect::<Vec<_>>(),
-                        "reexports": a.reexports.iter().map(|r| {
-                            match &r.kind {
-                                ReexportKind::Star => json!({"source": r.source, "kind": "star", "resolved": r.resolved}),
-                                ReexportKind::Named(names) => json!({"source": r.source, "kind": "named", "names": names, "resolved": r.resolved})
-                            }
-                        }).collect::<Vec<_>>(),
-                        "dynamicImports": a.dynamic_imports,
-                        "exports": a.exports.iter().map(|e| json!({"name": e.name, "kind": e.kind})).collect::<Vec<_>>(),
-                        "commandCalls": a.command_calls.iter().map(|c| json!({"name": c.name, "line": c.line})).collect::<Vec<_>>(),
-                        "commandHandlers": a.command_handlers.iter().map(|c| json!({"name": c.name, "line": c.line})).collect::<Vec<_>>(),
-                    })
-                })
-                .collect();
-
-            let payload = json!({
-                "root": root_path,
-                "filesAnalyzed": analyses.len(),
-                "duplicateExports": duplicate_exports
-                    .iter()
-                    .map(|(name, files)| json!({"name": name, "files": files}))
-                    .collect::<Vec<_>>(),
-                "duplicateExportsRanked": ranked_dups
-                    .iter()
-                    .map(|(name, files, score, prod, dev, canonical, refactors)| json!({
-                        "name": name,
-                        "files": files,
-                        "score": score,
-                        "nonDevCount": prod,
-                        "devCount": dev,
-                        "canonical": canonical,
-                        "refactorTargets": refactors,
-                    }))
-                    .collect::<Vec<_>>(),
-                "reexportCascades": cascades
-                    .iter()
-                    .map(|(from, to)| json!({"from": from, "to": to}))
-                    .collect::<Vec<_>>(),
-                "dynamicImports": dynamic_summary
-                    .iter()
-                    .map(|(file, sources)| {
-                        let unique: HashSet<_> = sources.iter().collect();
-                        json!({
-                            "file": file,
-                            "sources": sources,
-                            "manySources": sources.len() > 5,
-                            "selfImport": unique.len() < sources.len(),
-                    })
-                })
-                .collect::<Vec<_>>(),
-                "commands": {
-                    "frontend": fe_commands.iter().map(|(k,v)| json!({"name": k, "locations": v})).collect::<Vec<_>>(),
-                    "backend": be_commands.iter().map(|(k,v)| json!({"name": k, "locations": v})).collect::<Vec<_>>(),
-                    "missingHandlers": missing_handlers.iter().map(|g| json!({"name": g.name, "locations": g.locations})).collect::<Vec<_>>(),
-                    "unusedHandlers": unused_handlers.iter().map(|g| json!({"name": g.name, "locations": g.locations})).collect::<Vec<_>>(),
-                },
-                "files": files_json,
-            });
-
-            if matches!(options.output, OutputMode::Jsonl) {
-                println!("{}", serde_json::to_string(&payload).unwrap());
-            } else {
-                json_results.push(payload);
-            }
-            continue;
-        }
-
-        if idx > 0 {
-            println!();
-        }
-
-        println!("Import/export analysis for {}/", root_path.display());
-        println!("  Files analyzed: {}", analyses.len());
-        println!("  Duplicate exports: {}", duplicate_exports.len());
-        println!("  Files with re-exports: {}", reexport_files.len());
-        println!("  Dynamic imports: {}", dynamic_summary.len());
-
-        if !duplicate_exports.is_empty() {
-            println!(
-                "\nTop duplicate exports (showing up to {}):",
-                options.analyze_limit
-            );
-            for (name, files, score, prod, dev, canonical, refactors) in
-                ranked_dups.iter().take(options.analyze_limit)
-            {
-                println!(
-                    "  - {} (score {}, {} files: {} prod, {} dev) canonical: {} | refs: {}",
-                    name,
-                    score,
-                    files.len(),
-                    prod,
-                    dev,
-                    canonical,
-                    refactors.join(", ")
-                );
-            }
-        }
-
-        if !cascades.is_empty() {
-            println!("\nRe-export cascades:");
-            for (from, to) in &cascades {
-                println!("  - {} -> {}", from, to);
+                    report.print(options.analyze_limit);
+                }
             }
         }
-
-        if !dynamic_summary.is_empty() {
-            println!(
-                "\nDynamic imports (showing up to {}):",
-                options.analyze_limit
-            );
-            let mut sorted_dyn = dynamic_summary.clone();
-            sorted_dyn.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
-            for (file, sources) in sorted_dyn.iter().take(options.analyze_limit) {
-                println!(
-                    "  - {}: {}{}",
-                    file,
-                    sources.join(", "),
-                    if sources.len() > 5 {
-                        "  [many sources]"
-                    } else {
-                        ""
-                    }
-                );
-            }
+        if options.report_path.is_some() {
+            report_sections.push(report);
         }
+    }
 
-        if !missing_handlers.is_empty() || !unused_handlers.is_empty() {
-            println!("\nTauri command coverage:");
-            if !missing_handlers.is_empty() {
-                println!(
-                    "  Missing handlers (frontend calls without backend): {}",
-                    missing_handlers
-                        .iter()
-                        .map(|g| g.name.clone())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
-            }
-            if !unused_handlers.is_empty() {
-                println!(
-                    "  Unused handlers (backend not called by FE): {}",
-                    unused_handlers
-                        .iter()
-                        .map(|g| g.name.clone())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
+    if let Some(format) = parsed.graph_format {
+        let rendered = render_graph(format, &file_graphs);
+        match parsed.graph_out.as_ref() {
+            Some(path) => {
+                fs::write(path, rendered)?;
+                eprintln!("[loctree] graph written to {}", path.display());
             }
+            None => print!("{}", rendered),
         }
-
-        println!("\nTip: rerun with --json for machine-readable output.");
     }
 
-    if matches!(parsed.output, OutputMode::Json) {
+    if matches!(parsed.output, OutputMode::Json) && !json_results.is_empty() {
         if json_results.len() == 1 {
             println!(
                 "{}",
@@ -1659,7 +2232,12 @@ pub fn run_import_analyzer(root_list: &[PathBuf], parsed: &ParsedArgs) -> io::Re
     }
 
     if let Some(report_path) = parsed.report_path.as_ref() {
-        render_html_report(report_path, &report_sections)?;
+        render_html_report(
+            report_path,
+            &report_sections,
+            parsed.analyze_limit,
+            parsed.graph,
+        )?;
         eprintln!("[loctree] HTML report written to {}", report_path.display());
         open_in_browser(report_path);
     }
@@ -1674,7 +2252,8 @@ pub fn run_import_analyzer(root_list: &[PathBuf], parsed: &ParsedArgs) -> io::Re
     }
 
     drop(server_handle);
-    Ok(())
+
+    Ok(total_violations)
 }
 
 pub fn default_analyzer_exts() -> HashSet<String> {
@@ -1684,22 +2263,124 @@ pub fn default_analyzer_exts() -> HashSet<String> {
         .collect()
 }
 
-pub fn brace_list_to_names(raw: &str) -> Vec<String> {
+/// Parses the clause between `import` and `from`: default, namespace and
+/// named bindings, with `type` markers on the whole clause or single names.
+fn parse_js_import_clause(raw: &str) -> (bool, Vec<ImportSymbol>) {
+    let mut clause = raw.trim();
+    let mut type_only = false;
+    if let Some(rest) = clause.strip_prefix("type") {
+        if rest.starts_with(|c: char| c.is_whitespace() || c == '{') {
+            type_only = true;
+            clause = rest.trim_start();
+        }
+    }
+    let mut symbols = Vec::new();
+    let (outside, named) = match (clause.find('{'), clause.rfind('}')) {
+        (Some(open), Some(close)) if open < close => (
+            format!("{}{}", &clause[..open], &clause[close + 1..]),
+            Some(&clause[open + 1..close]),
+        ),
+        _ => (clause.to_string(), None),
+    };
+    for part in outside.split(',') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        if let Some(ns) = part.strip_prefix('*') {
+            let alias = ns.trim().strip_prefix("as").map(|a| a.trim().to_string());
+            symbols.push(ImportSymbol {
+                name: "*".to_string(),
+                alias,
+                is_default: false,
+                is_namespace: true,
+                type_only,
+            });
+        } else {
+            symbols.push(ImportSymbol {
+                name: "default".to_string(),
+                alias: Some(part.to_string()),
+                is_default: true,
+                is_namespace: false,
+                type_only,
+            });
+        }
+    }
+    for item in named.unwrap_or("").split(',') {
+        let mut item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        let mut item_type_only = type_only;
+        if let Some(rest) = item.strip_prefix("type ") {
+            item_type_only = true;
+            item = rest.trim();
+        }
+        let (name, alias) = match item.split_once(" as ") {
+            Some((name, alias)) => (name.trim(), Some(alias.trim().to_string())),
+            None => (item, None),
+        };
+        symbols.push(ImportSymbol {
+            name: name.to_string(),
+            alias,
+            is_default: name == "default",
+            is_namespace: false,
+            type_only: item_type_only,
+        });
+    }
+    // `import { type A, type B } from` is erased at runtime just like `import type`.
+    if !symbols.is_empty() && symbols.iter().all(|s| s.type_only) {
+        type_only = true;
+    }
+    (type_only, symbols)
+}
+
+/// Splits `name as alias` from a Python import list item.
+fn split_py_alias(item: &str) -> (&str, Option<&str>) {
+    match item.trim().split_once(" as ") {
+        Some((name, alias)) => (name.trim(), Some(alias.trim())),
+        None => (item.trim(), None),
+    }
+}
+
+/// Entries of an `export { ... }` list as `(original, exported, type-only)`:
+/// `a as b` exports `a` under `b`, and an inline `type` modifier
+/// (`export { type Foo }`) marks a type-only entry.
+pub fn brace_list_to_names(raw: &str) -> Vec<(String, String, bool)> {
     raw.split(',')
         .filter_map(|item| {
-            let trimmed = item.trim();
+            let mut trimmed = item.trim();
             if trimmed.is_empty() {
                 return None;
             }
-            if let Some((_, alias)) = trimmed.split_once(" as ") {
-                Some(alias.trim().to_string())
-            } else {
-                Some(trimmed.to_string())
+            let type_name = trimmed.starts_with("type ");
+            if type_name {
+                trimmed = trimmed["type ".len()..].trim_start();
             }
+            let (original, name) = match trimmed.split_once(" as ") {
+                Some((original, alias)) => (original.trim(), alias.trim()),
+                None => (trimmed, trimmed),
+            };
+            Some((original.to_string(), name.to_string(), type_name))
         })
         .collect()
 }
 
-fn offset_to_line(content: &str, offset: usize) -> usize {
+pub fn offset_to_line(content: &str, offset: usize) -> usize {
     content[..offset].bytes().filter(|b| *b == b'\n').count() + 1
 }
+
+/// 1-based line and column (in characters) of a byte offset.
+pub fn offset_to_line_col(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    (
+        offset_to_line(content, offset),
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Offset of the first non-whitespace character of a `(?m)^\s*...` match.
+fn statement_start(m: regex::Match) -> usize {
+    m.start() + (m.as_str().len() - m.as_str().trim_start().len())
+}
@@ -1,7 +1,8 @@
 use std::collections::HashSet;
 use std::path::PathBuf;
 
-use crate::types::{ColorMode, Mode, OutputMode, DEFAULT_LOC_THRESHOLD};
+use crate::clones::DEFAULT_CLONE_TOKENS;
+use crate::types::{ColorMode, GraphFormat, Mode, OutputMode, DEFAULT_LOC_THRESHOLD};
 
 pub struct ParsedArgs {
     pub extensions: Option<HashSet<String>>,
@@ -25,6 +26,19 @@ pub struct ParsedArgs {
     pub report_path: Option<PathBuf>,
     pub serve: bool,
     pub editor_cmd: Option<String>,
+    pub entries: Vec<String>,
+    pub config_path: Option<PathBuf>,
+    pub graph_format: Option<GraphFormat>,
+    pub graph_out: Option<PathBuf>,
+    /// Analyzer: report code clones (`--clones`, or a `--clone-tokens` above 0).
+    pub clones: bool,
+    /// Minimum clone length in tokens.
+    pub clone_tokens: usize,
+    /// Analyzer: skip the on-disk cache of per-file analyses.
+    pub no_cache: bool,
+    /// Analyzer: where the cache is kept (`--cache-dir`); see `cache::cache_base`.
+    pub cache_dir: Option<PathBuf>,
+    pub verbose: bool,
 }
 
 impl Default for ParsedArgs {
@@ -51,6 +65,15 @@ impl Default for ParsedArgs {
             report_path: None,
             serve: false,
             editor_cmd: None,
+            entries: Vec::new(),
+            config_path: None,
+            graph_format: None,
+            graph_out: None,
+            clones: false,
+            clone_tokens: DEFAULT_CLONE_TOKENS,
+            no_cache: false,
+            cache_dir: None,
+            verbose: false,
         }
     }
 }
@@ -64,6 +87,16 @@ fn parse_color_mode(raw: &str) -> Result<ColorMode, String> {
     }
 }
 
+fn parse_graph_format(raw: &str) -> Result<GraphFormat, String> {
+    match raw.to_lowercase().as_str() {
+        "dot" => Ok(GraphFormat::Dot),
+        "graphml" => Ok(GraphFormat::GraphMl),
+        "mermaid" => Ok(GraphFormat::Mermaid),
+        "json" => Ok(GraphFormat::Json),
+        _ => Err("--graph-format expects dot|graphml|mermaid|json".to_string()),
+    }
+}
+
 fn parse_summary_limit(raw: &str) -> Result<usize, String> {
     let value = raw
         .parse::<usize>()
@@ -188,6 +221,25 @@ pub fn parse_args() -> Result<ParsedArgs, String> {
                 parsed.report_path = Some(PathBuf::from(next));
                 i += 2;
             }
+            "--graph-format" => {
+                let next = args
+                    .get(i + 1)
+                    .ok_or_else(|| "--graph-format expects dot|graphml|mermaid|json".to_string())?;
+                parsed.graph_format = Some(parse_graph_format(next)?);
+                i += 2;
+            }
+            _ if arg.starts_with("--graph-format=") => {
+                let value = arg.trim_start_matches("--graph-format=");
+                parsed.graph_format = Some(parse_graph_format(value)?);
+                i += 1;
+            }
+            "--graph-out" => {
+                let next = args
+                    .get(i + 1)
+                    .ok_or_else(|| "--graph-out requires a file path".to_string())?;
+                parsed.graph_out = Some(PathBuf::from(next));
+                i += 2;
+            }
             "--serve" => {
                 parsed.serve = true;
                 i += 1;
@@ -242,6 +294,35 @@ pub fn parse_args() -> Result<ParsedArgs, String> {
                 parsed.analyze_limit = value;
                 i += 2;
             }
+            "--clone-tokens" => {
+                let next = args
+                    .get(i + 1)
+                    .ok_or_else(|| "--clone-tokens requires a non-negative integer".to_string())?;
+                parsed.clone_tokens = next
+                    .parse::<usize>()
+                    .map_err(|_| "--clone-tokens requires a non-negative integer".to_string())?;
+                parsed.clones = parsed.clone_tokens > 0;
+                i += 2;
+            }
+            "--clones" => {
+                parsed.clones = true;
+                i += 1;
+            }
+            "--no-cache" => {
+                parsed.no_cache = true;
+                i += 1;
+            }
+            "--cache-dir" => {
+                let next = args
+                    .get(i + 1)
+                    .ok_or_else(|| "--cache-dir requires a directory".to_string())?;
+                parsed.cache_dir = Some(PathBuf::from(next));
+                i += 2;
+            }
+            "--verbose" | "-v" => {
+                parsed.verbose = true;
+                i += 1;
+            }
             "--analyze-imports" | "-A" => {
                 parsed.mode = Mode::AnalyzeImports;
                 i += 1;
@@ -292,6 +373,25 @@ pub fn parse_args() -> Result<ParsedArgs, String> {
                 parsed.ignore_symbols_preset = Some(value.to_string());
                 i += 1;
             }
+            "--config" => {
+                let next = args
+                    .get(i + 1)
+                    .ok_or_else(|| "--config requires a file path".to_string())?;
+                parsed.config_path = Some(PathBuf::from(next));
+                i += 2;
+            }
+            "--entry" => {
+                let next = args
+                    .get(i + 1)
+                    .ok_or_else(|| "--entry requires a file path".to_string())?;
+                parsed.entries.push(next.clone());
+                i += 2;
+            }
+            _ if arg.starts_with("--entry=") => {
+                let value = arg.trim_start_matches("--entry=");
+                parsed.entries.push(value.to_string());
+                i += 1;
+            }
             "-I" | "--ignore" => {
                 let next = args
                     .get(i + 1)
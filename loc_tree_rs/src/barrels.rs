@@ -0,0 +1,366 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use serde_json::json;
+
+use crate::report::{escape_html, HtmlContext};
+use crate::types::{ExportCategory, FileAnalysis, ReexportKind};
+
+/// A re-exported symbol followed from a barrel to the file declaring it.
+#[derive(Clone)]
+pub struct ReexportChain {
+    pub symbol: String,
+    /// The barrel first, the declaring file last.
+    pub files: Vec<String>,
+    /// Line of the re-export in the barrel.
+    pub line: usize,
+}
+
+impl ReexportChain {
+    pub fn depth(&self) -> usize {
+        self.files.len().saturating_sub(1)
+    }
+}
+
+#[derive(Clone)]
+pub struct BarrelStats {
+    pub path: String,
+    /// Symbols importers can pull from the barrel.
+    pub symbols: usize,
+    /// Files loaded transitively when the barrel is imported, and their LOC.
+    pub files: usize,
+    pub loc: usize,
+    pub max_depth: usize,
+}
+
+#[derive(Default)]
+pub struct BarrelReport {
+    /// Files with re-exports.
+    pub reexport_files: usize,
+    /// Re-exports of a file that itself re-exports: `(from, to, line)`.
+    pub cascades: Vec<(String, String, usize)>,
+    /// Chains with more than one hop, deepest first.
+    pub chains: Vec<ReexportChain>,
+    pub max_depth: usize,
+    /// Barrels ranked by the code they pull in.
+    pub barrels: Vec<BarrelStats>,
+}
+
+#[derive(Default)]
+struct Module<'a> {
+    /// Names declared in the file itself.
+    own: HashSet<&'a str>,
+    /// `(exported name, name in the target, target, line)`.
+    named: Vec<(&'a str, &'a str, &'a str, usize)>,
+    stars: Vec<(&'a str, usize)>,
+}
+
+/// Traces every symbol exposed by a barrel (a file with resolved re-exports)
+/// back to its declaring file and measures what importing the barrel loads.
+/// Also lists re-exports that point at another re-exporting file.
+pub fn analyze_barrels(
+    analyses: &[FileAnalysis],
+    edges: &[(String, String, String)],
+) -> BarrelReport {
+    let mut modules: HashMap<&str, Module> = HashMap::new();
+    for analysis in analyses {
+        let mut module = Module::default();
+        for re in &analysis.reexports {
+            let Some(target) = re.resolved.as_deref() else {
+                continue;
+            };
+            match &re.kind {
+                ReexportKind::Star => module.stars.push((target, re.line)),
+                ReexportKind::Named(names) => {
+                    module.named.extend(names.iter().map(|(original, name)| {
+                        (name.as_str(), original.as_str(), target, re.line)
+                    }))
+                }
+            }
+        }
+        // `export { a } from "./x"` also shows up as a plain named export.
+        let forwarded: HashSet<&str> = module.named.iter().map(|(n, _, _, _)| *n).collect();
+        for export in &analysis.exports {
+            if export.kind == "reexport" || forwarded.contains(export.name.as_str()) {
+                continue;
+            }
+            // A default export is imported as `default`, whatever it is called.
+            module.own.insert(match export.category {
+                ExportCategory::Default => "default",
+                _ => export.name.as_str(),
+            });
+        }
+        modules.insert(analysis.path.as_str(), module);
+    }
+
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (from, to, kind) in edges {
+        if kind != "dynamic" {
+            adjacency
+                .entry(from.as_str())
+                .or_default()
+                .push(to.as_str());
+        }
+    }
+    let loc_by_path: HashMap<&str, usize> =
+        analyses.iter().map(|a| (a.path.as_str(), a.loc)).collect();
+
+    let mut report = BarrelReport::default();
+    let reexport_files: HashSet<&str> = analyses
+        .iter()
+        .filter(|a| !a.reexports.is_empty())
+        .map(|a| a.path.as_str())
+        .collect();
+    report.reexport_files = reexport_files.len();
+    for analysis in analyses {
+        for re in &analysis.reexports {
+            if let Some(target) = re.resolved.as_deref() {
+                if reexport_files.contains(target) {
+                    report
+                        .cascades
+                        .push((analysis.path.clone(), target.to_string(), re.line));
+                }
+            }
+        }
+    }
+
+    for analysis in analyses {
+        let path = analysis.path.as_str();
+        let Some(module) = modules.get(path) else {
+            continue;
+        };
+        if module.named.is_empty() && module.stars.is_empty() {
+            continue;
+        }
+        let symbols = exposed(&modules, path, &mut HashSet::new());
+        let mut max_depth = 0;
+        for symbol in &symbols {
+            let Some((files, line)) = trace(&modules, path, symbol, &mut HashSet::new()) else {
+                continue;
+            };
+            let chain = ReexportChain {
+                symbol: symbol.to_string(),
+                files: files.into_iter().map(str::to_string).collect(),
+                line,
+            };
+            max_depth = max_depth.max(chain.depth());
+            if chain.depth() > 1 {
+                report.chains.push(chain);
+            }
+        }
+
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = VecDeque::from([path]);
+        visited.insert(path);
+        while let Some(current) = queue.pop_front() {
+            for target in adjacency.get(current).into_iter().flatten() {
+                if visited.insert(target) {
+                    queue.push_back(target);
+                }
+            }
+        }
+        visited.remove(path);
+        report.max_depth = report.max_depth.max(max_depth);
+        report.barrels.push(BarrelStats {
+            path: path.to_string(),
+            symbols: symbols.len(),
+            files: visited.len(),
+            loc: visited
+                .iter()
+                .map(|p| loc_by_path.get(p).copied().unwrap_or(0))
+                .sum(),
+            max_depth,
+        });
+    }
+
+    report.chains.sort_by(|a, b| {
+        b.depth()
+            .cmp(&a.depth())
+            .then_with(|| a.files[0].cmp(&b.files[0]))
+            .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+    report.barrels.sort_by(|a, b| {
+        b.loc
+            .cmp(&a.loc)
+            .then(b.files.cmp(&a.files))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    report
+}
+
+impl BarrelReport {
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "reexportCascades": self.cascades
+                .iter()
+                .map(|(from, to, line)| json!({"from": from, "to": to, "line": line}))
+                .collect::<Vec<_>>(),
+            "reexportChains": self.chains
+                .iter()
+                .map(|chain| json!({
+                    "symbol": chain.symbol,
+                    "barrel": chain.files.first(),
+                    "declaredIn": chain.files.last(),
+                    "chain": chain.files,
+                    "depth": chain.depth(),
+                    "line": chain.line,
+                }))
+                .collect::<Vec<_>>(),
+            "maxReexportDepth": self.max_depth,
+            "barrels": self.barrels
+                .iter()
+                .map(|b| json!({
+                    "path": b.path,
+                    "symbols": b.symbols,
+                    "files": b.files,
+                    "loc": b.loc,
+                    "maxDepth": b.max_depth,
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self, limit: usize) {
+        if !self.cascades.is_empty() {
+            println!("\nRe-export cascades:");
+            for (from, to, line) in &self.cascades {
+                println!("  - {}:{} -> {}", from, line, to);
+            }
+        }
+
+        if !self.chains.is_empty() {
+            println!(
+                "\nRe-export chains (max depth {}, showing up to {}):",
+                self.max_depth, limit
+            );
+            for chain in self.chains.iter().take(limit) {
+                println!(
+                    "  - {}: {}:{} -> {}",
+                    chain.symbol,
+                    chain.files[0],
+                    chain.line,
+                    chain.files[1..].join(" -> ")
+                );
+            }
+        }
+
+        if !self.barrels.is_empty() {
+            println!("\nBarrel bloat (showing up to {}):", limit);
+            for barrel in self.barrels.iter().take(limit) {
+                println!(
+                    "  - {}: {} symbols, pulls in {} files / {} LOC (max chain depth {})",
+                    barrel.path, barrel.symbols, barrel.files, barrel.loc, barrel.max_depth
+                );
+            }
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        out.push_str("<h3>Re-export cascades</h3>");
+        if self.cascades.is_empty() {
+            out.push_str("<p class=\"muted\">None</p>");
+        } else {
+            out.push_str("<ul>");
+            for (from, to, line) in &self.cascades {
+                out.push_str(&format!(
+                    "<li><code>{}</code> → <code>{}</code></li>",
+                    ctx.link(from, *line),
+                    escape_html(to)
+                ));
+            }
+            out.push_str("</ul>");
+        }
+        if !self.chains.is_empty() {
+            out.push_str(&format!(
+                "<h3>Re-export chains</h3><p class=\"muted\">Max depth: {}</p><ul>",
+                self.max_depth
+            ));
+            for chain in self.chains.iter().take(ctx.limit) {
+                out.push_str(&format!(
+                    "<li><code>{}</code>: {} ({} hops)</li>",
+                    escape_html(&chain.symbol),
+                    std::iter::once(ctx.link(&chain.files[0], chain.line))
+                        .chain(
+                            chain.files[1..]
+                                .iter()
+                                .map(|f| format!("<code>{}</code>", escape_html(f)))
+                        )
+                        .collect::<Vec<_>>()
+                        .join(" → "),
+                    chain.depth()
+                ));
+            }
+            out.push_str("</ul>");
+        }
+        if !self.barrels.is_empty() {
+            out.push_str("<h3>Barrel bloat</h3>");
+            out.push_str("<table><tr><th>Barrel</th><th>Symbols</th><th>Files pulled in</th><th>LOC pulled in</th><th>Max chain depth</th></tr>");
+            for barrel in self.barrels.iter().take(ctx.limit) {
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&barrel.path),
+                    barrel.symbols,
+                    barrel.files,
+                    barrel.loc,
+                    barrel.max_depth
+                ));
+            }
+            out.push_str("</table>");
+        }
+    }
+}
+
+/// Names a module exposes; star re-exports do not forward `default`.
+fn exposed<'a>(
+    modules: &HashMap<&'a str, Module<'a>>,
+    file: &'a str,
+    seen: &mut HashSet<&'a str>,
+) -> BTreeSet<&'a str> {
+    let mut out = BTreeSet::new();
+    if !seen.insert(file) {
+        return out;
+    }
+    let Some(module) = modules.get(file) else {
+        return out;
+    };
+    out.extend(module.own.iter().copied());
+    out.extend(module.named.iter().map(|(n, _, _, _)| *n));
+    for (target, _) in &module.stars {
+        out.extend(
+            exposed(modules, target, seen)
+                .into_iter()
+                .filter(|n| *n != "default"),
+        );
+    }
+    out
+}
+
+/// Files a symbol passes through from `file` to its declaration, with the
+/// line of the re-export it leaves `file` by.
+fn trace<'a>(
+    modules: &HashMap<&'a str, Module<'a>>,
+    file: &'a str,
+    symbol: &str,
+    seen: &mut HashSet<&'a str>,
+) -> Option<(Vec<&'a str>, usize)> {
+    if !seen.insert(file) {
+        return None;
+    }
+    let Some(module) = modules.get(file) else {
+        return Some((vec![file], 0));
+    };
+    if let Some((_, original, target, line)) = module.named.iter().find(|(n, _, _, _)| *n == symbol)
+    {
+        // `a as b` continues as `a` in the target.
+        let rest = trace(modules, target, original, seen)
+            .map(|(files, _)| files)
+            .unwrap_or_else(|| vec![*target]);
+        return Some((std::iter::once(file).chain(rest).collect(), *line));
+    }
+    if module.own.contains(symbol) {
+        return Some((vec![file], 0));
+    }
+    module.stars.iter().find_map(|(target, line)| {
+        trace(modules, target, symbol, seen)
+            .map(|(files, _)| (std::iter::once(file).chain(files).collect(), *line))
+    })
+}
@@ -0,0 +1,410 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DynamicImportKind, FileAnalysis};
+
+const CACHE_FILE: &str = "analysis.json";
+
+/// Manifests import resolution depends on (Python project directories) or
+/// that decide how files are classified; editing one resets the cache.
+const MANIFEST_FILES: &[&str] = &[
+    "package.json",
+    "Cargo.toml",
+    "pyproject.toml",
+    "setup.py",
+    "setup.cfg",
+    "requirements.txt",
+    "requirements-dev.txt",
+];
+
+/// Size and modification time of a file, taken before it is read so that a
+/// write racing the read shows up as a changed stamp on the next run.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    size: u64,
+    mtime_secs: u64,
+    mtime_nanos: u32,
+}
+
+impl FileStamp {
+    pub fn of(meta: &fs::Metadata) -> Option<Self> {
+        let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: meta.len(),
+            mtime_secs: mtime.as_secs(),
+            mtime_nanos: mtime.subsec_nanos(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    stamp: FileStamp,
+    hash: u64,
+    analysis: FileAnalysis,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    fingerprint: String,
+    /// Relative paths of the analysed files when the cache was written.
+    files: Vec<String>,
+    /// Preload bridge wrappers the renderer analyses were made with.
+    bridge: Vec<(String, String)>,
+    entries: HashMap<String, CacheEntry>,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct CacheStats {
+    /// Files whose analysis was reused.
+    pub hits: usize,
+    /// Files analysed because they were new, changed or not cached.
+    pub misses: usize,
+    /// Entries dropped because files were added or removed and their imports
+    /// may now resolve differently.
+    pub invalidated: usize,
+    /// A cache was found but written for other options or manifests.
+    pub reset: bool,
+}
+
+/// Per-file analyses from earlier runs over the same root, keyed by relative
+/// path and validated by size, mtime and content hash.
+///
+/// Analysis depends on the options in effect, so the whole cache is discarded
+/// when the `fingerprint` (binary, options, manifests) differs from the one it
+/// was written with. Import resolution also depends on which other files exist; when files
+/// are added or removed only the entries whose imports may resolve differently
+/// are dropped.
+pub struct AnalysisCache {
+    path: Option<PathBuf>,
+    fingerprint: String,
+    files: Vec<String>,
+    bridge: Vec<(String, String)>,
+    entries: HashMap<String, CacheEntry>,
+    fresh: HashMap<String, CacheEntry>,
+    stats: CacheStats,
+}
+
+impl AnalysisCache {
+    /// A cache that neither reads nor writes anything (`--no-cache`).
+    pub fn disabled() -> Self {
+        Self {
+            path: None,
+            fingerprint: String::new(),
+            files: Vec::new(),
+            bridge: Vec::new(),
+            entries: HashMap::new(),
+            fresh: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Loads the cache of `root`, kept under `base`, for an analysis of
+    /// `files` (relative paths).
+    pub fn load(base: &Path, root: &Path, fingerprint: String, files: &[String]) -> Self {
+        let path = base.join(root_key(root)).join(CACHE_FILE);
+        let cached = fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<CacheFile>(&raw).ok());
+        let mut stats = CacheStats {
+            reset: cached
+                .as_ref()
+                .is_some_and(|file| file.fingerprint != fingerprint),
+            ..CacheStats::default()
+        };
+        let (entries, bridge) = cached
+            .filter(|file| file.fingerprint == fingerprint)
+            .map(|file| {
+                let before = file.entries.len();
+                let entries = invalidate_file_set_changes(file.entries, &file.files, files);
+                stats.invalidated = before - entries.len();
+                (entries, file.bridge)
+            })
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            fingerprint,
+            files: files.to_vec(),
+            bridge,
+            entries,
+            fresh: HashMap::new(),
+            stats,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.path.is_some()
+    }
+
+    fn location(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The cached analysis of `file` if it has not changed. A touched file
+    /// with unchanged content is still a hit.
+    pub fn lookup(&mut self, file: &Path, relative: &str) -> Option<FileAnalysis> {
+        if !self.enabled() {
+            return None;
+        }
+        let stamp = FileStamp::of(&fs::metadata(file).ok()?)?;
+        let mut entry = self.entries.remove(relative)?;
+        if entry.stamp.size != stamp.size {
+            return None;
+        }
+        if entry.stamp != stamp {
+            if content_hash(&fs::read(file).ok()?) != entry.hash {
+                return None;
+            }
+            entry.stamp = stamp;
+        }
+        self.stats.hits += 1;
+        let analysis = entry.analysis.clone();
+        self.fresh.insert(relative.to_string(), entry);
+        Some(analysis)
+    }
+
+    /// Records the preload bridge of this run; returns whether it differs from
+    /// the one the cached analyses were made with.
+    pub fn update_bridge(&mut self, bridge: &[(String, String)]) -> bool {
+        let changed = self.bridge != bridge;
+        self.bridge = bridge.to_vec();
+        changed
+    }
+
+    /// Takes back a hit whose analysis turned out to be stale.
+    pub fn evict(&mut self, relative: &str) {
+        if self.fresh.remove(relative).is_some() {
+            self.stats.hits -= 1;
+        }
+    }
+
+    /// Caches `analysis` of the file read as `bytes`, whose stamp was taken
+    /// before reading.
+    pub fn store(
+        &mut self,
+        relative: &str,
+        stamp: Option<FileStamp>,
+        bytes: &[u8],
+        analysis: &FileAnalysis,
+    ) {
+        if !self.enabled() {
+            return;
+        }
+        self.stats.misses += 1;
+        let Some(stamp) = stamp else {
+            return;
+        };
+        self.fresh.insert(
+            relative.to_string(),
+            CacheEntry {
+                stamp,
+                hash: content_hash(bytes),
+                analysis: analysis.clone(),
+            },
+        );
+    }
+
+    /// One line of hit/miss counts for `--verbose`.
+    pub fn print_stats(&self) {
+        let Some(location) = self.location() else {
+            eprintln!("[loctree] cache: disabled");
+            return;
+        };
+        let stats = self.stats;
+        eprintln!(
+            "[loctree] cache: {} hit(s), {} miss(es){} ({})",
+            stats.hits,
+            stats.misses,
+            if stats.reset {
+                ", reset after option or manifest change".to_string()
+            } else if stats.invalidated > 0 {
+                format!(", {} dropped after file set change", stats.invalidated)
+            } else {
+                String::new()
+            },
+            location.display()
+        );
+    }
+
+    /// Writes the entries seen in this run.
+    pub fn save(&mut self) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let file = CacheFile {
+            fingerprint: std::mem::take(&mut self.fingerprint),
+            files: std::mem::take(&mut self.files),
+            bridge: std::mem::take(&mut self.bridge),
+            entries: std::mem::take(&mut self.fresh),
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| {
+                serde_json::to_string(&file)
+                    .map_err(std::io::Error::other)
+                    .and_then(|raw| fs::write(&path, raw))
+            });
+        if let Err(err) = result {
+            eprintln!(
+                "[loctree][warn] could not write cache {}: {}",
+                path.display(),
+                err
+            );
+        }
+    }
+}
+
+/// Drops the entries whose import resolution may change between the cached
+/// and the current file set: those resolving to a removed file, those with a
+/// specifier that names an added file (its stem or directory, which covers
+/// `index`, `mod.rs` and `__init__.py` targets) and glob imports.
+fn invalidate_file_set_changes(
+    mut entries: HashMap<String, CacheEntry>,
+    before: &[String],
+    now: &[String],
+) -> HashMap<String, CacheEntry> {
+    let before: HashSet<&str> = before.iter().map(String::as_str).collect();
+    let now: HashSet<&str> = now.iter().map(String::as_str).collect();
+    let removed: HashSet<&str> = before.difference(&now).copied().collect();
+    let added_names: HashSet<&str> = now
+        .difference(&before)
+        .flat_map(|file| {
+            let path = Path::new(*file);
+            let stem = path.file_stem().and_then(|s| s.to_str());
+            let dir = path
+                .parent()
+                .and_then(|p| p.file_name())
+                .and_then(|s| s.to_str());
+            stem.into_iter().chain(dir)
+        })
+        .collect();
+    if removed.is_empty() && added_names.is_empty() {
+        return entries;
+    }
+    let names_added = |specifier: &str| {
+        specifier
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-' || c == '$'))
+            .any(|name| added_names.contains(name))
+    };
+    entries.retain(|_, entry| {
+        let analysis = &entry.analysis;
+        let resolved_removed = analysis
+            .imports
+            .iter()
+            .filter_map(|i| i.resolved.as_deref())
+            .chain(
+                analysis
+                    .reexports
+                    .iter()
+                    .filter_map(|r| r.resolved.as_deref()),
+            )
+            .chain(
+                analysis
+                    .dynamic_imports
+                    .iter()
+                    .flat_map(|d| d.resolved.iter().map(String::as_str)),
+            )
+            .any(|target| removed.contains(target));
+        let names_new_file =
+            !added_names.is_empty()
+                && (analysis.imports.iter().any(|i| {
+                    names_added(&i.source) || i.symbols.iter().any(|s| names_added(&s.name))
+                }) || analysis.reexports.iter().any(|r| names_added(&r.source))
+                    || analysis
+                        .dynamic_imports
+                        .iter()
+                        .any(|d| d.kind == DynamicImportKind::Glob || names_added(&d.source)));
+        !resolved_removed && !names_new_file
+    });
+    entries
+}
+
+/// Directory caches are kept in: `--cache-dir`, else `$XDG_CACHE_HOME/loctree`
+/// or `~/.cache/loctree`. Nothing is written into the analysed tree unless
+/// `--cache-dir` points there; `None` when there is nowhere to write.
+pub fn cache_base(explicit: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = explicit {
+        return Some(dir.to_path_buf());
+    }
+    let non_empty = |var| std::env::var_os(var).filter(|v| !v.is_empty());
+    non_empty("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| non_empty("LOCALAPPDATA").map(PathBuf::from))
+        .map(|dir| dir.join("loctree"))
+}
+
+/// Subdirectory of the cache base for one root: its name and a hash of its
+/// absolute path, so that roots sharing a name do not collide.
+fn root_key(root: &Path) -> String {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let name = root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("root")
+        .to_string();
+    format!(
+        "{}-{:016x}",
+        name,
+        content_hash(root.to_string_lossy().as_bytes())
+    )
+}
+
+/// Content hashes of the manifests in the directories holding `files`
+/// (absolute paths) and their ancestors up to `root`, for the fingerprint.
+pub fn manifest_hashes(root: &Path, files: &[PathBuf]) -> Vec<String> {
+    let dirs: BTreeSet<&Path> = files
+        .iter()
+        .filter_map(|f| f.parent())
+        .flat_map(|dir| dir.ancestors().take_while(|d| d.starts_with(root)))
+        .collect();
+    let mut out = Vec::new();
+    for dir in dirs {
+        for name in MANIFEST_FILES {
+            let path = dir.join(name);
+            if let Ok(bytes) = fs::read(&path) {
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                out.push(format!(
+                    "{}={:016x}",
+                    relative.display(),
+                    content_hash(&bytes)
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// FNV-1a; stable across runs and Rust versions, unlike `DefaultHasher`.
+fn content_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Identifies what the cached analyses depend on besides the files' own
+/// content and the file set: the loctree binary, the options in effect and
+/// the manifests (see `manifest_hashes`).
+pub fn fingerprint(parts: &[String]) -> String {
+    let mut input = format!("{}\n", env!("CARGO_PKG_VERSION"));
+    if let Some(stamp) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| fs::metadata(exe).ok())
+        .and_then(|meta| FileStamp::of(&meta))
+    {
+        input.push_str(&format!(
+            "{}:{}.{}\n",
+            stamp.size, stamp.mtime_secs, stamp.mtime_nanos
+        ));
+    }
+    for part in parts {
+        input.push_str(part);
+        input.push('\n');
+    }
+    format!("{:016x}", content_hash(input.as_bytes()))
+}
@@ -0,0 +1,548 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::json;
+
+use crate::analyzer::offset_to_line;
+use crate::fs_utils::{gather_files, GitIgnoreChecker};
+use crate::report::{escape_html, locations_list, CommandGap, HtmlContext, Locations};
+use crate::tauri::{normalize_command, plugin_command, plugin_of, CommandCoverage, PluginCrates};
+use crate::types::Options;
+
+/// A Tauri v2 capability file (`capabilities/*.json`).
+pub struct Capability {
+    pub identifier: String,
+    pub file: String,
+    /// Permission identifiers with the line they are listed on.
+    pub permissions: Vec<(String, usize)>,
+}
+
+/// `[[permission]]`, `[[set]]` or `[default]` from a permission TOML, with its
+/// identifier qualified by the plugin it belongs to (`clipboard:allow-read`).
+struct PermissionDef {
+    identifier: String,
+    plugin: Option<String>,
+    file: String,
+    allow: Vec<(String, usize)>,
+    deny: Vec<(String, usize)>,
+    includes: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct CapabilityIndex {
+    pub capabilities: Vec<Capability>,
+    permissions: HashMap<String, PermissionDef>,
+    /// The app ships permission files for its own commands, so those commands
+    /// need a grant as well (otherwise Tauri allows all app commands).
+    app_scoped: bool,
+}
+
+/// Permission problem tied to a file and line: a permission naming a command
+/// that does not exist, or a capability granting a command nobody invokes.
+#[derive(Clone)]
+pub struct PermissionIssue {
+    pub capability: Option<String>,
+    pub permission: String,
+    pub command: String,
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Default)]
+pub struct CapabilityReport {
+    pub capability_files: Vec<(String, String)>,
+    /// Frontend invokes no capability allows, with their call sites.
+    pub ungranted: Vec<(String, Vec<(String, usize)>)>,
+    pub dangling: Vec<PermissionIssue>,
+    pub unused_grants: Vec<PermissionIssue>,
+}
+
+#[derive(Default)]
+struct Grants {
+    commands: BTreeSet<String>,
+    /// Plugins granted as a whole (`dialog:default` for a plugin whose
+    /// permission files are not in the project).
+    plugins: HashSet<String>,
+}
+
+fn regex_toml_string() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#""([^"]*)"|'([^']*)'"#).unwrap())
+}
+
+/// Checks the Tauri v2 capabilities and permission files under `root`, when
+/// the project has them, against the commands in use.
+pub fn check_capabilities(
+    root: &Path,
+    options: &Options,
+    git_checker: Option<&GitIgnoreChecker>,
+    commands: &mut CommandCoverage,
+) -> io::Result<Option<CapabilityReport>> {
+    let mut manifest_options = options.clone();
+    manifest_options.extensions = Some(["json", "toml"].iter().map(|e| e.to_string()).collect());
+    let mut manifest_files = Vec::new();
+    gather_files(root, &manifest_options, 0, git_checker, &mut manifest_files)?;
+    let index = load_capabilities(root, &manifest_files, &mut commands.plugins);
+    Ok((!index.is_empty()).then(|| index.check(&commands.frontend, &commands.backend)))
+}
+
+/// Loads capability JSON files (any `capabilities/` directory) and permission
+/// TOMLs (anything under a `permissions/` directory) among `files`.
+fn load_capabilities(
+    root: &Path,
+    files: &[PathBuf],
+    plugins: &mut PluginCrates,
+) -> CapabilityIndex {
+    let mut index = CapabilityIndex::default();
+    for path in files {
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let in_dir = |name: &str| {
+            Path::new(&relative)
+                .parent()
+                .is_some_and(|dir| dir.components().any(|c| c.as_os_str() == name))
+        };
+        let Ok(raw) = fs::read_to_string(path) else {
+            continue;
+        };
+        if ext == "json" && in_dir("capabilities") {
+            parse_capability_json(&raw, &relative, &mut index.capabilities);
+        } else if ext == "toml" && in_dir("permissions") {
+            let plugin = plugins.plugin_for(&relative);
+            index.app_scoped |= plugin.is_none();
+            for def in parse_permission_toml(&raw, &relative, plugin) {
+                index.permissions.insert(def.identifier.clone(), def);
+            }
+        }
+    }
+    index
+}
+
+fn parse_capability_json(raw: &str, file: &str, out: &mut Vec<Capability>) {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(raw) else {
+        eprintln!("[loctree][warn] could not parse capability file {}", file);
+        return;
+    };
+    let entries: Vec<&serde_json::Value> = match &value {
+        serde_json::Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+    let stem = Path::new(file)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    for entry in entries {
+        let Some(perms) = entry.get("permissions").and_then(|p| p.as_array()) else {
+            continue;
+        };
+        let mut search_from = 0;
+        let permissions = perms
+            .iter()
+            .filter_map(|p| {
+                p.as_str()
+                    .or_else(|| p.get("identifier").and_then(|id| id.as_str()))
+            })
+            .map(|id| {
+                // Capabilities are small; the next quoted occurrence is the entry.
+                let needle = format!("\"{}\"", id);
+                let offset = raw[search_from..]
+                    .find(&needle)
+                    .map(|pos| search_from + pos)
+                    .or_else(|| raw.find(&needle))
+                    .unwrap_or(0);
+                search_from = offset;
+                (id.to_string(), offset_to_line(raw, offset))
+            })
+            .collect();
+        out.push(Capability {
+            identifier: entry
+                .get("identifier")
+                .and_then(|id| id.as_str())
+                .map_or_else(|| stem.clone(), str::to_string),
+            file: file.to_string(),
+            permissions,
+        });
+    }
+}
+
+/// Reads the parts of a permission TOML that name commands or other
+/// permissions; scopes and descriptions are ignored.
+fn parse_permission_toml(raw: &str, file: &str, plugin: Option<String>) -> Vec<PermissionDef> {
+    let qualify = |id: &str| match &plugin {
+        Some(p) if !id.contains(':') => format!("{}:{}", p, id),
+        _ => id.to_string(),
+    };
+    let mut defs = Vec::new();
+    let mut current: Option<PermissionDef> = None;
+    let mut in_commands = false;
+    let mut lines = raw.lines().enumerate().peekable();
+    while let Some((idx, line)) = lines.next() {
+        let text = strip_toml_comment(line).trim();
+        if text.starts_with('[') {
+            let header = text.trim_matches(|c| c == '[' || c == ']').trim();
+            in_commands = header == "permission.commands";
+            if matches!(header, "permission" | "set" | "default") {
+                defs.extend(current.take());
+                current = Some(PermissionDef {
+                    identifier: if header == "default" {
+                        qualify("default")
+                    } else {
+                        String::new()
+                    },
+                    plugin: plugin.clone(),
+                    file: file.to_string(),
+                    allow: Vec::new(),
+                    deny: Vec::new(),
+                    includes: Vec::new(),
+                });
+            } else if !in_commands && !header.starts_with("permission.") {
+                defs.extend(current.take());
+            }
+            continue;
+        }
+        let Some(def) = current.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = text.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        // Gather a multi-line array together with the line of each piece.
+        let mut pieces = vec![(idx + 1, value.to_string())];
+        let mut depth = bracket_depth(value);
+        while depth > 0 {
+            let Some((next_idx, next)) = lines.next() else {
+                break;
+            };
+            let next = strip_toml_comment(next);
+            depth += bracket_depth(next);
+            pieces.push((next_idx + 1, next.to_string()));
+        }
+        let strings = || {
+            pieces.iter().flat_map(|(line, piece)| {
+                regex_toml_string()
+                    .captures_iter(piece)
+                    .filter_map(move |c| {
+                        c.get(1)
+                            .or_else(|| c.get(2))
+                            .map(|m| (m.as_str().to_string(), *line))
+                    })
+            })
+        };
+        match (key, in_commands) {
+            ("identifier", false) => {
+                if let Some((id, _)) = strings().next() {
+                    def.identifier = qualify(&id);
+                }
+            }
+            ("commands.allow", false) | ("allow", true) => def.allow.extend(strings()),
+            ("commands.deny", false) | ("deny", true) => def.deny.extend(strings()),
+            ("permissions", false) => def.includes.extend(strings().map(|(id, _)| qualify(&id))),
+            _ => {}
+        }
+    }
+    defs.extend(current);
+    defs.retain(|d| !d.identifier.is_empty());
+    defs
+}
+
+fn strip_toml_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    for (idx, ch) in line.char_indices() {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(q), c) if c == q => quote = None,
+            (None, '#') => return &line[..idx],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn bracket_depth(text: &str) -> isize {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for ch in text.chars() {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(q), c) if c == q => quote = None,
+            (None, '[') => depth += 1,
+            (None, ']') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// Splits `plugin:local` (and `core:plugin:local`) into its plugin and local part.
+fn split_permission(id: &str) -> (Option<&str>, &str) {
+    let id = id.strip_prefix("core:").unwrap_or(id);
+    match id.split_once(':') {
+        Some((plugin, local)) => (Some(plugin), local),
+        None => (None, id),
+    }
+}
+
+/// Command an implicit `allow-<command>`/`deny-<command>` permission names.
+fn implicit_command(local: &str) -> Option<String> {
+    local
+        .strip_prefix("allow-")
+        .or_else(|| local.strip_prefix("deny-"))
+        .map(|cmd| cmd.replace('-', "_"))
+}
+
+fn command_key(plugin: Option<&str>, command: &str) -> String {
+    match plugin {
+        Some(plugin) => plugin_command(plugin, command),
+        None => normalize_command(command),
+    }
+}
+
+impl CapabilityIndex {
+    pub fn is_empty(&self) -> bool {
+        self.capabilities.is_empty()
+    }
+
+    fn resolve(&self, id: &str, seen: &mut HashSet<String>, grants: &mut Grants) {
+        if !seen.insert(id.to_string()) {
+            return;
+        }
+        if let Some(def) = self.permissions.get(id) {
+            for (command, _) in &def.allow {
+                grants
+                    .commands
+                    .insert(command_key(def.plugin.as_deref(), command));
+            }
+            for include in &def.includes {
+                self.resolve(include, seen, grants);
+            }
+            return;
+        }
+        let (plugin, local) = split_permission(id);
+        if local.starts_with("deny-") {
+            return;
+        }
+        match (implicit_command(local), plugin) {
+            (Some(command), _) => {
+                grants.commands.insert(command_key(plugin, &command));
+            }
+            (None, Some(plugin)) => {
+                grants.plugins.insert(plugin.to_string());
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Cross-checks capabilities against frontend invokes (`fe`) and backend
+    /// handlers (`be`), both keyed by normalised command name.
+    pub fn check(&self, fe: &Locations, be: &Locations) -> CapabilityReport {
+        let in_workspace = |plugin: Option<&str>| match plugin {
+            None => true,
+            Some(p) => {
+                self.permissions
+                    .values()
+                    .any(|d| d.plugin.as_deref() == Some(p))
+                    || be.keys().any(|k| plugin_of(k) == Some(p))
+            }
+        };
+
+        let mut all = Grants::default();
+        let mut unused_grants = Vec::new();
+        let mut dangling = Vec::new();
+        for capability in &self.capabilities {
+            let mut reported: HashSet<String> = HashSet::new();
+            for (permission, line) in &capability.permissions {
+                let mut grants = Grants::default();
+                self.resolve(permission, &mut HashSet::new(), &mut grants);
+                for command in &grants.commands {
+                    if be.contains_key(command)
+                        && !fe.contains_key(command)
+                        && reported.insert(command.clone())
+                    {
+                        unused_grants.push(PermissionIssue {
+                            capability: Some(capability.identifier.clone()),
+                            permission: permission.clone(),
+                            command: command.clone(),
+                            file: capability.file.clone(),
+                            line: *line,
+                        });
+                    }
+                }
+                all.commands.extend(grants.commands);
+                all.plugins.extend(grants.plugins);
+
+                if self.permissions.contains_key(permission) {
+                    continue;
+                }
+                let (plugin, local) = split_permission(permission);
+                if let Some(command) = implicit_command(local) {
+                    let key = command_key(plugin, &command);
+                    if in_workspace(plugin) && !be.contains_key(&key) {
+                        dangling.push(PermissionIssue {
+                            capability: Some(capability.identifier.clone()),
+                            permission: permission.clone(),
+                            command: key,
+                            file: capability.file.clone(),
+                            line: *line,
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut defs: Vec<&PermissionDef> = self.permissions.values().collect();
+        defs.sort_by(|a, b| (&a.file, &a.identifier).cmp(&(&b.file, &b.identifier)));
+        for def in defs {
+            for (command, line) in def.allow.iter().chain(&def.deny) {
+                let key = command_key(def.plugin.as_deref(), command);
+                if !be.contains_key(&key) {
+                    dangling.push(PermissionIssue {
+                        capability: None,
+                        permission: def.identifier.clone(),
+                        command: key,
+                        file: def.file.clone(),
+                        line: *line,
+                    });
+                }
+            }
+        }
+
+        let mut ungranted: Vec<(String, Vec<(String, usize)>)> = fe
+            .iter()
+            .filter(|(command, _)| {
+                if all.commands.contains(*command) {
+                    return false;
+                }
+                match plugin_of(command) {
+                    Some(plugin) => !all.plugins.contains(plugin),
+                    None => self.app_scoped && be.contains_key(*command),
+                }
+            })
+            .map(|(command, locs)| (command.clone(), locs.clone()))
+            .collect();
+        ungranted.sort_by(|a, b| a.0.cmp(&b.0));
+
+        CapabilityReport {
+            capability_files: self
+                .capabilities
+                .iter()
+                .map(|c| (c.identifier.clone(), c.file.clone()))
+                .collect(),
+            ungranted,
+            dangling,
+            unused_grants,
+        }
+    }
+}
+
+impl CapabilityReport {
+    fn has_issues(&self) -> bool {
+        !self.ungranted.is_empty() || !self.dangling.is_empty() || !self.unused_grants.is_empty()
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "files": self.capability_files.iter().map(|(id, file)| json!({"identifier": id, "file": file})).collect::<Vec<_>>(),
+            "ungrantedCommands": self.ungranted.iter().map(|(name, locs)| json!({"name": name, "locations": locs})).collect::<Vec<_>>(),
+            "danglingPermissions": self.dangling.iter().map(permission_issue_json).collect::<Vec<_>>(),
+            "unusedGrants": self.unused_grants.iter().map(permission_issue_json).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self) {
+        if !self.has_issues() {
+            return;
+        }
+        println!(
+            "\nTauri capabilities ({} file(s)):",
+            self.capability_files.len()
+        );
+        if !self.ungranted.is_empty() {
+            println!("  Invoked but not granted by any capability:");
+            for (name, locs) in &self.ungranted {
+                println!("    - {} ({})", name, locations_list(locs));
+            }
+        }
+        for (label, issues) in [
+            ("Permissions naming unknown commands", &self.dangling),
+            ("Granted but never invoked", &self.unused_grants),
+        ] {
+            if issues.is_empty() {
+                continue;
+            }
+            println!("  {}:", label);
+            for issue in issues {
+                println!(
+                    "    - {} via {} ({}:{})",
+                    issue.command, issue.permission, issue.file, issue.line
+                );
+            }
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        out.push_str(&format!(
+            "<h3>Tauri capabilities</h3><p class=\"muted\">{} capability file(s): {}</p>",
+            self.capability_files.len(),
+            self.capability_files
+                .iter()
+                .map(|(id, file)| format!(
+                    "<code>{}</code> ({})",
+                    escape_html(id),
+                    ctx.link(file, 1)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        if !self.has_issues() {
+            out.push_str("<p class=\"muted\">Capabilities match the commands in use.</p>");
+            return;
+        }
+        let ungranted: Vec<CommandGap> = self
+            .ungranted
+            .iter()
+            .map(|(name, locs)| CommandGap::new(name, None, locs))
+            .collect();
+        let issues = |issues: &[PermissionIssue]| {
+            if issues.is_empty() {
+                return "<span class=\"muted\">None</span>".to_string();
+            }
+            issues
+                .iter()
+                .map(|i| {
+                    format!(
+                        "<code>{}</code> via <code>{}</code> ({})",
+                        escape_html(&i.command),
+                        escape_html(&i.permission),
+                        ctx.link(&i.file, i.line)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" · ")
+        };
+        out.push_str(&format!(
+            "<table><tr><th>Invoked, not granted</th><th>Permissions naming unknown commands</th><th>Granted, never invoked</th></tr><tr><td>{}</td><td>{}</td><td>{}</td></tr></table>",
+            ctx.gap_list(&ungranted),
+            issues(&self.dangling),
+            issues(&self.unused_grants)
+        ));
+    }
+}
+
+fn permission_issue_json(issue: &PermissionIssue) -> serde_json::Value {
+    json!({
+        "capability": issue.capability,
+        "permission": issue.permission,
+        "command": issue.command,
+        "file": issue.file,
+        "line": issue.line,
+    })
+}
@@ -0,0 +1,384 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use serde_json::json;
+
+use crate::report::HtmlContext;
+use crate::types::FileAnalysis;
+
+/// Minimum clone length in tokens for `--clones` unless `--clone-tokens` says otherwise.
+pub const DEFAULT_CLONE_TOKENS: usize = 50;
+
+#[derive(Clone)]
+pub struct CloneCopy {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+impl CloneCopy {
+    pub fn lines(&self) -> usize {
+        self.end_line + 1 - self.start_line
+    }
+}
+
+/// Code that appears (modulo identifier names and literal values) in several
+/// places.
+#[derive(Clone)]
+pub struct CloneGroup {
+    pub tokens: usize,
+    pub copies: Vec<CloneCopy>,
+}
+
+impl CloneGroup {
+    /// Lines that would go away if every copy but one were removed.
+    pub fn duplicated_loc(&self) -> usize {
+        let total: usize = self.copies.iter().map(CloneCopy::lines).sum();
+        total - self.copies.iter().map(CloneCopy::lines).max().unwrap_or(0)
+    }
+}
+
+/// Kept as-is so that clones must share their control flow.
+const KEYWORDS: &str = "\
+    and as async await break case catch class const continue def default del delete do elif else \
+    enum except export extends False false finally fn for from function if impl import in \
+    instanceof interface is lambda let loop match mod mut new None not null of or pass pub raise \
+    ref return self Self static struct super switch this throw trait True true try type typeof \
+    undefined use var void where while with yield";
+
+fn keywords() -> &'static HashSet<&'static str> {
+    static SET: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    SET.get_or_init(|| KEYWORDS.split_whitespace().collect())
+}
+
+/// Normalised tokens of a file for clone detection, import statements left out.
+pub fn clone_tokens(content: &str, ext: &str) -> Vec<(String, usize)> {
+    drop_imports(tokenize(content, ext), ext == "py")
+}
+
+/// Splits source into normalised tokens with their line: identifiers become
+/// `$id`, string and number literals `$str`/`$num`, comments are dropped.
+fn tokenize(content: &str, ext: &str) -> Vec<(String, usize)> {
+    let hash_comments = ext == "py";
+    let slash_comments = ext != "py";
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if (hash_comments && c == '#') || (slash_comments && c == '/' && next == Some('/')) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if slash_comments && c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '`' || (c == '\'' && !(ext == "rs" && is_lifetime(&chars, i))) {
+            let start_line = line;
+            let triple = ext == "py" && next == Some(c) && chars.get(i + 2) == Some(&c);
+            i += if triple { 3 } else { 1 };
+            while i < chars.len() {
+                if chars[i] == '\\' {
+                    i += 2;
+                    continue;
+                }
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                if chars[i] == c
+                    && (!triple || (chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c)))
+                {
+                    i += if triple { 3 } else { 1 };
+                    break;
+                }
+                i += 1;
+            }
+            out.push(("$str".to_string(), start_line));
+        } else if c.is_ascii_digit() {
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_')
+            {
+                i += 1;
+            }
+            out.push(("$num".to_string(), line));
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if keywords().contains(word.as_str()) {
+                out.push((word, line));
+            } else {
+                out.push(("$id".to_string(), line));
+            }
+        } else {
+            out.push((c.to_string(), line));
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Removes import statements (`import`, `use`, `from ... import`), whose
+/// shape repeats in every file without being copy-paste.
+fn drop_imports(tokens: Vec<(String, usize)>, python: bool) -> Vec<(String, usize)> {
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut skipping = false;
+    // A JS import may end at its specifier, with or without a `;`.
+    let mut after_specifier = false;
+    let mut depth = 0usize;
+    let mut prev_line = 0;
+    for (token, line) in tokens {
+        let line_start = line != prev_line;
+        prev_line = line;
+        if std::mem::take(&mut after_specifier) && token == ";" {
+            continue;
+        }
+        if skipping {
+            if python && line_start && depth == 0 {
+                skipping = false;
+            } else {
+                match token.as_str() {
+                    "(" | "{" | "[" => depth += 1,
+                    ")" | "}" | "]" => depth = depth.saturating_sub(1),
+                    ";" => skipping = false,
+                    "$str" if depth == 0 && !python => {
+                        skipping = false;
+                        after_specifier = true;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+        }
+        if line_start && matches!(token.as_str(), "import" | "use" | "from") {
+            skipping = true;
+            depth = 0;
+            continue;
+        }
+        out.push((token, line));
+    }
+    out
+}
+
+/// `'a` in Rust is a lifetime unless it closes as a char literal (`'a'`).
+fn is_lifetime(chars: &[char], i: usize) -> bool {
+    chars
+        .get(i + 1)
+        .is_some_and(|c| c.is_alphabetic() || *c == '_')
+        && chars.get(i + 2) != Some(&'\'')
+}
+
+struct Tokens {
+    ids: Vec<u32>,
+    lines: Vec<usize>,
+}
+
+/// Finds clone groups of at least `min_tokens` tokens across the analysed
+/// files, from the tokens `clone_tokens` collected while analysing them.
+pub fn detect_clones(analyses: &[FileAnalysis], min_tokens: usize) -> Vec<CloneGroup> {
+    if min_tokens == 0 {
+        return Vec::new();
+    }
+    // Interned in file order so token ids do not depend on scheduling.
+    let mut interner: HashMap<&str, u32> = HashMap::new();
+    let mut tokens: Vec<Tokens> = Vec::new();
+    for analysis in analyses {
+        let mut ids = Vec::new();
+        let mut lines = Vec::new();
+        for (token, line) in &analysis.clone_tokens {
+            let next = interner.len() as u32;
+            ids.push(*interner.entry(token.as_str()).or_insert(next));
+            lines.push(*line);
+        }
+        tokens.push(Tokens { ids, lines });
+    }
+
+    // Rolling hash of every window of `min_tokens` tokens.
+    const BASE: u64 = 1_000_003;
+    let top = (1..min_tokens).fold(1u64, |acc, _| acc.wrapping_mul(BASE));
+    let mut windows: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+    for (file_idx, t) in tokens.iter().enumerate() {
+        if t.ids.len() < min_tokens {
+            continue;
+        }
+        let mut hash = 0u64;
+        for (pos, id) in t.ids.iter().enumerate() {
+            if pos >= min_tokens {
+                let old = t.ids[pos - min_tokens] as u64 + 1;
+                hash = hash.wrapping_sub(old.wrapping_mul(top));
+            }
+            hash = hash.wrapping_mul(BASE).wrapping_add(*id as u64 + 1);
+            if pos + 1 >= min_tokens {
+                windows
+                    .entry(hash)
+                    .or_default()
+                    .push((file_idx, pos + 1 - min_tokens));
+            }
+        }
+    }
+
+    let same = |a: (usize, usize), b: (usize, usize), len: usize| {
+        tokens[a.0].ids[a.1..a.1 + len] == tokens[b.0].ids[b.1..b.1 + len]
+    };
+    let mut groups = Vec::new();
+    let mut seen: HashSet<Vec<(usize, usize)>> = HashSet::new();
+    for occurrences in windows.values() {
+        if occurrences.len() < 2 {
+            continue;
+        }
+        // Drop hash collisions and windows overlapping an earlier one in the same file.
+        let first = occurrences[0];
+        let mut group: Vec<(usize, usize)> = Vec::new();
+        for &occ in occurrences {
+            if !same(first, occ, min_tokens) {
+                continue;
+            }
+            if group
+                .iter()
+                .any(|&(f, p)| f == occ.0 && occ.1 < p + min_tokens)
+            {
+                continue;
+            }
+            group.push(occ);
+        }
+        if group.len() < 2 {
+            continue;
+        }
+        // Literal tables (`"a", "b", ...`) repeat a couple of tokens; that is data, not code.
+        let (f, p) = group[0];
+        let distinct: HashSet<u32> = tokens[f].ids[p..p + min_tokens].iter().copied().collect();
+        if distinct.len() < (min_tokens / 5).max(3) {
+            continue;
+        }
+        // Only start groups where the clone starts, not one token into it.
+        let continues = group.iter().all(|&(_, p)| p > 0)
+            && group
+                .iter()
+                .all(|&(f, p)| tokens[f].ids[p - 1] == tokens[group[0].0].ids[group[0].1 - 1]);
+        if continues {
+            continue;
+        }
+        // Extend while every copy still matches and copies in one file stay apart.
+        let mut len = min_tokens;
+        while let Some(next) = tokens[group[0].0].ids.get(group[0].1 + len) {
+            let extends = group.iter().all(|&(f, p)| {
+                tokens[f].ids.get(p + len) == Some(next)
+                    && !group
+                        .iter()
+                        .any(|&(g, q)| g == f && q > p && p + len + 1 > q)
+            });
+            if !extends {
+                break;
+            }
+            len += 1;
+        }
+        group.sort();
+        if !seen.insert(group.clone()) {
+            continue;
+        }
+        groups.push(CloneGroup {
+            tokens: len,
+            copies: group
+                .iter()
+                .map(|&(f, p)| CloneCopy {
+                    file: analyses[f].path.clone(),
+                    start_line: tokens[f].lines[p],
+                    end_line: tokens[f].lines[p + len - 1],
+                })
+                .collect(),
+        });
+    }
+    groups.sort_by(|a, b| {
+        b.duplicated_loc()
+            .cmp(&a.duplicated_loc())
+            .then(b.tokens.cmp(&a.tokens))
+            .then_with(|| a.copies[0].file.cmp(&b.copies[0].file))
+            .then(a.copies[0].start_line.cmp(&b.copies[0].start_line))
+    });
+    groups
+}
+
+pub fn clones_json(groups: &[CloneGroup]) -> serde_json::Value {
+    json!({
+        "clones": groups
+            .iter()
+            .map(|group| json!({
+                "tokens": group.tokens,
+                "duplicatedLoc": group.duplicated_loc(),
+                "copies": group.copies.iter().map(|c| json!({
+                    "file": c.file,
+                    "startLine": c.start_line,
+                    "endLine": c.end_line,
+                })).collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+pub fn print_clones(groups: &[CloneGroup], limit: usize) {
+    if groups.is_empty() {
+        return;
+    }
+    println!(
+        "\nCode clones: {} groups, {} duplicated LOC (showing up to {}):",
+        groups.len(),
+        groups.iter().map(CloneGroup::duplicated_loc).sum::<usize>(),
+        limit
+    );
+    for group in groups.iter().take(limit) {
+        println!(
+            "  - {} tokens, {} duplicated LOC: {}",
+            group.tokens,
+            group.duplicated_loc(),
+            group
+                .copies
+                .iter()
+                .map(|c| format!("{}:{}-{}", c.file, c.start_line, c.end_line))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+pub fn clones_html(groups: &[CloneGroup], out: &mut String, ctx: &HtmlContext) {
+    if groups.is_empty() {
+        return;
+    }
+    out.push_str(&format!(
+        "<h3>Code clones</h3><p class=\"muted\">{} groups, {} duplicated LOC</p>",
+        groups.len(),
+        groups.iter().map(CloneGroup::duplicated_loc).sum::<usize>()
+    ));
+    out.push_str("<table><tr><th>Copies</th><th>Tokens</th><th>Duplicated LOC</th></tr>");
+    for group in groups.iter().take(ctx.limit) {
+        let copies: Vec<String> = group
+            .copies
+            .iter()
+            .map(|c| format!("{}–{}", ctx.link(&c.file, c.start_line), c.end_line))
+            .collect();
+        out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            copies.join("<br>"),
+            group.tokens,
+            group.duplicated_loc()
+        ));
+    }
+    out.push_str("</table>");
+}
@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+
+pub const CONFIG_FILE_NAME: &str = ".loctree.json";
+
+/// Analyzer configuration read from `.loctree.json` in the analyzed root or from `--config`.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LoctreeConfig {
+    pub rules: Vec<DependencyRule>,
+    /// Frontend functions that invoke Tauri commands; defaults to
+    /// `safeInvoke`, `invokeSnake` and `invoke`.
+    pub invoke_wrappers: Option<Vec<String>>,
+    /// Duplicate-export collision kinds to report (`value`, `type`,
+    /// `default`, `module`, `mixed`); defaults to `value`, `type` and `default`.
+    pub duplicate_kinds: Option<Vec<String>>,
+}
+
+/// A dependency rule: files matching `from` must not import anything matching
+/// `forbid`, and when `allow` is set they may only import matching files.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyRule {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub from: String,
+    #[serde(default)]
+    pub forbid: Vec<String>,
+    #[serde(default)]
+    pub allow: Option<Vec<String>>,
+}
+
+impl DependencyRule {
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.from.clone())
+    }
+}
+
+/// Loads the config for a root. An explicit `--config` path wins; otherwise
+/// `<root>/.loctree.json` is used when present.
+pub fn load_config(root: &Path, explicit: Option<&PathBuf>) -> Result<LoctreeConfig, String> {
+    let path = match explicit {
+        Some(path) => path.clone(),
+        None => {
+            let candidate = root.join(CONFIG_FILE_NAME);
+            if !candidate.is_file() {
+                return Ok(LoctreeConfig::default());
+            }
+            candidate
+        }
+    };
+    let raw = fs::read_to_string(&path)
+        .map_err(|err| format!("Could not read config {}: {}", path.display(), err))?;
+    let config: LoctreeConfig = serde_json::from_str(&raw)
+        .map_err(|err| format!("Invalid config {}: {}", path.display(), err))?;
+    for rule in &config.rules {
+        let patterns = std::iter::once(&rule.from)
+            .chain(&rule.forbid)
+            .chain(rule.allow.iter().flatten());
+        for pattern in patterns {
+            compile_glob(pattern).map_err(|err| {
+                format!(
+                    "Invalid config {}: rule '{}' has an invalid pattern '{}': {}",
+                    path.display(),
+                    rule.label(),
+                    pattern,
+                    err
+                )
+            })?;
+        }
+    }
+    Ok(config)
+}
+
+/// Compiles a path glob (`*`, `?`, `**`, `{a,b}`) into an anchored regex. A pattern
+/// without wildcards matches the path itself and everything below it.
+pub fn compile_glob(pattern: &str) -> Result<Regex, String> {
+    let pattern = pattern.trim().trim_start_matches("./").replace('\\', "/");
+    let mut out = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    let mut has_wildcard = false;
+    let mut braces = 0usize;
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' => {
+                has_wildcard = true;
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        out.push_str("(?:.*/)?");
+                    } else {
+                        out.push_str(".*");
+                    }
+                } else {
+                    out.push_str("[^/]*");
+                }
+            }
+            '?' => {
+                has_wildcard = true;
+                out.push_str("[^/]");
+            }
+            '{' => {
+                has_wildcard = true;
+                braces += 1;
+                out.push_str("(?:");
+            }
+            '}' if braces > 0 => {
+                braces -= 1;
+                out.push(')');
+            }
+            ',' if braces > 0 => out.push('|'),
+            other => out.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    if braces > 0 {
+        return Err("unclosed `{`".to_string());
+    }
+    if !has_wildcard {
+        out.push_str("(?:/.*)?");
+    }
+    out.push('$');
+    Regex::new(&out).map_err(|err| err.to_string())
+}
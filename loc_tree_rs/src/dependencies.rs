@@ -0,0 +1,1006 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::analyzer::python_search_bases;
+use crate::report::{escape_html, HtmlContext};
+use crate::types::{FileAnalysis, ImportKind};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Ecosystem {
+    Npm,
+    Cargo,
+    Pypi,
+}
+
+impl Ecosystem {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Ecosystem::Npm => "npm",
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::Pypi => "pypi",
+        }
+    }
+
+    fn for_path(path: &str) -> Option<Self> {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|s| s.to_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => Some(Ecosystem::Npm),
+            "rs" => Some(Ecosystem::Cargo),
+            "py" => Some(Ecosystem::Pypi),
+            _ => None,
+        }
+    }
+
+    fn manifest_names(&self) -> &'static [&'static str] {
+        match self {
+            Ecosystem::Npm => &["package.json"],
+            Ecosystem::Cargo => &["Cargo.toml"],
+            Ecosystem::Pypi => &["pyproject.toml", "requirements.txt", "requirements-dev.txt"],
+        }
+    }
+
+    /// Key used to match an import name against a declared dependency.
+    fn normalise(&self, name: &str) -> String {
+        match self {
+            Ecosystem::Npm => name.to_string(),
+            Ecosystem::Cargo => name.replace('-', "_"),
+            Ecosystem::Pypi => name.to_lowercase().replace(['-', '.'], "_"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ExternalPackage {
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    pub usages: usize,
+    pub files: Vec<String>,
+    pub declared_in: Option<String>,
+    /// Some importing file has a manifest for the ecosystem above it.
+    pub has_manifest: bool,
+}
+
+#[derive(Clone)]
+pub struct UndeclaredDependency {
+    pub name: String,
+    pub locations: Vec<(String, usize)>,
+}
+
+#[derive(Clone)]
+pub struct UnusedDependency {
+    pub name: String,
+    pub section: String,
+}
+
+#[derive(Clone)]
+pub struct ManifestReport {
+    pub path: String,
+    pub ecosystem: Ecosystem,
+    pub declared: usize,
+    pub undeclared: Vec<UndeclaredDependency>,
+    pub unused: Vec<UnusedDependency>,
+}
+
+#[derive(Clone)]
+pub struct DependencyInventory {
+    pub packages: Vec<ExternalPackage>,
+    pub manifests: Vec<ManifestReport>,
+}
+
+impl DependencyInventory {
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "packages": self.packages.iter().map(|p| json!({
+                "name": p.name,
+                "ecosystem": p.ecosystem.label(),
+                "usages": p.usages,
+                "files": p.files,
+                "declaredIn": p.declared_in,
+                "hasManifest": p.has_manifest,
+            })).collect::<Vec<_>>(),
+            "manifests": self.manifests.iter().map(|m| json!({
+                "path": m.path,
+                "ecosystem": m.ecosystem.label(),
+                "declared": m.declared,
+                "undeclared": m.undeclared.iter().map(|d| json!({"name": d.name, "locations": d.locations})).collect::<Vec<_>>(),
+                "unused": m.unused.iter().map(|d| json!({"name": d.name, "section": d.section})).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self, limit: usize) {
+        if !self.packages.is_empty() {
+            println!(
+                "\nExternal dependencies: {} packages (showing up to {}):",
+                self.packages.len(),
+                limit
+            );
+            for pkg in self.packages.iter().take(limit) {
+                println!(
+                    "  - {} ({}): {} imports in {} files{}",
+                    pkg.name,
+                    pkg.ecosystem.label(),
+                    pkg.usages,
+                    pkg.files.len(),
+                    match (&pkg.declared_in, pkg.has_manifest) {
+                        (Some(_), _) => "",
+                        (None, true) => "  [undeclared]",
+                        (None, false) => "  [no manifest]",
+                    }
+                );
+            }
+        }
+        for manifest in &self.manifests {
+            if manifest.undeclared.is_empty() && manifest.unused.is_empty() {
+                continue;
+            }
+            println!(
+                "\nManifest {} ({}):",
+                manifest.path,
+                manifest.ecosystem.label()
+            );
+            if !manifest.undeclared.is_empty() {
+                println!(
+                    "  Imported but undeclared: {}",
+                    manifest
+                        .undeclared
+                        .iter()
+                        .map(|d| {
+                            let (file, line) = &d.locations[0];
+                            format!("{} ({}:{})", d.name, file, line)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            if !manifest.unused.is_empty() {
+                println!(
+                    "  Declared but never imported: {}",
+                    manifest
+                        .unused
+                        .iter()
+                        .map(|d| d.name.clone())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        if self.packages.is_empty() && self.manifests.is_empty() {
+            return;
+        }
+        out.push_str("<h3>External dependencies</h3>");
+        out.push_str(
+            "<table><tr><th>Package</th><th>Ecosystem</th><th>Imports</th><th>Files</th><th>Declared in</th></tr>",
+        );
+        for pkg in self.packages.iter().take(ctx.limit) {
+            out.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&pkg.name),
+                pkg.ecosystem.label(),
+                pkg.usages,
+                pkg.files.len(),
+                match (&pkg.declared_in, pkg.has_manifest) {
+                    (Some(path), _) => escape_html(path),
+                    (None, true) => "<span class=\"muted\">undeclared</span>".to_string(),
+                    (None, false) => "<span class=\"muted\">no manifest</span>".to_string(),
+                }
+            ));
+        }
+        out.push_str("</table>");
+        for manifest in &self.manifests {
+            if manifest.undeclared.is_empty() && manifest.unused.is_empty() {
+                continue;
+            }
+            out.push_str(&format!(
+                "<p><code>{}</code> ({} declared)</p><ul>",
+                escape_html(&manifest.path),
+                manifest.declared
+            ));
+            for dep in &manifest.undeclared {
+                let locs: Vec<String> =
+                    dep.locations.iter().map(|(f, l)| ctx.link(f, *l)).collect();
+                out.push_str(&format!(
+                    "<li>Imported but undeclared: <code>{}</code> ({})</li>",
+                    escape_html(&dep.name),
+                    locs.join("; ")
+                ));
+            }
+            for dep in &manifest.unused {
+                out.push_str(&format!(
+                    "<li>Declared but never imported: <code>{}</code> <span class=\"muted\">[{}]</span></li>",
+                    escape_html(&dep.name),
+                    escape_html(&dep.section)
+                ));
+            }
+            out.push_str("</ul>");
+        }
+    }
+}
+
+struct PackageUsage {
+    usages: usize,
+    files: Vec<String>,
+    /// Nearest manifest of each importing file, in first-use order.
+    manifest_dirs: Vec<PathBuf>,
+}
+
+struct DeclaredDep {
+    name: String,
+    section: String,
+}
+
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
+/// Path roots that are not crates: keywords, the sysroot and primitive types
+/// (`u32::MAX`, `str::from_utf8`).
+const RUST_BUILTIN_ROOTS: &[&str] = &[
+    "crate",
+    "self",
+    "super",
+    "std",
+    "core",
+    "alloc",
+    "proc_macro",
+    "test",
+    "bool",
+    "char",
+    "str",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "f32",
+    "f64",
+];
+
+const PYTHON_STDLIB: &[&str] = &[
+    "__future__",
+    "abc",
+    "argparse",
+    "array",
+    "ast",
+    "asyncio",
+    "atexit",
+    "base64",
+    "bisect",
+    "builtins",
+    "bz2",
+    "calendar",
+    "cmath",
+    "cmd",
+    "codecs",
+    "collections",
+    "colorsys",
+    "concurrent",
+    "configparser",
+    "contextlib",
+    "contextvars",
+    "copy",
+    "copyreg",
+    "csv",
+    "ctypes",
+    "curses",
+    "dataclasses",
+    "datetime",
+    "decimal",
+    "difflib",
+    "dis",
+    "doctest",
+    "email",
+    "encodings",
+    "enum",
+    "errno",
+    "faulthandler",
+    "fcntl",
+    "filecmp",
+    "fileinput",
+    "fnmatch",
+    "fractions",
+    "ftplib",
+    "functools",
+    "gc",
+    "getopt",
+    "getpass",
+    "gettext",
+    "glob",
+    "graphlib",
+    "gzip",
+    "hashlib",
+    "heapq",
+    "hmac",
+    "html",
+    "http",
+    "imaplib",
+    "importlib",
+    "inspect",
+    "io",
+    "ipaddress",
+    "itertools",
+    "json",
+    "keyword",
+    "linecache",
+    "locale",
+    "logging",
+    "lzma",
+    "mailbox",
+    "marshal",
+    "math",
+    "mimetypes",
+    "mmap",
+    "multiprocessing",
+    "netrc",
+    "numbers",
+    "operator",
+    "os",
+    "pathlib",
+    "pdb",
+    "pickle",
+    "pkgutil",
+    "platform",
+    "plistlib",
+    "poplib",
+    "posixpath",
+    "pprint",
+    "profile",
+    "pstats",
+    "pty",
+    "pwd",
+    "queue",
+    "quopri",
+    "random",
+    "re",
+    "readline",
+    "reprlib",
+    "resource",
+    "runpy",
+    "sched",
+    "secrets",
+    "select",
+    "selectors",
+    "shelve",
+    "shlex",
+    "shutil",
+    "signal",
+    "site",
+    "smtplib",
+    "socket",
+    "socketserver",
+    "sqlite3",
+    "ssl",
+    "stat",
+    "statistics",
+    "string",
+    "struct",
+    "subprocess",
+    "sys",
+    "sysconfig",
+    "syslog",
+    "tarfile",
+    "tempfile",
+    "termios",
+    "textwrap",
+    "threading",
+    "time",
+    "timeit",
+    "tkinter",
+    "token",
+    "tokenize",
+    "tomllib",
+    "trace",
+    "traceback",
+    "tracemalloc",
+    "tty",
+    "types",
+    "typing",
+    "unicodedata",
+    "unittest",
+    "urllib",
+    "uuid",
+    "venv",
+    "warnings",
+    "wave",
+    "weakref",
+    "webbrowser",
+    "winreg",
+    "wsgiref",
+    "xml",
+    "xmlrpc",
+    "zipfile",
+    "zipimport",
+    "zlib",
+    "zoneinfo",
+];
+
+/// Import names whose distribution name on PyPI differs.
+const PYTHON_IMPORT_ALIASES: &[(&str, &str)] = &[
+    ("PIL", "pillow"),
+    ("yaml", "pyyaml"),
+    ("sklearn", "scikit-learn"),
+    ("cv2", "opencv-python"),
+    ("bs4", "beautifulsoup4"),
+    ("dateutil", "python-dateutil"),
+    ("dotenv", "python-dotenv"),
+    ("jwt", "pyjwt"),
+    ("attr", "attrs"),
+    ("magic", "python-magic"),
+    ("serial", "pyserial"),
+    ("usb", "pyusb"),
+    ("Crypto", "pycryptodome"),
+    ("OpenSSL", "pyopenssl"),
+    ("git", "gitpython"),
+    ("docx", "python-docx"),
+    ("pptx", "python-pptx"),
+    ("fitz", "pymupdf"),
+    ("multipart", "python-multipart"),
+    ("zmq", "pyzmq"),
+    ("gi", "pygobject"),
+];
+
+/// Returns the package name for a bare import specifier, or `None` when the
+/// import is relative, local or part of the language runtime.
+fn package_name(ecosystem: Ecosystem, source: &str, local_mods: &HashSet<&str>) -> Option<String> {
+    match ecosystem {
+        Ecosystem::Npm => {
+            if source.starts_with('.')
+                || source.starts_with('/')
+                || source.starts_with('~')
+                || source.starts_with('#')
+                || source.starts_with("@/")
+                || source.contains(':')
+            {
+                return None;
+            }
+            let mut segments = source.split('/');
+            let first = segments.next()?;
+            let name = if first.starts_with('@') {
+                format!("{}/{}", first, segments.next()?)
+            } else {
+                first.to_string()
+            };
+            if NODE_BUILTINS.contains(&name.as_str()) {
+                return None;
+            }
+            Some(name)
+        }
+        Ecosystem::Cargo => {
+            let trimmed = source.trim().trim_start_matches("::");
+            let first = trimmed.split([':', '{', ' ', ';']).next()?.trim();
+            if first.is_empty()
+                || RUST_BUILTIN_ROOTS.contains(&first)
+                || local_mods.contains(first)
+                || first.starts_with(|c: char| c.is_ascii_uppercase())
+            {
+                return None;
+            }
+            Some(first.to_string())
+        }
+        Ecosystem::Pypi => {
+            if source.starts_with('.') {
+                return None;
+            }
+            let first = source.split('.').next()?.trim();
+            if first.is_empty() || PYTHON_STDLIB.contains(&first) {
+                return None;
+            }
+            Some(first.to_string())
+        }
+    }
+}
+
+fn manifest_match_key(ecosystem: Ecosystem, import_name: &str) -> String {
+    if ecosystem == Ecosystem::Pypi {
+        if let Some((_, dist)) = PYTHON_IMPORT_ALIASES
+            .iter()
+            .find(|(import, _)| *import == import_name)
+        {
+            return ecosystem.normalise(dist);
+        }
+    }
+    ecosystem.normalise(import_name)
+}
+
+/// Finds the nearest directory (from the file up to the root) holding a manifest
+/// for the ecosystem.
+fn nearest_manifest_dir(
+    root: &Path,
+    file: &str,
+    ecosystem: Ecosystem,
+    cache: &mut HashMap<(PathBuf, Ecosystem), Option<PathBuf>>,
+) -> Option<PathBuf> {
+    let mut dir = root.join(file).parent()?.to_path_buf();
+    let mut visited = Vec::new();
+    let found = loop {
+        if let Some(hit) = cache.get(&(dir.clone(), ecosystem)) {
+            break hit.clone();
+        }
+        visited.push(dir.clone());
+        if ecosystem
+            .manifest_names()
+            .iter()
+            .any(|name| dir.join(name).is_file())
+        {
+            break Some(dir.clone());
+        }
+        if dir == root || !dir.starts_with(root) {
+            break None;
+        }
+        match dir.parent() {
+            Some(parent) => dir = parent.to_path_buf(),
+            None => break None,
+        }
+    };
+    for seen in visited {
+        cache.insert((seen, ecosystem), found.clone());
+    }
+    found
+}
+
+fn parse_package_json(path: &Path) -> Vec<DeclaredDep> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&raw) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for section in [
+        "dependencies",
+        "devDependencies",
+        "peerDependencies",
+        "optionalDependencies",
+    ] {
+        if let Some(map) = value.get(section).and_then(|v| v.as_object()) {
+            for name in map.keys() {
+                out.push(DeclaredDep {
+                    name: name.clone(),
+                    section: section.to_string(),
+                });
+            }
+        }
+    }
+    out
+}
+
+fn cargo_section_kind(prefix: &str) -> &'static str {
+    if prefix.ends_with("dev-") {
+        "dev-dependencies"
+    } else if prefix.ends_with("build-") {
+        "build-dependencies"
+    } else {
+        "dependencies"
+    }
+}
+
+fn parse_cargo_toml(path: &Path) -> Vec<DeclaredDep> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    let mut section: Option<&'static str> = None;
+    for line in raw.lines() {
+        let trimmed = line.split('#').next().unwrap_or("").trim();
+        if trimmed.starts_with('[') {
+            let header = trimmed.trim_matches(|c| c == '[' || c == ']').trim();
+            section = None;
+            if header.starts_with("workspace.") {
+                continue;
+            }
+            if let Some(prefix) = header.strip_suffix("dependencies") {
+                section = Some(cargo_section_kind(prefix));
+            } else if let Some((prefix, name)) = header.rsplit_once("dependencies.") {
+                // `[dependencies.foo]` table form declares a single crate.
+                out.push(DeclaredDep {
+                    name: name.trim_matches('"').to_string(),
+                    section: cargo_section_kind(prefix).to_string(),
+                });
+            }
+            continue;
+        }
+        let Some(kind) = section else {
+            continue;
+        };
+        let Some((key, _)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let name = key.split('.').next().unwrap_or("").trim().trim_matches('"');
+        if !name.is_empty() {
+            out.push(DeclaredDep {
+                name: name.to_string(),
+                section: kind.to_string(),
+            });
+        }
+    }
+    out
+}
+
+fn requirement_name(spec: &str) -> Option<String> {
+    let spec = spec.trim();
+    if spec.is_empty() || spec.starts_with('-') || spec.contains("://") {
+        return None;
+    }
+    let name: String = spec
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+fn parse_requirements(path: &Path, section: &str) -> Vec<DeclaredDep> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    raw.lines()
+        .filter_map(|line| requirement_name(line.split('#').next().unwrap_or("")))
+        .map(|name| DeclaredDep {
+            name,
+            section: section.to_string(),
+        })
+        .collect()
+}
+
+/// Collects quoted strings until the array opened on the current line closes.
+/// Returns true while the array is still open.
+fn collect_array_strings(fragment: &str, out: &mut Vec<String>) -> bool {
+    let mut in_quote: Option<char> = None;
+    let mut current = String::new();
+    for ch in fragment.chars() {
+        match in_quote {
+            Some(q) if ch == q => {
+                out.push(std::mem::take(&mut current));
+                in_quote = None;
+            }
+            Some(_) => current.push(ch),
+            None => match ch {
+                '"' | '\'' => in_quote = Some(ch),
+                ']' => return false,
+                '#' => return true,
+                _ => {}
+            },
+        }
+    }
+    true
+}
+
+fn parse_pyproject(path: &Path) -> Vec<DeclaredDep> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    let mut header = String::new();
+    let mut open_array: Option<String> = None;
+    let push_specs = |specs: Vec<String>, section: &str, out: &mut Vec<DeclaredDep>| {
+        for spec in specs {
+            if let Some(name) = requirement_name(&spec) {
+                out.push(DeclaredDep {
+                    name,
+                    section: section.to_string(),
+                });
+            }
+        }
+    };
+
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if let Some(section) = open_array.clone() {
+            let mut specs = Vec::new();
+            if !collect_array_strings(trimmed, &mut specs) {
+                open_array = None;
+            }
+            push_specs(specs, &section, &mut out);
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            header = trimmed
+                .trim_matches(|c| c == '[' || c == ']')
+                .trim()
+                .to_string();
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().trim_matches('"');
+        let value = value.trim();
+        let array_section = match header.as_str() {
+            "project" if key == "dependencies" => Some("dependencies"),
+            "project.optional-dependencies" => Some("optional-dependencies"),
+            "dependency-groups" => Some("dev-dependencies"),
+            _ => None,
+        };
+        if let Some(section) = array_section {
+            if let Some(rest) = value.strip_prefix('[') {
+                let mut specs = Vec::new();
+                if collect_array_strings(rest, &mut specs) {
+                    open_array = Some(section.to_string());
+                }
+                push_specs(specs, section, &mut out);
+            }
+            continue;
+        }
+        let poetry_section = if header == "tool.poetry.dependencies" {
+            Some("dependencies")
+        } else if header == "tool.poetry.dev-dependencies"
+            || (header.starts_with("tool.poetry.group.") && header.ends_with(".dependencies"))
+        {
+            Some("dev-dependencies")
+        } else {
+            None
+        };
+        if let Some(section) = poetry_section {
+            if key != "python" && !key.is_empty() {
+                out.push(DeclaredDep {
+                    name: key.to_string(),
+                    section: section.to_string(),
+                });
+            }
+        }
+    }
+    out
+}
+
+fn read_manifests(dir: &Path, ecosystem: Ecosystem) -> (Vec<String>, Vec<DeclaredDep>) {
+    let mut names = Vec::new();
+    let mut declared = Vec::new();
+    for name in ecosystem.manifest_names() {
+        let path = dir.join(name);
+        if !path.is_file() {
+            continue;
+        }
+        names.push(name.to_string());
+        declared.extend(match *name {
+            "package.json" => parse_package_json(&path),
+            "Cargo.toml" => parse_cargo_toml(&path),
+            "pyproject.toml" => parse_pyproject(&path),
+            "requirements-dev.txt" => parse_requirements(&path, "dev-dependencies"),
+            _ => parse_requirements(&path, "dependencies"),
+        });
+    }
+    (names, declared)
+}
+
+/// Declared entries that are not expected to show up as imports (tooling, type stubs).
+fn is_unused_candidate(ecosystem: Ecosystem, dep: &DeclaredDep) -> bool {
+    if dep.section.starts_with("dev") || dep.section.starts_with("build") {
+        return false;
+    }
+    if dep.section.starts_with("optional") {
+        return false;
+    }
+    !(ecosystem == Ecosystem::Npm && dep.name.starts_with("@types/"))
+}
+
+/// Groups bare imports by external package and cross-checks them against the
+/// nearest manifest of each file's ecosystem.
+pub fn build_dependency_inventory(root: &Path, analyses: &[FileAnalysis]) -> DependencyInventory {
+    let mut manifest_cache: HashMap<(PathBuf, Ecosystem), Option<PathBuf>> = HashMap::new();
+    let mut usage: BTreeMap<(Ecosystem, String), PackageUsage> = BTreeMap::new();
+    // (manifest dir, ecosystem) -> normalised key -> (display name, locations)
+    let mut by_manifest: BTreeMap<(PathBuf, Ecosystem), BTreeMap<String, UndeclaredDependency>> =
+        BTreeMap::new();
+
+    for analysis in analyses {
+        let Some(ecosystem) = Ecosystem::for_path(&analysis.path) else {
+            continue;
+        };
+        let local_mods: HashSet<&str> = analysis
+            .imports
+            .iter()
+            .filter(|i| matches!(i.kind, ImportKind::Module))
+            .map(|i| i.source.as_str())
+            .collect();
+        let manifest_dir =
+            nearest_manifest_dir(root, &analysis.path, ecosystem, &mut manifest_cache);
+        // Crates are also used through qualified paths and macros without a `use`.
+        let references = analysis
+            .imports
+            .iter()
+            .filter(|imp| imp.resolved.is_none() && !matches!(imp.kind, ImportKind::Module))
+            .map(|imp| (imp.source.as_str(), imp.line))
+            .chain(
+                analysis
+                    .path_roots
+                    .iter()
+                    .map(|(name, line)| (name.as_str(), *line)),
+            );
+        for (source, line) in references {
+            let Some(name) = package_name(ecosystem, source, &local_mods) else {
+                continue;
+            };
+            if ecosystem == Ecosystem::Pypi && python_module_is_local(root, &analysis.path, &name) {
+                continue;
+            }
+            let entry = usage
+                .entry((ecosystem, name.clone()))
+                .or_insert_with(|| PackageUsage {
+                    usages: 0,
+                    files: Vec::new(),
+                    manifest_dirs: Vec::new(),
+                });
+            entry.usages += 1;
+            if !entry.files.contains(&analysis.path) {
+                entry.files.push(analysis.path.clone());
+            }
+            if let Some(dir) = &manifest_dir {
+                if !entry.manifest_dirs.contains(dir) {
+                    entry.manifest_dirs.push(dir.clone());
+                }
+            }
+            if let Some(dir) = &manifest_dir {
+                by_manifest
+                    .entry((dir.clone(), ecosystem))
+                    .or_default()
+                    .entry(manifest_match_key(ecosystem, &name))
+                    .or_insert_with(|| UndeclaredDependency {
+                        name: name.clone(),
+                        locations: Vec::new(),
+                    })
+                    .locations
+                    .push((analysis.path.clone(), line));
+            }
+        }
+    }
+
+    let mut manifests = Vec::new();
+    let mut declared_lookup: HashMap<(PathBuf, Ecosystem), (String, HashSet<String>)> =
+        HashMap::new();
+    let mut manifest_dirs: Vec<(PathBuf, Ecosystem)> = manifest_cache
+        .iter()
+        .filter_map(|((_, eco), found)| found.clone().map(|dir| (dir, *eco)))
+        .collect();
+    manifest_dirs.sort();
+    manifest_dirs.dedup();
+
+    for (dir, ecosystem) in manifest_dirs {
+        let (names, declared) = read_manifests(&dir, ecosystem);
+        if names.is_empty() {
+            continue;
+        }
+        let declared_keys: HashSet<String> = declared
+            .iter()
+            .map(|d| ecosystem.normalise(&d.name))
+            .collect();
+        let imported = by_manifest
+            .remove(&(dir.clone(), ecosystem))
+            .unwrap_or_default();
+        let undeclared: Vec<UndeclaredDependency> = imported
+            .iter()
+            .filter(|(key, _)| !declared_keys.contains(*key))
+            .map(|(_, dep)| dep.clone())
+            .collect();
+        let mut unused: Vec<UnusedDependency> = declared
+            .iter()
+            .filter(|d| is_unused_candidate(ecosystem, d))
+            .filter(|d| !imported.contains_key(&ecosystem.normalise(&d.name)))
+            .map(|d| UnusedDependency {
+                name: d.name.clone(),
+                section: d.section.clone(),
+            })
+            .collect();
+        unused.sort_by(|a, b| a.name.cmp(&b.name));
+        unused.dedup_by(|a, b| a.name == b.name);
+
+        let rel_dir = dir
+            .strip_prefix(root)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| dir.to_string_lossy().to_string());
+        let path = names
+            .iter()
+            .map(|n| {
+                if rel_dir.is_empty() {
+                    n.clone()
+                } else {
+                    format!("{}/{}", rel_dir, n)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        declared_lookup.insert((dir.clone(), ecosystem), (path.clone(), declared_keys));
+        manifests.push(ManifestReport {
+            path,
+            ecosystem,
+            declared: declared.len(),
+            undeclared,
+            unused,
+        });
+    }
+
+    let mut packages: Vec<ExternalPackage> = usage
+        .into_iter()
+        .map(|((ecosystem, name), pkg)| {
+            let key = manifest_match_key(ecosystem, &name);
+            // Each file is checked against its own nearest manifest; the first
+            // one that declares the package is reported.
+            let declared_in = pkg
+                .manifest_dirs
+                .iter()
+                .filter_map(|d| declared_lookup.get(&(d.clone(), ecosystem)))
+                .find(|(_, keys)| keys.contains(&key))
+                .map(|(path, _)| path.clone());
+            ExternalPackage {
+                name,
+                ecosystem,
+                usages: pkg.usages,
+                files: pkg.files,
+                declared_in,
+                has_manifest: !pkg.manifest_dirs.is_empty(),
+            }
+        })
+        .collect();
+    packages.sort_by(|a, b| b.usages.cmp(&a.usages).then(a.name.cmp(&b.name)));
+
+    DependencyInventory {
+        packages,
+        manifests,
+    }
+}
+
+/// A top-level module that exists next to the importing file, in its project
+/// or under the root is first-party even when the import did not resolve.
+fn python_module_is_local(root: &Path, file: &str, name: &str) -> bool {
+    python_search_bases(Some(&root.join(file)), root)
+        .iter()
+        .any(|base| base.join(name).is_dir() || base.join(format!("{}.py", name)).is_file())
+}
@@ -0,0 +1,458 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::analyzer::is_dev_file;
+use crate::args::{preset_ignore_symbols, ParsedArgs};
+use crate::report::{escape_html, HtmlContext};
+use crate::types::{ExportCategory, ExportIndex, ExportSymbol, FileAnalysis, ReexportKind};
+
+/// Collision kinds of duplicate exports: one per export category, plus
+/// `mixed` for a name exported with different categories from different files.
+const DUPLICATE_KINDS: &[&str] = &["value", "type", "default", "module", "mixed"];
+
+/// Reported unless the config says otherwise: `mod utils` in two crates and a
+/// `type Foo` in one file next to `const Foo` in another are rarely mistakes.
+const DEFAULT_DUPLICATE_KINDS: &[&str] = &["value", "type", "default"];
+
+/// Export names left out of the duplicate check (`--ignore-symbols` and
+/// `--ignore-symbols-preset`); a trailing `*` matches a prefix.
+pub struct SymbolFilter {
+    exact: HashSet<String>,
+    prefixes: Vec<String>,
+}
+
+impl SymbolFilter {
+    pub fn from_args(parsed: &ParsedArgs) -> Self {
+        let mut filter = SymbolFilter {
+            exact: HashSet::new(),
+            prefixes: Vec::new(),
+        };
+
+        if let Some(preset_name) = parsed.ignore_symbols_preset.as_deref() {
+            if let Some(set) = preset_ignore_symbols(preset_name) {
+                for s in set {
+                    filter.add(s);
+                }
+            } else {
+                eprintln!(
+                    "[loctree][warn] unknown --ignore-symbols-preset '{}', ignoring",
+                    preset_name
+                );
+            }
+        }
+
+        if let Some(user_syms) = parsed.ignore_symbols.clone() {
+            for s in user_syms {
+                filter.add(s.to_lowercase());
+            }
+        }
+        filter
+    }
+
+    fn add(&mut self, symbol: String) {
+        if symbol.ends_with('*') {
+            self.prefixes.push(symbol.trim_end_matches('*').to_string());
+        } else {
+            self.exact.insert(symbol);
+        }
+    }
+
+    fn ignores(&self, name: &str) -> bool {
+        let name_lc = name.to_lowercase();
+        self.exact.contains(&name_lc) || self.prefixes.iter().any(|p| name_lc.starts_with(p))
+    }
+}
+
+/// Collision kinds to report: the config's `duplicateKinds`, or the defaults.
+pub fn reported_kinds(configured: Option<&[String]>) -> HashSet<String> {
+    match configured {
+        Some(kinds) => {
+            for kind in kinds
+                .iter()
+                .filter(|k| !DUPLICATE_KINDS.contains(&k.as_str()))
+            {
+                eprintln!(
+                    "[loctree][warn] unknown duplicate kind '{}' in config (expected one of: {})",
+                    kind,
+                    DUPLICATE_KINDS.join(", ")
+                );
+            }
+            kinds.iter().cloned().collect()
+        }
+        None => DEFAULT_DUPLICATE_KINDS
+            .iter()
+            .map(|k| k.to_string())
+            .collect(),
+    }
+}
+
+struct RankedDup {
+    name: String,
+    /// Collision kind: the shared export category, or `mixed`.
+    kind: &'static str,
+    files: Vec<String>,
+    score: usize,
+    prod: usize,
+    dev: usize,
+    canonical: String,
+    /// Non-canonical copies, cheapest migration (fewest import sites) first.
+    refactors: Vec<String>,
+    /// Line of the export in each file, for source links.
+    lines: HashMap<String, usize>,
+    /// Who imports each copy of the symbol.
+    usage: HashMap<String, CopyUsage>,
+}
+
+/// Importers of one copy of a duplicated export: distinct files and the
+/// number of import/re-export statements that would need rewriting.
+#[derive(Clone, Default)]
+struct CopyUsage {
+    importers: BTreeSet<String>,
+    sites: usize,
+}
+
+/// Names exported from more than one file, with the copy to keep and the
+/// ones to migrate away from.
+pub struct DuplicateExports {
+    /// Sorted by name: `(name, kind, files)`.
+    duplicates: Vec<(String, &'static str, Vec<String>)>,
+    lines: HashMap<(String, String), usize>,
+    /// Most worth cleaning up first.
+    ranked: Vec<RankedDup>,
+}
+
+impl DuplicateExports {
+    pub fn find(analyses: &[FileAnalysis], filter: &SymbolFilter, kinds: &HashSet<String>) -> Self {
+        let mut export_index: ExportIndex = HashMap::new();
+        let mut lines: HashMap<(String, String), usize> = HashMap::new();
+        for analysis in analyses {
+            for exp in &analysis.exports {
+                let key = export_key(&analysis.path, exp);
+                if filter.ignores(&key) {
+                    continue;
+                }
+                export_index
+                    .entry(key.clone())
+                    .or_default()
+                    .push((analysis.path.clone(), exp.category));
+                lines
+                    .entry((key, analysis.path.clone()))
+                    .or_insert(exp.line);
+            }
+        }
+
+        let mut duplicates: Vec<(String, &'static str, Vec<String>)> = export_index
+            .into_iter()
+            .flat_map(|(name, copies)| {
+                classify_duplicates(&copies)
+                    .into_iter()
+                    .map(move |(kind, files)| (name.clone(), kind, files))
+            })
+            .filter(|(_, kind, _)| kinds.contains(*kind))
+            .collect();
+        duplicates.sort();
+
+        let ranked = rank(&duplicates, &lines, analyses);
+        DuplicateExports {
+            duplicates,
+            lines,
+            ranked,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.duplicates.len()
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "duplicateExports": self.duplicates
+                .iter()
+                .map(|(name, kind, files)| json!({
+                    "name": name,
+                    "kind": kind,
+                    "files": files,
+                    "locations": files.iter().map(|f| json!({"file": f, "line": self.lines.get(&(name.clone(), f.clone()))})).collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+            "duplicateExportsRanked": self.ranked
+                .iter()
+                .map(|dup| json!({
+                    "name": dup.name,
+                    "kind": dup.kind,
+                    "files": dup.files,
+                    "score": dup.score,
+                    "nonDevCount": dup.prod,
+                    "devCount": dup.dev,
+                    "canonical": dup.canonical,
+                    "canonicalLine": dup.lines.get(&dup.canonical),
+                    "refactorTargets": dup.refactors,
+                    "copies": dup.files.iter().map(|f| {
+                        let usage = dup.usage.get(f).cloned().unwrap_or_default();
+                        json!({
+                            "file": f,
+                            "line": dup.lines.get(f),
+                            "importers": usage.importers,
+                            "importSites": usage.sites,
+                            "sharedPath": is_shared_path(f),
+                        })
+                    }).collect::<Vec<_>>(),
+                    "locations": dup.files.iter().map(|f| json!({"file": f, "line": dup.lines.get(f)})).collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self, limit: usize) {
+        if self.duplicates.is_empty() {
+            return;
+        }
+        println!("\nTop duplicate exports (showing up to {}):", limit);
+        for dup in self.ranked.iter().take(limit) {
+            let sites = |file: &String| dup.usage.get(file).map_or(0, |u| u.sites);
+            println!(
+                "  - {} [{}] (score {}, {} files: {} prod, {} dev) canonical: {} ({} importers) | refs: {}",
+                dup.name,
+                dup.kind,
+                dup.score,
+                dup.files.len(),
+                dup.prod,
+                dup.dev,
+                dup.canonical,
+                dup.usage
+                    .get(&dup.canonical)
+                    .map_or(0, |u| u.importers.len()),
+                dup.refactors
+                    .iter()
+                    .map(|f| format!("{} ({} sites)", f, sites(f)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        out.push_str("<h3>Top duplicate exports</h3>");
+        if self.ranked.is_empty() {
+            out.push_str("<p class=\"muted\">None</p>");
+            return;
+        }
+        out.push_str("<table><tr><th>Symbol</th><th>Kind</th><th>Files</th><th>Prod</th><th>Dev</th><th>Canonical</th><th>Refactor targets (import sites)</th></tr>");
+        for dup in self.ranked.iter().take(ctx.limit) {
+            let link = |file: &String| {
+                let usage = dup.usage.get(file).cloned().unwrap_or_default();
+                format!(
+                    "{} <span class=\"muted\">({} importers, {} sites)</span>",
+                    match dup.lines.get(file) {
+                        Some(line) => ctx.link(file, *line),
+                        None => escape_html(file),
+                    },
+                    usage.importers.len(),
+                    usage.sites
+                )
+            };
+            out.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><code>{}</code></td><td>{}</td></tr>",
+                escape_html(&dup.name),
+                dup.kind,
+                dup.files.len(),
+                dup.prod,
+                dup.dev,
+                link(&dup.canonical),
+                dup.refactors.iter().map(link).collect::<Vec<_>>().join(", ")
+            ));
+        }
+        out.push_str("</table>");
+    }
+}
+
+/// Scores each duplicate and picks its canonical copy from who imports what.
+fn rank(
+    duplicates: &[(String, &'static str, Vec<String>)],
+    export_lines: &HashMap<(String, String), usize>,
+    analyses: &[FileAnalysis],
+) -> Vec<RankedDup> {
+    // (target file, imported name) -> importing sites; `*` for namespace
+    // imports and star re-exports, which may use any export of the file.
+    let mut symbol_sites: HashMap<(&str, &str), Vec<&str>> = HashMap::new();
+    let mut default_exports: HashMap<&str, String> = HashMap::new();
+    // (file, export key) pairs the file declares rather than re-exports.
+    let mut declared: HashSet<(&str, String)> = HashSet::new();
+    for analysis in analyses {
+        for imp in &analysis.imports {
+            let Some(target) = imp.resolved.as_deref() else {
+                continue;
+            };
+            for sym in &imp.symbols {
+                let name = if sym.is_namespace {
+                    "*"
+                } else {
+                    sym.name.rsplit(['.', ':']).next().unwrap_or(&sym.name)
+                };
+                symbol_sites
+                    .entry((target, name))
+                    .or_default()
+                    .push(&analysis.path);
+            }
+        }
+        for re in &analysis.reexports {
+            let Some(target) = re.resolved.as_deref() else {
+                continue;
+            };
+            let names: Vec<&str> = match &re.kind {
+                ReexportKind::Star => vec!["*"],
+                // `a as b` imports `a` from the target.
+                ReexportKind::Named(names) => names.iter().map(|(n, _)| n.as_str()).collect(),
+            };
+            for name in names {
+                symbol_sites
+                    .entry((target, name))
+                    .or_default()
+                    .push(&analysis.path);
+            }
+        }
+        for exp in analysis
+            .exports
+            .iter()
+            .filter(|e| e.category == ExportCategory::Default)
+        {
+            default_exports.insert(&analysis.path, export_key(&analysis.path, exp));
+        }
+        for exp in analysis.exports.iter().filter(|e| e.kind != "reexport") {
+            declared.insert((&analysis.path, export_key(&analysis.path, exp)));
+        }
+    }
+    let copy_usage = |file: &str, name: &str| {
+        let mut usage = CopyUsage::default();
+        let mut keys = vec![(file, name), (file, "*")];
+        if default_exports.get(file).is_some_and(|key| key == name) {
+            keys.push((file, "default"));
+        }
+        for key in keys {
+            for importer in symbol_sites.get(&key).into_iter().flatten() {
+                usage.importers.insert(importer.to_string());
+                usage.sites += 1;
+            }
+        }
+        usage
+    };
+
+    let mut ranked_dups = Vec::new();
+    for (name, kind, files) in duplicates {
+        let dev_count = files.iter().filter(|f| is_dev_file(f)).count();
+        let prod_count = files.len().saturating_sub(dev_count);
+        let score = prod_count * 2 + dev_count;
+        let usage: HashMap<String, CopyUsage> = files
+            .iter()
+            .map(|f| (f.clone(), copy_usage(f, name)))
+            .collect();
+        // Prefer production copies, then the module declaring the symbol over
+        // barrels re-exporting it, then the most imported one, then one living
+        // in a shared/lib directory.
+        let canonical = files
+            .iter()
+            .min_by_key(|f| {
+                (
+                    is_dev_file(f),
+                    !declared.contains(&(f.as_str(), name.clone())),
+                    std::cmp::Reverse(usage[*f].importers.len()),
+                    !is_shared_path(f),
+                    f.len(),
+                    (*f).clone(),
+                )
+            })
+            .cloned()
+            .unwrap_or_else(|| files[0].clone());
+        let mut refactors: Vec<String> =
+            files.iter().filter(|f| *f != &canonical).cloned().collect();
+        refactors.sort_by(|a, b| usage[a].sites.cmp(&usage[b].sites).then(a.cmp(b)));
+        let lines = files
+            .iter()
+            .filter_map(|f| {
+                export_lines
+                    .get(&(name.clone(), f.clone()))
+                    .map(|line| (f.clone(), *line))
+            })
+            .collect();
+        ranked_dups.push(RankedDup {
+            name: name.clone(),
+            kind,
+            files: files.clone(),
+            score,
+            prod: prod_count,
+            dev: dev_count,
+            canonical,
+            refactors,
+            lines,
+            usage,
+        });
+    }
+    ranked_dups.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(b.files.len().cmp(&a.files.len()))
+    });
+    ranked_dups
+}
+
+/// Name an export is indexed under. Anonymous default exports take the name
+/// importers usually give them: the file stem, or the directory for `index`.
+fn export_key(path: &str, exp: &ExportSymbol) -> String {
+    if exp.category != ExportCategory::Default || exp.name != "default" {
+        return exp.name.clone();
+    }
+    let path = Path::new(path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("default");
+    let stem = stem.split('.').next().unwrap_or(stem);
+    if stem == "index" {
+        if let Some(dir) = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|d| d.to_str())
+        {
+            return dir.to_string();
+        }
+    }
+    stem.to_string()
+}
+
+/// Splits the copies of one exported name into collisions: files sharing a
+/// category, or — when no category repeats — every file if they differ.
+fn classify_duplicates(copies: &[(String, ExportCategory)]) -> Vec<(&'static str, Vec<String>)> {
+    let mut by_category: BTreeMap<ExportCategory, BTreeSet<&str>> = BTreeMap::new();
+    for (file, category) in copies {
+        by_category.entry(*category).or_default().insert(file);
+    }
+    let mut out: Vec<(&'static str, Vec<String>)> = by_category
+        .iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|(category, files)| {
+            (
+                category.as_str(),
+                files.iter().map(|f| f.to_string()).collect(),
+            )
+        })
+        .collect();
+    if out.is_empty() {
+        let files: BTreeSet<&str> = copies.iter().map(|(f, _)| f.as_str()).collect();
+        if files.len() > 1 {
+            out.push(("mixed", files.into_iter().map(str::to_string).collect()));
+        }
+    }
+    out
+}
+
+/// Directories that conventionally hold code meant to be shared.
+fn is_shared_path(path: &str) -> bool {
+    path.split('/').any(|seg| {
+        matches!(
+            seg,
+            "shared" | "lib" | "libs" | "common" | "core" | "utils" | "util" | "packages"
+        )
+    })
+}
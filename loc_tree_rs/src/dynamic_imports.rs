@@ -0,0 +1,456 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::json;
+
+use crate::analyzer::{offset_to_line_col, relative_to_root, resolve_js_relative};
+use crate::config::compile_glob;
+use crate::non_code::NonCode;
+use crate::report::{escape_html, HtmlContext};
+use crate::routes::{call_args, js_string};
+use crate::types::{DynamicImport, DynamicImportKind, FileAnalysis};
+
+fn regex_import_call() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"(?:^|[^\w$.])(import)\s*\("#).unwrap())
+}
+
+fn regex_lazy_wrapper() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"\blazy\s*\(\s*(?:async\s*)?\(\s*\)\s*=>\s*(?:\{\s*return\s+)?(?:await\s+)?$"#)
+            .unwrap()
+    })
+}
+
+fn regex_meta_glob() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"import\s*\.\s*meta\s*\.\s*glob(?:Eager)?\s*(?:<[^()]*?>)?\s*\("#).unwrap()
+    })
+}
+
+fn regex_worker_url() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"new\s+(?:Shared)?Worker\s*\(\s*new\s+URL\s*\("#).unwrap())
+}
+
+/// Splits a call's argument text on top-level commas.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (idx, ch) in args.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => quote = Some(ch),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                out.push(&args[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    out.push(&args[start..]);
+    out
+}
+
+/// The value of `arg` if it is a plain string literal (no `${}` substitutions).
+fn literal(arg: &str) -> Option<String> {
+    let arg = arg.trim();
+    if !arg.starts_with(['"', '\'', '`']) || (arg.starts_with('`') && arg.contains("${")) {
+        return None;
+    }
+    let (value, end) = js_string(arg, 0)?;
+    arg[end..].trim().is_empty().then_some(value)
+}
+
+/// Expression text as shown in reports, with whitespace collapsed.
+fn expression(arg: &str) -> String {
+    arg.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Offset of the first non-whitespace byte of `arg`, which starts at `offset`.
+fn arg_offset(arg: &str, offset: usize) -> usize {
+    offset + (arg.len() - arg.trim_start().len())
+}
+
+/// Dynamic imports in a JS/TS module: `import()` (also inside `React.lazy`),
+/// `import.meta.glob` and `new Worker(new URL(..., import.meta.url))`.
+/// Literal specifiers are resolved like static imports; template literals and
+/// variables are kept as unresolvable expressions. Calls in comments and string
+/// literals are skipped.
+pub fn js_dynamic_imports(
+    content: &str,
+    path: &Path,
+    root: &Path,
+    extensions: Option<&HashSet<String>>,
+) -> Vec<DynamicImport> {
+    let non_code = NonCode::js(content);
+    let mut out = Vec::new();
+    let mut push = |kind, arg: &str, offset: usize, resolved: Option<Vec<String>>| {
+        let literal = literal(arg);
+        // Literals point inside the quotes, like static import columns.
+        let start = arg_offset(arg, offset) + usize::from(literal.is_some());
+        let source = literal.unwrap_or_else(|| expression(arg));
+        let unresolvable = resolved.is_none();
+        let (line, column) = offset_to_line_col(content, start);
+        out.push(DynamicImport {
+            source,
+            kind,
+            resolved: resolved.unwrap_or_default(),
+            unresolvable,
+            line,
+            column,
+        });
+    };
+
+    for caps in regex_import_call().captures_iter(content) {
+        let (Some(keyword), Some(whole)) = (caps.get(1), caps.get(0)) else {
+            continue;
+        };
+        if non_code.contains(keyword.start()) {
+            continue;
+        }
+        let Some(arg) = split_args(call_args(&content[whole.end()..]))
+            .first()
+            .copied()
+        else {
+            continue;
+        };
+        if arg.trim().is_empty() {
+            continue;
+        }
+        let mut from = keyword.start().saturating_sub(160);
+        while !content.is_char_boundary(from) {
+            from -= 1;
+        }
+        let kind = if regex_lazy_wrapper().is_match(&content[from..keyword.start()]) {
+            DynamicImportKind::Lazy
+        } else {
+            DynamicImportKind::Import
+        };
+        let resolved = literal(arg).map(|spec| {
+            resolve_js_relative(path, root, &spec, extensions)
+                .into_iter()
+                .collect()
+        });
+        push(kind, arg, whole.end(), resolved);
+    }
+
+    for m in regex_meta_glob()
+        .find_iter(content)
+        .filter(|m| !non_code.contains(m.start()))
+    {
+        let Some(arg) = split_args(call_args(&content[m.end()..])).first().copied() else {
+            continue;
+        };
+        let trimmed = arg.trim();
+        let patterns: Option<Vec<String>> = match trimmed.strip_prefix('[') {
+            Some(list) => split_args(list.trim_end().trim_end_matches(']'))
+                .into_iter()
+                .filter(|p| !p.trim().is_empty())
+                .map(literal)
+                .collect(),
+            None => literal(trimmed).map(|p| vec![p]),
+        };
+        let resolved = patterns.map(|patterns| expand_globs(path, root, &patterns));
+        push(DynamicImportKind::Glob, arg, m.end(), resolved);
+    }
+
+    for m in regex_worker_url()
+        .find_iter(content)
+        .filter(|m| !non_code.contains(m.start()))
+    {
+        let args = split_args(call_args(&content[m.end()..]));
+        if !args
+            .get(1)
+            .is_some_and(|base| base.contains("import.meta.url"))
+        {
+            continue;
+        }
+        let arg = args[0];
+        let resolved = literal(arg).map(|spec| {
+            let spec = if spec.starts_with('.') {
+                spec
+            } else {
+                format!("./{spec}")
+            };
+            resolve_js_relative(path, root, &spec, extensions)
+                .into_iter()
+                .collect()
+        });
+        push(DynamicImportKind::Worker, arg, m.end(), resolved);
+    }
+
+    out.sort_by_key(|d| (d.line, d.column));
+    out
+}
+
+/// Files matched by Vite glob patterns (relative to the module, or to the
+/// root when they start with `/`); `!` patterns exclude matches.
+fn expand_globs(path: &Path, root: &Path, patterns: &[String]) -> Vec<String> {
+    let mut matched: Vec<String> = Vec::new();
+    let mut excluded: HashSet<String> = HashSet::new();
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(negated) => excluded.extend(expand_glob(path, root, negated)),
+            None => matched.extend(expand_glob(path, root, pattern)),
+        }
+    }
+    matched.retain(|m| !excluded.contains(m));
+    matched.sort();
+    matched.dedup();
+    matched
+}
+
+fn expand_glob(path: &Path, root: &Path, pattern: &str) -> Vec<String> {
+    let (mut base, mut rest) = match pattern.strip_prefix('/') {
+        Some(rest) => (root.to_path_buf(), rest),
+        None if pattern.starts_with('.') => match path.parent() {
+            Some(parent) => (parent.to_path_buf(), pattern),
+            None => return Vec::new(),
+        },
+        // Aliases (`@/pages/*`) need the bundler config.
+        None => return Vec::new(),
+    };
+    // Walk from the literal directory prefix so `../` segments resolve on disk.
+    while let Some((segment, tail)) = rest.split_once('/') {
+        if segment.contains(['*', '?', '{', '[']) {
+            break;
+        }
+        base = base.join(segment);
+        rest = tail;
+    }
+    let Ok(matcher) = compile_glob(rest) else {
+        return Vec::new();
+    };
+    let mut files = Vec::new();
+    walk_files(&base, &mut files);
+    files
+        .into_iter()
+        .filter(|file| {
+            file.strip_prefix(&base)
+                .map(|rel| matcher.is_match(&rel.to_string_lossy().replace('\\', "/")))
+                .unwrap_or(false)
+        })
+        .filter_map(|file| relative_to_root(&file, root))
+        .collect()
+}
+
+fn walk_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || name == "node_modules" {
+            continue;
+        }
+        if path.is_dir() {
+            walk_files(&path, out);
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+/// Files with dynamic imports, in file order.
+pub struct DynamicImports {
+    files: Vec<(String, Vec<DynamicImport>)>,
+}
+
+impl DynamicImports {
+    pub fn collect(analyses: &[FileAnalysis]) -> Self {
+        DynamicImports {
+            files: analyses
+                .iter()
+                .filter(|a| !a.dynamic_imports.is_empty())
+                .map(|a| (a.path.clone(), a.dynamic_imports.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Files with the most dynamic imports first.
+    fn by_count(&self) -> Vec<&(String, Vec<DynamicImport>)> {
+        let mut sorted: Vec<_> = self.files.iter().collect();
+        sorted.sort_by_key(|b| std::cmp::Reverse(b.1.len()));
+        sorted
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "dynamicImports": self.files
+                .iter()
+                .map(|(file, sources)| {
+                    let unique: HashSet<_> = sources.iter().map(|d| &d.source).collect();
+                    json!({
+                        "file": file,
+                        "sources": sources.iter().map(|d| &d.source).collect::<Vec<_>>(),
+                        "locations": sources.iter().map(dynamic_import_json).collect::<Vec<_>>(),
+                        "manySources": sources.len() > 5,
+                        "selfImport": unique.len() < sources.len(),
+                    })
+                })
+                .collect::<Vec<_>>(),
+            "unresolvableDynamicImports": self.files
+                .iter()
+                .flat_map(|(file, sources)| {
+                    sources.iter().filter(|d| d.unresolvable).map(move |d| json!({
+                        "file": file,
+                        "expression": d.source,
+                        "kind": d.kind.as_str(),
+                        "line": d.line,
+                        "column": d.column,
+                    }))
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self, limit: usize) {
+        if !self.files.is_empty() {
+            println!("\nDynamic imports (showing up to {}):", limit);
+            for (file, sources) in self.by_count().into_iter().take(limit) {
+                println!(
+                    "  - {}: {}{}",
+                    file,
+                    sources
+                        .iter()
+                        .map(|d| format!(
+                            "{}{}{} (line {})",
+                            d.source,
+                            match d.kind {
+                                DynamicImportKind::Import => String::new(),
+                                kind => format!(" [{}]", kind.as_str()),
+                            },
+                            dynamic_targets(d)
+                                .map(|t| format!(" -> {}", t))
+                                .unwrap_or_default(),
+                            d.line
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    if sources.len() > 5 {
+                        "  [many sources]"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+
+        let unresolvable: Vec<(&String, &DynamicImport)> = self
+            .files
+            .iter()
+            .flat_map(|(file, sources)| sources.iter().map(move |d| (file, d)))
+            .filter(|(_, d)| d.unresolvable)
+            .collect();
+        if !unresolvable.is_empty() {
+            println!("\nUnresolvable dynamic imports:");
+            for (file, d) in unresolvable {
+                println!("  - {}:{} {}", file, d.line, d.source);
+            }
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        let sorted = self.by_count();
+        out.push_str("<h3>Dynamic imports</h3>");
+        if sorted.is_empty() {
+            out.push_str("<p class=\"muted\">None</p>");
+        } else {
+            out.push_str("<table><tr><th>File</th><th>Sources</th></tr>");
+            for (file, sources) in sorted.iter().take(ctx.limit) {
+                let items: Vec<String> = sources
+                    .iter()
+                    .map(|d| {
+                        format!(
+                            "<code>{}</code>{}{} ({})",
+                            escape_html(&d.source),
+                            kind_note(d),
+                            dynamic_targets(d)
+                                .map(|t| format!(" → <code>{}</code>", escape_html(&t)))
+                                .unwrap_or_default(),
+                            ctx.link(file, d.line)
+                        )
+                    })
+                    .collect();
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td></tr>",
+                    escape_html(file),
+                    items.join(", ")
+                ));
+            }
+            out.push_str("</table>");
+        }
+
+        let unresolvable: Vec<(&String, &DynamicImport)> = sorted
+            .iter()
+            .flat_map(|(file, sources)| sources.iter().map(move |d| (file, d)))
+            .filter(|(_, d)| d.unresolvable)
+            .collect();
+        if !unresolvable.is_empty() {
+            out.push_str("<h3>Unresolvable dynamic imports</h3><ul>");
+            for (file, d) in unresolvable {
+                out.push_str(&format!(
+                    "<li><code>{}</code>{} ({})</li>",
+                    escape_html(&d.source),
+                    kind_note(d),
+                    ctx.link(file, d.line)
+                ));
+            }
+            out.push_str("</ul>");
+        }
+    }
+}
+
+pub fn dynamic_import_json(d: &DynamicImport) -> serde_json::Value {
+    json!({
+        "source": d.source,
+        "kind": d.kind.as_str(),
+        "resolved": d.resolved,
+        "unresolvable": d.unresolvable,
+        "line": d.line,
+        "column": d.column,
+    })
+}
+
+/// Resolved files of a dynamic import as one label (globs can match many).
+fn dynamic_targets(d: &DynamicImport) -> Option<String> {
+    match d.resolved.as_slice() {
+        [] => None,
+        [single] if d.kind != DynamicImportKind::Glob => Some(single.clone()),
+        [_] => Some("1 file".to_string()),
+        many => Some(format!("{} files", many.len())),
+    }
+}
+
+fn kind_note(d: &DynamicImport) -> String {
+    match d.kind {
+        DynamicImportKind::Import => String::new(),
+        kind => format!(" <span class=\"muted\">{}</span>", kind.as_str()),
+    }
+}
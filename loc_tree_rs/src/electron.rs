@@ -0,0 +1,219 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::json;
+
+use crate::analyzer::offset_to_line;
+use crate::report::{gaps, HtmlContext, Locations};
+use crate::tauri::string_constants;
+use crate::types::{CommandRef, FileAnalysis};
+
+/// Finds renderer-side IPC sends: `ipcRenderer.invoke/send/sendSync/postMessage`
+/// and calls through `contextBridge` wrappers that forward their first
+/// argument as the channel (`window.electron.invoke("channel")`).
+pub struct IpcMatcher {
+    call: Regex,
+}
+
+fn regex_expose_in_main_world() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"contextBridge\s*\.\s*exposeInMainWorld\s*\(\s*["'`]([A-Za-z_$][\w$]*)["'`]\s*,\s*\{"#)
+            .unwrap()
+    })
+}
+
+fn regex_forwarding_member() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        // `name: (channel, ...args) => ipcRenderer.invoke(channel, ...args)`,
+        // `name: function (channel) { return ipcRenderer.send(channel) }` or
+        // the method shorthand `name(channel) { ... }`.
+        Regex::new(
+            r#"([A-Za-z_$][\w$]*)\s*(?::\s*(?:async\s+)?(?:function\s*)?)?\(?\s*([A-Za-z_$][\w$]*)\s*(?:[,:][^)]*)?\)?\s*(?:=>|\{)\s*(?:return\s+)?ipcRenderer\s*\.\s*(?:invoke|send|sendSync|postMessage)\s*\(\s*([A-Za-z_$][\w$]*)\b"#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_main_handler() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"\bipcMain\s*\.\s*(?:handle|handleOnce|on|once)\s*\(\s*(?:["'`]([^"'`$]+)["'`]|([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)?)\s*,)"#,
+        )
+        .unwrap()
+    })
+}
+
+/// `(api, method)` pairs a preload script (a JS/TS file with extension `ext`)
+/// exposes through `contextBridge.exposeInMainWorld` whose method forwards its
+/// first argument to `ipcRenderer` as the channel.
+pub fn preload_bridge(ext: &str, content: &str) -> Vec<(String, String)> {
+    if !matches!(ext, "js" | "mjs" | "cjs" | "ts" | "mts" | "cts")
+        || !content.contains("exposeInMainWorld")
+    {
+        return Vec::new();
+    }
+    bridge_forwarders(content)
+}
+
+fn bridge_forwarders(content: &str) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    for caps in regex_expose_in_main_world().captures_iter(content) {
+        let api = caps[1].to_string();
+        let body_start = caps.get(0).map_or(0, |m| m.end());
+        let body = object_body(&content[body_start..]);
+        for member in regex_forwarding_member().captures_iter(body) {
+            if member[2] == member[3] {
+                out.push((api.clone(), member[1].to_string()));
+            }
+        }
+    }
+    out
+}
+
+/// Text of an object literal up to its closing brace (`text` starts after `{`).
+fn object_body(text: &str) -> &str {
+    let mut depth = 0usize;
+    for (idx, ch) in text.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' if depth == 0 => return &text[..idx],
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    text
+}
+
+impl IpcMatcher {
+    pub fn new(forwarders: &[(String, String)]) -> Self {
+        let mut callees =
+            vec![r"ipcRenderer\s*\.\s*(?:invoke|send|sendSync|postMessage)".to_string()];
+        callees.extend(forwarders.iter().map(|(api, method)| {
+            format!(
+                r"(?:window\s*\.\s*)?{}\s*\.\s*{}",
+                regex::escape(api),
+                regex::escape(method)
+            )
+        }));
+        let pattern = format!(
+            r#"(?:^|[^\w$.])(?:{})\s*\(\s*(?:["'`]([^"'`$]+)["'`]|([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)?)\s*[,)])"#,
+            callees.join("|")
+        );
+        IpcMatcher {
+            call: Regex::new(&pattern).expect("escaped bridge names form a valid regex"),
+        }
+    }
+
+    pub fn renderer_calls(&self, content: &str) -> Vec<CommandRef> {
+        channel_refs(&self.call, content)
+    }
+}
+
+/// Channels registered in the main process with `ipcMain.handle/handleOnce/on/once`.
+pub fn main_handlers(content: &str) -> Vec<CommandRef> {
+    if !content.contains("ipcMain") {
+        return Vec::new();
+    }
+    channel_refs(regex_main_handler(), content)
+}
+
+fn channel_refs(re: &Regex, content: &str) -> Vec<CommandRef> {
+    let constants = string_constants(content);
+    let mut refs = Vec::new();
+    for caps in re.captures_iter(content) {
+        let (name, offset) = if let Some(literal) = caps.get(1) {
+            (literal.as_str().to_string(), literal.start())
+        } else if let Some(ident) = caps.get(2) {
+            match constants.get(ident.as_str()) {
+                Some(value) => (value.clone(), ident.start()),
+                None => continue,
+            }
+        } else {
+            continue;
+        };
+        refs.push(CommandRef {
+            name,
+            line: offset_to_line(content, offset),
+            rust_name: None,
+            args: None,
+        });
+    }
+    refs
+}
+
+/// Renderer channels and `ipcMain` handlers gathered across a project.
+#[derive(Default)]
+pub struct IpcCoverage {
+    renderer: Locations,
+    main: Locations,
+}
+
+impl IpcCoverage {
+    pub fn add(&mut self, analysis: &FileAnalysis) {
+        for call in &analysis.ipc_calls {
+            self.renderer
+                .entry(call.name.clone())
+                .or_default()
+                .push((analysis.path.clone(), call.line));
+        }
+        for handler in &analysis.ipc_handlers {
+            self.main
+                .entry(handler.name.clone())
+                .or_default()
+                .push((analysis.path.clone(), handler.line));
+        }
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "renderer": self.renderer.iter().map(|(k,v)| json!({"name": k, "locations": v})).collect::<Vec<_>>(),
+            "main": self.main.iter().map(|(k,v)| json!({"name": k, "locations": v})).collect::<Vec<_>>(),
+            "missingHandlers": gaps(&self.renderer, &self.main).iter().map(|g| json!({"name": g.name, "locations": g.locations})).collect::<Vec<_>>(),
+            "unusedHandlers": gaps(&self.main, &self.renderer).iter().map(|g| json!({"name": g.name, "locations": g.locations})).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self) {
+        let missing = gaps(&self.renderer, &self.main);
+        let unused = gaps(&self.main, &self.renderer);
+        if missing.is_empty() && unused.is_empty() {
+            return;
+        }
+        println!("\nElectron IPC coverage:");
+        for (label, gaps) in [
+            (
+                "Missing handlers (renderer sends without ipcMain)",
+                &missing,
+            ),
+            ("Unused handlers (ipcMain not used by renderer)", &unused),
+        ] {
+            if gaps.is_empty() {
+                continue;
+            }
+            println!(
+                "  {}: {}",
+                label,
+                gaps.iter()
+                    .map(|g| g.label())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        let missing = gaps(&self.renderer, &self.main);
+        let unused = gaps(&self.main, &self.renderer);
+        if missing.is_empty() && unused.is_empty() {
+            return;
+        }
+        out.push_str(&format!(
+            "<table><tr><th>Electron: renderer channels without ipcMain handler</th><th>ipcMain handlers unused by renderer</th></tr><tr><td>{}</td><td>{}</td></tr></table>",
+            ctx.gap_list(&missing),
+            ctx.gap_list(&unused)
+        ));
+    }
+}
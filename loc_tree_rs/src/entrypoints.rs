@@ -0,0 +1,310 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::analyzer::{is_dev_file, relative_to_root, resolve_python_absolute};
+use crate::graph_export::language_for_path;
+use crate::report::{escape_html, HtmlContext};
+use crate::types::FileAnalysis;
+
+pub struct EntryPoint {
+    pub path: String,
+    pub origin: String,
+}
+
+pub struct EntryReach {
+    pub path: String,
+    pub origin: String,
+    pub reachable_files: usize,
+    pub reachable_loc: usize,
+}
+
+pub struct OrphanFile {
+    pub path: String,
+    pub loc: usize,
+    pub is_dev: bool,
+}
+
+pub struct Reachability {
+    pub entries: Vec<EntryReach>,
+    pub orphans: Vec<OrphanFile>,
+    pub orphan_loc: usize,
+    pub missing_entries: Vec<String>,
+}
+
+/// Reachability from the entry points of `root`, or `None` when it has none
+/// and no `--entry` was given.
+pub fn reachability(
+    root: &Path,
+    cli_entries: &[String],
+    edges: &[(String, String, String)],
+    analyses: &[FileAnalysis],
+) -> Option<Reachability> {
+    let (entry_points, missing_entries) = collect_entry_points(root, cli_entries);
+    for missing in &missing_entries {
+        eprintln!(
+            "[loctree][warn] entry '{}' not found under {}",
+            missing,
+            root.display()
+        );
+    }
+    if entry_points.is_empty() && missing_entries.is_empty() {
+        return None;
+    }
+    Some(compute_reachability(
+        &entry_points,
+        missing_entries,
+        edges,
+        analyses,
+    ))
+}
+
+/// Collects entry points for a root: explicit `--entry` paths plus Python
+/// console scripts declared in `pyproject.toml`. Entries that do not exist under
+/// the root are returned separately so they can be reported.
+fn collect_entry_points(root: &Path, cli_entries: &[String]) -> (Vec<EntryPoint>, Vec<String>) {
+    let mut found = Vec::new();
+    let mut missing = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for raw in cli_entries {
+        let candidate = Path::new(raw);
+        let full = if candidate.is_absolute() {
+            candidate.to_path_buf()
+        } else {
+            root.join(candidate)
+        };
+        match full
+            .is_file()
+            .then(|| relative_to_root(&full, root))
+            .flatten()
+        {
+            Some(rel) => {
+                if seen.insert(rel.clone()) {
+                    found.push(EntryPoint {
+                        path: rel,
+                        origin: "cli".to_string(),
+                    });
+                }
+            }
+            None => missing.push(raw.clone()),
+        }
+    }
+
+    for module in pyproject_script_modules(root) {
+        if let Some(rel) = resolve_python_absolute(&module, None, root) {
+            if seen.insert(rel.clone()) {
+                found.push(EntryPoint {
+                    path: rel,
+                    origin: "pyproject".to_string(),
+                });
+            }
+        }
+    }
+
+    (found, missing)
+}
+
+/// Reads `[project.scripts]`, `[project.gui-scripts]` and `[tool.poetry.scripts]`
+/// from `pyproject.toml` and returns the module part of each `module:function` target.
+fn pyproject_script_modules(root: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(root.join("pyproject.toml")) else {
+        return Vec::new();
+    };
+    let mut modules = Vec::new();
+    let mut in_scripts = false;
+    for line in content.lines() {
+        let trimmed = line.split('#').next().unwrap_or("").trim();
+        if trimmed.starts_with('[') {
+            let header = trimmed.trim_matches(|c| c == '[' || c == ']').trim();
+            in_scripts = matches!(
+                header,
+                "project.scripts" | "project.gui-scripts" | "tool.poetry.scripts"
+            );
+            continue;
+        }
+        if !in_scripts {
+            continue;
+        }
+        let Some((_, value)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let target = value.trim().trim_matches(|c| c == '"' || c == '\'');
+        let module = target.split(':').next().unwrap_or("").trim();
+        if !module.is_empty() {
+            modules.push(module.to_string());
+        }
+    }
+    modules
+}
+
+/// Languages that import each other: TS/JS and the CSS they pull in form one
+/// family, Rust and Python are separate.
+fn language_family(path: &str) -> &'static str {
+    match language_for_path(path) {
+        "typescript" | "javascript" | "css" => "web",
+        other => other,
+    }
+}
+
+/// Walks the resolved file graph from every entry point and lists files that
+/// no entry reaches. Only files in a language family that has an entry point
+/// can be orphans: a Python console script says nothing about a Rust crate
+/// next to it.
+fn compute_reachability(
+    entries: &[EntryPoint],
+    missing_entries: Vec<String>,
+    edges: &[(String, String, String)],
+    analyses: &[FileAnalysis],
+) -> Reachability {
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (from, to, _) in edges {
+        adjacency
+            .entry(from.as_str())
+            .or_default()
+            .push(to.as_str());
+    }
+    let loc_by_path: HashMap<&str, usize> =
+        analyses.iter().map(|a| (a.path.as_str(), a.loc)).collect();
+
+    let mut reached_any: HashSet<&str> = HashSet::new();
+    let mut entry_reach = Vec::new();
+    for entry in entries {
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = VecDeque::new();
+        visited.insert(entry.path.as_str());
+        queue.push_back(entry.path.as_str());
+        while let Some(current) = queue.pop_front() {
+            if let Some(targets) = adjacency.get(current) {
+                for target in targets {
+                    if visited.insert(target) {
+                        queue.push_back(target);
+                    }
+                }
+            }
+        }
+        let reachable_loc = visited
+            .iter()
+            .map(|p| loc_by_path.get(p).copied().unwrap_or(0))
+            .sum();
+        entry_reach.push(EntryReach {
+            path: entry.path.clone(),
+            origin: entry.origin.clone(),
+            reachable_files: visited.len(),
+            reachable_loc,
+        });
+        reached_any.extend(visited);
+    }
+
+    let families: HashSet<&str> = entries.iter().map(|e| language_family(&e.path)).collect();
+    let mut orphans: Vec<OrphanFile> = analyses
+        .iter()
+        .filter(|a| families.contains(language_family(&a.path)))
+        .filter(|a| !reached_any.contains(a.path.as_str()))
+        .map(|a| OrphanFile {
+            path: a.path.clone(),
+            loc: a.loc,
+            is_dev: is_dev_file(&a.path),
+        })
+        .collect();
+    orphans.sort_by(|a, b| b.loc.cmp(&a.loc).then(a.path.cmp(&b.path)));
+    let orphan_loc = orphans.iter().map(|o| o.loc).sum();
+
+    Reachability {
+        entries: entry_reach,
+        orphans,
+        orphan_loc,
+        missing_entries,
+    }
+}
+
+impl Reachability {
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "entries": self.entries.iter().map(|e| json!({
+                "path": e.path,
+                "origin": e.origin,
+                "reachableFiles": e.reachable_files,
+                "reachableLoc": e.reachable_loc,
+            })).collect::<Vec<_>>(),
+            "missingEntries": self.missing_entries,
+            "orphans": self.orphans.iter().map(|o| json!({"path": o.path, "loc": o.loc, "isDev": o.is_dev})).collect::<Vec<_>>(),
+            "orphanLoc": self.orphan_loc,
+        })
+    }
+
+    pub fn print(&self, limit: usize) {
+        println!("\nEntry-point reachability:");
+        for entry in &self.entries {
+            println!(
+                "  - {} ({}): {} files, {} LOC reachable",
+                entry.path, entry.origin, entry.reachable_files, entry.reachable_loc
+            );
+        }
+        if !self.missing_entries.is_empty() {
+            println!("  Missing entries: {}", self.missing_entries.join(", "));
+        }
+        if !self.entries.is_empty() {
+            println!(
+                "  Orphan files (unreachable from any entry): {} ({} LOC), showing up to {}:",
+                self.orphans.len(),
+                self.orphan_loc,
+                limit
+            );
+            for orphan in self.orphans.iter().take(limit) {
+                println!(
+                    "    - {} ({} LOC){}",
+                    orphan.path,
+                    orphan.loc,
+                    if orphan.is_dev { "  [dev]" } else { "" }
+                );
+            }
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        out.push_str("<h3>Entry-point reachability</h3>");
+        if self.entries.is_empty() {
+            out.push_str("<p class=\"muted\">No entry points resolved.</p>");
+        } else {
+            out.push_str(
+                "<table><tr><th>Entry</th><th>Origin</th><th>Reachable files</th><th>Reachable LOC</th></tr>",
+            );
+            for entry in &self.entries {
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&entry.path),
+                    escape_html(&entry.origin),
+                    entry.reachable_files,
+                    entry.reachable_loc
+                ));
+            }
+            out.push_str("</table>");
+            out.push_str(&format!(
+                "<p class=\"muted\">Orphan files: {} ({} LOC)</p>",
+                self.orphans.len(),
+                self.orphan_loc
+            ));
+            if !self.orphans.is_empty() {
+                out.push_str("<table><tr><th>File</th><th>LOC</th><th>Dev</th></tr>");
+                for orphan in self.orphans.iter().take(ctx.limit) {
+                    out.push_str(&format!(
+                        "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+                        escape_html(&orphan.path),
+                        orphan.loc,
+                        if orphan.is_dev { "yes" } else { "" }
+                    ));
+                }
+                out.push_str("</table>");
+            }
+        }
+        if !self.missing_entries.is_empty() {
+            out.push_str(&format!(
+                "<p class=\"muted\">Missing entries: {}</p>",
+                escape_html(&self.missing_entries.join(", "))
+            ));
+        }
+    }
+}
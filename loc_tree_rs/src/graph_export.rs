@@ -0,0 +1,272 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::analyzer::is_dev_file;
+use crate::types::{FileAnalysis, GraphFormat};
+
+pub struct GraphNode {
+    pub id: String,
+    pub loc: Option<usize>,
+    pub language: &'static str,
+    pub is_dev: bool,
+}
+
+pub struct FileGraph {
+    pub root: String,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<(String, String, String)>,
+}
+
+pub fn language_for_path(path: &str) -> &'static str {
+    let ext = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|s| s.to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "ts" | "tsx" => "typescript",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "rs" => "rust",
+        "py" => "python",
+        "css" => "css",
+        _ => "other",
+    }
+}
+
+/// Builds the resolved file graph for one root. Every analyzed file becomes a
+/// node; edge targets outside the analyzed set are kept without a LOC value.
+pub fn build_file_graph(
+    root: &str,
+    analyses: &[FileAnalysis],
+    edges: &[(String, String, String)],
+) -> FileGraph {
+    let mut nodes: BTreeMap<String, GraphNode> = analyses
+        .iter()
+        .map(|a| {
+            (
+                a.path.clone(),
+                GraphNode {
+                    id: a.path.clone(),
+                    loc: Some(a.loc),
+                    language: language_for_path(&a.path),
+                    is_dev: is_dev_file(&a.path),
+                },
+            )
+        })
+        .collect();
+    let unique: BTreeSet<(String, String, String)> = edges.iter().cloned().collect();
+    for (from, to, _) in &unique {
+        for id in [from, to] {
+            nodes.entry(id.clone()).or_insert_with(|| GraphNode {
+                id: id.clone(),
+                loc: None,
+                language: language_for_path(id),
+                is_dev: is_dev_file(id),
+            });
+        }
+    }
+    FileGraph {
+        root: root.to_string(),
+        nodes: nodes.into_values().collect(),
+        edges: unique.into_iter().collect(),
+    }
+}
+
+fn xml_escape(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn dot_escape(raw: &str) -> String {
+    raw.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn render_dot(graphs: &[FileGraph]) -> String {
+    let mut out = String::new();
+    for graph in graphs {
+        out.push_str(&format!("digraph \"{}\" {{\n", dot_escape(&graph.root)));
+        out.push_str("  rankdir=LR;\n  node [shape=box, fontsize=10];\n");
+        for node in &graph.nodes {
+            let label = match node.loc {
+                Some(loc) => format!("{}\\n{} LOC", dot_escape(&node.id), loc),
+                None => dot_escape(&node.id),
+            };
+            out.push_str(&format!(
+                "  \"{}\" [label=\"{}\", language=\"{}\", loc={}, dev={}{}];\n",
+                dot_escape(&node.id),
+                label,
+                node.language,
+                node.loc.unwrap_or(0),
+                node.is_dev,
+                if node.is_dev { ", style=dashed" } else { "" }
+            ));
+        }
+        for (from, to, kind) in &graph.edges {
+            let style = match kind.as_str() {
+                "reexport" => ", style=bold",
+                "dynamic" => ", style=dashed",
+                _ => "",
+            };
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [kind=\"{}\", label=\"{}\"{}];\n",
+                dot_escape(from),
+                dot_escape(to),
+                kind,
+                if kind == "import" { "" } else { kind.as_str() },
+                style
+            ));
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+fn render_graphml(graphs: &[FileGraph]) -> String {
+    let mut out = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="loc" for="node" attr.name="loc" attr.type="int"/>
+  <key id="language" for="node" attr.name="language" attr.type="string"/>
+  <key id="dev" for="node" attr.name="dev" attr.type="boolean"/>
+  <key id="path" for="node" attr.name="path" attr.type="string"/>
+  <key id="kind" for="edge" attr.name="kind" attr.type="string"/>
+"#,
+    );
+    // Node ids must be unique across the whole document, and the same relative
+    // path can exist under several roots.
+    for (idx, graph) in graphs.iter().enumerate() {
+        let node_id = |path: &str| xml_escape(&format!("r{}:{}", idx, path));
+        out.push_str(&format!(
+            "  <graph id=\"{}\" edgedefault=\"directed\">\n",
+            xml_escape(&graph.root)
+        ));
+        for node in &graph.nodes {
+            out.push_str(&format!(
+                "    <node id=\"{}\">\n      <data key=\"path\">{}</data>\n",
+                node_id(&node.id),
+                xml_escape(&node.id)
+            ));
+            if let Some(loc) = node.loc {
+                out.push_str(&format!("      <data key=\"loc\">{}</data>\n", loc));
+            }
+            out.push_str(&format!(
+                "      <data key=\"language\">{}</data>\n      <data key=\"dev\">{}</data>\n    </node>\n",
+                node.language, node.is_dev
+            ));
+        }
+        for (from, to, kind) in &graph.edges {
+            out.push_str(&format!(
+                "    <edge source=\"{}\" target=\"{}\">\n      <data key=\"kind\">{}</data>\n    </edge>\n",
+                node_id(from),
+                node_id(to),
+                xml_escape(kind)
+            ));
+        }
+        out.push_str("  </graph>\n");
+    }
+    out.push_str("</graphml>\n");
+    out
+}
+
+/// One diagram; with several roots each becomes a `subgraph`.
+fn render_mermaid(graphs: &[FileGraph]) -> String {
+    let mut out = String::from("graph LR\n");
+    let nested = graphs.len() > 1;
+    for (idx, graph) in graphs.iter().enumerate() {
+        if nested {
+            out.push_str(&format!(
+                "  subgraph r{}[\"{}\"]\n",
+                idx,
+                graph.root.replace('"', "#quot;")
+            ));
+        } else {
+            out.push_str(&format!("%% {}\n", graph.root));
+        }
+        let ids: BTreeMap<&str, String> = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), format!("r{}n{}", idx, i)))
+            .collect();
+        for node in &graph.nodes {
+            let label = match node.loc {
+                Some(loc) => format!("{}<br/>{} LOC", node.id, loc),
+                None => node.id.clone(),
+            };
+            out.push_str(&format!(
+                "  {}[\"{}\"]\n",
+                ids[node.id.as_str()],
+                label.replace('"', "#quot;")
+            ));
+        }
+        for (from, to, kind) in &graph.edges {
+            let arrow = match kind.as_str() {
+                "import" => "-->".to_string(),
+                "dynamic" => "-. dynamic .->".to_string(),
+                other => format!("-->|{}|", other),
+            };
+            out.push_str(&format!(
+                "  {} {} {}\n",
+                ids[from.as_str()],
+                arrow,
+                ids[to.as_str()]
+            ));
+        }
+        if nested {
+            out.push_str("  end\n");
+        }
+    }
+    out
+}
+
+/// JSON Graph Format (https://jsongraphformat.info): `graph` for a single root, `graphs` otherwise.
+fn render_json_graph(graphs: &[FileGraph]) -> String {
+    let as_json: Vec<_> = graphs
+        .iter()
+        .map(|graph| {
+            let nodes: serde_json::Map<String, serde_json::Value> = graph
+                .nodes
+                .iter()
+                .map(|n| {
+                    (
+                        n.id.clone(),
+                        json!({
+                            "label": n.id,
+                            "metadata": {"loc": n.loc, "language": n.language, "dev": n.is_dev},
+                        }),
+                    )
+                })
+                .collect();
+            json!({
+                "directed": true,
+                "label": graph.root,
+                "nodes": nodes,
+                "edges": graph
+                    .edges
+                    .iter()
+                    .map(|(from, to, kind)| json!({"source": from, "target": to, "relation": kind}))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    let payload = if as_json.len() == 1 {
+        json!({"graph": as_json[0]})
+    } else {
+        json!({"graphs": as_json})
+    };
+    serde_json::to_string_pretty(&payload).unwrap()
+}
+
+pub fn render_graph(format: GraphFormat, graphs: &[FileGraph]) -> String {
+    match format {
+        GraphFormat::Dot => render_dot(graphs),
+        GraphFormat::GraphMl => render_graphml(graphs),
+        GraphFormat::Mermaid => render_mermaid(graphs),
+        GraphFormat::Json => render_json_graph(graphs),
+    }
+}
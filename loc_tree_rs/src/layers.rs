@@ -0,0 +1,159 @@
+use regex::Regex;
+use serde_json::json;
+
+use crate::config::{compile_glob, DependencyRule};
+use crate::report::{escape_html, HtmlContext};
+use crate::types::FileAnalysis;
+
+#[derive(Clone)]
+pub struct RuleViolation {
+    pub rule: String,
+    pub from: String,
+    pub to: String,
+    pub source: String,
+    pub line: usize,
+    pub reason: String,
+}
+
+struct CompiledRule {
+    label: String,
+    from: Regex,
+    forbid: Vec<(String, Regex)>,
+    allow: Option<Vec<Regex>>,
+}
+
+/// Patterns were validated when the config was loaded.
+fn compile_rules(rules: &[DependencyRule]) -> Vec<CompiledRule> {
+    let glob = |pattern: &str| compile_glob(pattern).expect("validated by load_config");
+    rules
+        .iter()
+        .map(|rule| CompiledRule {
+            label: rule.label(),
+            from: glob(&rule.from),
+            forbid: rule.forbid.iter().map(|p| (p.clone(), glob(p))).collect(),
+            allow: rule
+                .allow
+                .as_ref()
+                .map(|list| list.iter().map(|p| glob(p)).collect()),
+        })
+        .collect()
+}
+
+/// Checks every resolved import, re-export and dynamic import edge against the
+/// configured rules.
+pub fn check_dependency_rules(
+    rules: &[DependencyRule],
+    analyses: &[FileAnalysis],
+) -> Vec<RuleViolation> {
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    let compiled = compile_rules(rules);
+    let mut violations = Vec::new();
+
+    for analysis in analyses {
+        let from = analysis.path.replace('\\', "/");
+        let applicable: Vec<&CompiledRule> =
+            compiled.iter().filter(|r| r.from.is_match(&from)).collect();
+        if applicable.is_empty() {
+            continue;
+        }
+        let edges = analysis
+            .imports
+            .iter()
+            .filter_map(|i| i.resolved.as_ref().map(|t| (t, &i.source, i.line)))
+            .chain(
+                analysis
+                    .reexports
+                    .iter()
+                    .filter_map(|r| r.resolved.as_ref().map(|t| (t, &r.source, r.line))),
+            )
+            .chain(
+                analysis
+                    .dynamic_imports
+                    .iter()
+                    .flat_map(|d| d.resolved.iter().map(move |t| (t, &d.source, d.line))),
+            );
+        for (target, source, line) in edges {
+            let to = target.replace('\\', "/");
+            if to == from {
+                continue;
+            }
+            for rule in &applicable {
+                let reason = if let Some((pattern, _)) =
+                    rule.forbid.iter().find(|(_, re)| re.is_match(&to))
+                {
+                    Some(format!("forbidden by {}", pattern))
+                } else if rule
+                    .allow
+                    .as_ref()
+                    .is_some_and(|allow| !allow.iter().any(|re| re.is_match(&to)))
+                {
+                    Some("not in allow list".to_string())
+                } else {
+                    None
+                };
+                if let Some(reason) = reason {
+                    violations.push(RuleViolation {
+                        rule: rule.label.clone(),
+                        from: analysis.path.clone(),
+                        to: target.clone(),
+                        source: source.clone(),
+                        line,
+                        reason,
+                    });
+                }
+            }
+        }
+    }
+
+    violations.sort_by(|a, b| a.from.cmp(&b.from).then(a.line.cmp(&b.line)));
+    violations
+}
+
+pub fn violations_json(violations: &[RuleViolation]) -> serde_json::Value {
+    json!({
+        "ruleViolations": violations.iter().map(|v| json!({
+            "rule": v.rule,
+            "from": v.from,
+            "to": v.to,
+            "source": v.source,
+            "line": v.line,
+            "reason": v.reason,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+pub fn print_violations(violations: &[RuleViolation]) {
+    if violations.is_empty() {
+        return;
+    }
+    println!("\nDependency rule violations: {}", violations.len());
+    for v in violations {
+        println!(
+            "  - {}:{} imports {} ({}) [{}: {}]",
+            v.from, v.line, v.to, v.source, v.rule, v.reason
+        );
+    }
+}
+
+pub fn violations_html(violations: &[RuleViolation], out: &mut String, ctx: &HtmlContext) {
+    if violations.is_empty() {
+        return;
+    }
+    out.push_str(&format!(
+        "<h3>Dependency rule violations ({})</h3>",
+        violations.len()
+    ));
+    out.push_str("<table><tr><th>Location</th><th>Imports</th><th>Rule</th><th>Reason</th></tr>");
+    for v in violations {
+        out.push_str(&format!(
+            "<tr><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+            ctx.link(&v.from, v.line),
+            escape_html(&v.to),
+            escape_html(&v.rule),
+            escape_html(&v.reason)
+        ));
+    }
+    out.push_str("</table>");
+}
@@ -1,6 +1,24 @@
 mod analyzer;
 mod args;
+mod barrels;
+mod cache;
+mod capabilities;
+mod clones;
+mod config;
+mod dependencies;
+mod duplicates;
+mod dynamic_imports;
+mod electron;
+mod entrypoints;
 mod fs_utils;
+mod graph_export;
+mod layers;
+mod non_code;
+mod python;
+mod report;
+mod routes;
+mod rust_syntax;
+mod tauri;
 mod tree;
 mod types;
 
@@ -31,7 +49,7 @@ fn install_broken_pipe_handler() {
 }
 
 fn format_usage() -> &'static str {
-    "loctree (Rust)\n\nUsage: loctree [root ...] [options]\n\nModes:\n  --analyze-imports, -A  Switch to import/export analyzer (reports re-exports, duplicate exports, dynamic imports).\n\nOptions:\n  --ext <list>         Comma-separated extensions to include (e.g. --ext rs,ts,tsx,py,css).\n                       Prunes non-matching files/dirs from the tree or the analyzer input set.\n                       Analyzer defaults: ts,tsx,js,jsx,mjs,cjs,rs,css,py when --ext is omitted.\n  -I, --ignore <path>  Ignore a folder/file (relative or absolute). Repeatable.\n  --gitignore, -g      Respect current Git ignore rules (requires git).\n  -L, --max-depth <n>  Limit recursion depth (0 = only direct children).\n  --color[=mode]       Colorize large files. mode: auto|always|never (default auto).\n  --loc <n>            Threshold (LOC) for large-file highlighting (tree mode). Default 1000.\n  --show-hidden, -H    Include dotfiles.\n  --json               Emit JSON instead of a tree view (single root => object, multi-root => array).\n  --jsonl              Emit one JSON object per line (per root) in analyzer mode.\n  --html-report <file> Write analyzer results to an HTML report file.\n  --graph              Embed an import graph into the HTML report (Cytoscape.js from CDN).\n  --serve              Start a lightweight local server so HTML links can open files in your editor/OS handler.\n  --editor-cmd <tpl>   Command template to open files (default tries: code -g {file}:{line}, else open/xdg-open).\n  --ignore-symbols <l> Analyzer: comma-separated symbols to skip when counting duplicate exports (case-insensitive).\n  --ignore-symbols-preset <name> Analyzer: predefined set (e.g. common => main,run,setup,test_*).\n  --summary[=N]        Tree: totals + top large files (N entries, default 5).\n  --limit <N>          Analyzer: top-N duplicate exports / dynamic imports (default 8).\n  --help, -h           Show this message.\n\nExamples:\n  loctree src --ext rs,ts --summary\n  loctree src packages/app src-tauri/src -I node_modules -L 2\n  loctree . --json > tree.json\n  loctree src apps/web -A --json --ext ts,tsx,css --limit 10 --serve --html-report /tmp/loctree.html\n  loctree backend -A --ext py --gitignore --limit 5\n  loctree . -A --ext py --ignore-symbols main,run\n  loctree . -A --ext py --ignore-symbols-preset common --graph --html-report /tmp/loctree.html\n"
+    "loctree (Rust)\n\nUsage: loctree [root ...] [options]\n\nModes:\n  --analyze-imports, -A  Switch to import/export analyzer (reports re-exports, duplicate exports, dynamic imports).\n\nOptions:\n  --ext <list>         Comma-separated extensions to include (e.g. --ext rs,ts,tsx,py,css).\n                       Prunes non-matching files/dirs from the tree or the analyzer input set.\n                       Analyzer defaults: ts,tsx,js,jsx,mjs,cjs,rs,css,py when --ext is omitted.\n  -I, --ignore <path>  Ignore a folder/file (relative or absolute). Repeatable.\n  --gitignore, -g      Respect current Git ignore rules (requires git).\n  -L, --max-depth <n>  Limit recursion depth (0 = only direct children).\n  --color[=mode]       Colorize large files. mode: auto|always|never (default auto).\n  --loc <n>            Threshold (LOC) for large-file highlighting (tree mode). Default 1000.\n  --show-hidden, -H    Include dotfiles.\n  --json               Emit JSON instead of a tree view (single root => object, multi-root => array).\n  --jsonl              Emit one JSON object per line (per root) in analyzer mode.\n  --html-report <file> Write analyzer results to an HTML report file.\n  --graph              Embed an import graph into the HTML report (inline SVG, works offline).\n  --graph-format <f>   Analyzer: export the resolved file graph as dot|graphml|mermaid|json.\n                       Printed to stdout instead of the analysis unless --graph-out is set.\n  --graph-out <file>   Write the --graph-format export to a file.\n  --serve              Start a lightweight local server so HTML links can open files in your editor/OS handler.\n  --editor-cmd <tpl>   Command template to open files (default tries: code -g {file}:{line}, else open/xdg-open).\n  --ignore-symbols <l> Analyzer: comma-separated symbols to skip when counting duplicate exports (case-insensitive).\n  --ignore-symbols-preset <name> Analyzer: predefined set (e.g. common => main,run,setup,test_*).\n  --summary[=N]        Tree: totals + top large files (N entries, default 5).\n  --limit <N>          Analyzer: top-N duplicate exports / dynamic imports (default 8).\n  --clones             Analyzer: report code clones (copy-pasted code modulo renames).\n  --clone-tokens <N>   Analyzer: minimum clone length in tokens (default 50); implies --clones, 0 = off.\n  --no-cache           Analyzer: do not read or write the per-file analysis cache.\n  --cache-dir <dir>    Analyzer: keep the cache under <dir> (default $XDG_CACHE_HOME/loctree or ~/.cache/loctree).\n  --verbose, -v        Print diagnostics such as cache statistics to stderr.\n  --entry <path>       Analyzer: entry point for reachability/orphan detection. Repeatable.\n                       Python scripts from pyproject.toml are picked up automatically.\n  --config <file>      Analyzer: JSON config (default: <root>/.loctree.json). Holds dependency\n                       rules; violations are reported and exit with code 2.\n  --help, -h           Show this message.\n\nExamples:\n  loctree src --ext rs,ts --summary\n  loctree src packages/app src-tauri/src -I node_modules -L 2\n  loctree . --json > tree.json\n  loctree src apps/web -A --json --ext ts,tsx,css --limit 10 --serve --html-report /tmp/loctree.html\n  loctree backend -A --ext py --gitignore --limit 5\n  loctree . -A --ext py --ignore-symbols main,run\n  loctree . -A --ext py --ignore-symbols-preset common --graph --html-report /tmp/loctree.html\n  loctree . -A --entry src/main.tsx --entry src-tauri/src/main.rs\n"
 }
 
 fn main() -> std::io::Result<()> {
@@ -79,10 +97,22 @@ fn main() -> std::io::Result<()> {
         root_list.push(root.canonicalize().unwrap_or_else(|_| root.clone()));
     }
 
-    match parsed.mode {
-        Mode::AnalyzeImports => analyzer::run_import_analyzer(&root_list, &parsed)?,
-        Mode::Tree => tree::run_tree(&root_list, &parsed)?,
+    let result = match parsed.mode {
+        Mode::AnalyzeImports => analyzer::run_import_analyzer(&root_list, &parsed),
+        Mode::Tree => tree::run_tree(&root_list, &parsed).map(|_| 0),
+    };
+    match result {
+        Ok(0) => Ok(()),
+        Ok(violations) => {
+            eprintln!(
+                "[loctree] {} dependency rule violation(s) found",
+                violations
+            );
+            std::process::exit(2);
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
     }
-
-    Ok(())
 }
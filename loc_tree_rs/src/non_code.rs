@@ -0,0 +1,209 @@
+/// Byte ranges of comments and string literals in a source file. Text
+/// scanners drop matches starting inside them, so commented-out code, doc
+/// examples and strings are not taken for code.
+pub struct NonCode(Vec<(usize, usize)>);
+
+impl NonCode {
+    /// Rust comments (nested block comments included), strings, raw strings
+    /// and char literals; used by the regex fallback for files `syn` rejects.
+    pub fn rust(content: &str) -> Self {
+        let bytes = content.as_bytes();
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let start = i;
+            let prev_ident =
+                i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_');
+            match bytes[i] {
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    i = content[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    let mut depth = 0usize;
+                    while i < bytes.len() {
+                        if bytes[i..].starts_with(b"/*") {
+                            depth += 1;
+                            i += 2;
+                        } else if bytes[i..].starts_with(b"*/") {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        } else {
+                            i += 1;
+                        }
+                    }
+                }
+                b'r' if !prev_ident && matches!(bytes.get(i + 1), Some(b'"' | b'#')) => {
+                    let hashes = bytes[i + 1..].iter().take_while(|b| **b == b'#').count();
+                    if bytes.get(i + 1 + hashes) != Some(&b'"') {
+                        i += 1;
+                        continue;
+                    }
+                    let close = format!("\"{}", "#".repeat(hashes));
+                    let body = i + 2 + hashes;
+                    i = content[body..]
+                        .find(&close)
+                        .map_or(bytes.len(), |n| body + n + close.len());
+                }
+                b'"' => {
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != b'"' {
+                        i += if bytes[i] == b'\\' { 2 } else { 1 };
+                    }
+                    i += 1;
+                }
+                b'\'' => {
+                    // `'a'`, `'\n'`; anything else is a lifetime or label.
+                    let rest = &content[i + 1..];
+                    let len = if rest.starts_with('\\') {
+                        rest[2.min(rest.len())..].find('\'').map(|n| n + 3)
+                    } else {
+                        rest.chars()
+                            .next()
+                            .filter(|c| rest[c.len_utf8()..].starts_with('\''))
+                            .map(|c| c.len_utf8() + 2)
+                    };
+                    i += len.unwrap_or(1);
+                    if len.is_none() {
+                        continue;
+                    }
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+            ranges.push((start, i.min(bytes.len())));
+        }
+        NonCode(ranges)
+    }
+
+    /// JS/TS comments, quoted strings, template literals (substitutions
+    /// included) and regex literals.
+    pub fn js(content: &str) -> Self {
+        let bytes = content.as_bytes();
+        let mut ranges = Vec::new();
+        // Last byte of code, to tell a regex literal from a division.
+        let mut prev: Option<u8> = None;
+        let mut i = 0;
+        while i < bytes.len() {
+            let start = i;
+            match bytes[i] {
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    i = content[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                    ranges.push((start, i));
+                    continue;
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = content[i + 2..]
+                        .find("*/")
+                        .map_or(bytes.len(), |n| i + 2 + n + 2);
+                    ranges.push((start, i));
+                    continue;
+                }
+                b'"' | b'\'' => {
+                    let quote = bytes[i];
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != quote && bytes[i] != b'\n' {
+                        i += if bytes[i] == b'\\' { 2 } else { 1 };
+                    }
+                    i += 1;
+                }
+                b'`' => {
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != b'`' {
+                        if bytes[i] == b'\\' {
+                            i += 2;
+                        } else if bytes[i..].starts_with(b"${") {
+                            let mut depth = 0usize;
+                            while i < bytes.len() {
+                                match bytes[i] {
+                                    b'{' => depth += 1,
+                                    b'}' if depth <= 1 => break,
+                                    b'}' => depth -= 1,
+                                    _ => {}
+                                }
+                                i += 1;
+                            }
+                            i += 1;
+                        } else {
+                            i += 1;
+                        }
+                    }
+                    i += 1;
+                }
+                b'/' if prev.is_none_or(|p| b"(,=:[!&|?{};+-*%<>~^".contains(&p)) => {
+                    let mut in_class = false;
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        match bytes[i] {
+                            b'\\' => i += 1,
+                            b'[' => in_class = true,
+                            b']' => in_class = false,
+                            b'/' if !in_class => break,
+                            _ => {}
+                        }
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                b => {
+                    if !b.is_ascii_whitespace() {
+                        prev = Some(b);
+                    }
+                    i += 1;
+                    continue;
+                }
+            }
+            // A literal is an operand: a `/` after it divides.
+            prev = Some(b'"');
+            ranges.push((start, i.min(bytes.len())));
+        }
+        NonCode(ranges)
+    }
+
+    /// Python comments and strings, triple-quoted ones (docstrings) included.
+    pub fn python(content: &str) -> Self {
+        let bytes = content.as_bytes();
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let start = i;
+            match bytes[i] {
+                b'#' => {
+                    i = content[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                }
+                b'"' | b'\'' => {
+                    let quote = bytes[i];
+                    let triple = [quote; 3];
+                    if bytes[i..].starts_with(&triple) {
+                        i += 3;
+                        while i < bytes.len() && !bytes[i..].starts_with(&triple) {
+                            i += if bytes[i] == b'\\' { 2 } else { 1 };
+                        }
+                        i += 3;
+                    } else {
+                        i += 1;
+                        while i < bytes.len() && bytes[i] != quote && bytes[i] != b'\n' {
+                            i += if bytes[i] == b'\\' { 2 } else { 1 };
+                        }
+                        i += 1;
+                    }
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+            ranges.push((start, i.min(bytes.len())));
+        }
+        NonCode(ranges)
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        let idx = self.0.partition_point(|(start, _)| *start <= offset);
+        idx > 0 && offset < self.0[idx - 1].1
+    }
+}
@@ -0,0 +1,268 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// One Python statement after joining bracketed and backslash-continued
+/// lines and dropping comments. String literals (including docstrings) are
+/// kept verbatim but never split a statement.
+pub struct LogicalLine {
+    pub line: usize,
+    pub indent: usize,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PyBlock {
+    /// `def` / `class` body: nothing inside is module level.
+    Scope,
+    /// `if TYPE_CHECKING:` body.
+    TypeChecking,
+    /// `try:` with an `except ImportError` handler, or that handler itself.
+    ImportGuard,
+    Other,
+}
+
+pub fn logical_lines(content: &str) -> Vec<LogicalLine> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = Vec::new();
+    let mut text = String::new();
+    let mut line = 1;
+    let mut start_line = 1;
+    let mut indent = 0;
+    let mut at_line_start = true;
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    let flush =
+        |text: &mut String, out: &mut Vec<LogicalLine>, start_line: usize, indent: usize| {
+            let trimmed = text.trim();
+            if !trimmed.is_empty() {
+                out.push(LogicalLine {
+                    line: start_line,
+                    indent,
+                    text: trimmed.to_string(),
+                });
+            }
+            text.clear();
+        };
+
+    while i < chars.len() {
+        let c = chars[i];
+        if at_line_start {
+            match c {
+                ' ' => {
+                    indent += 1;
+                    i += 1;
+                    continue;
+                }
+                '\t' => {
+                    indent += 8 - indent % 8;
+                    i += 1;
+                    continue;
+                }
+                '\r' | '\x0c' => {
+                    i += 1;
+                    continue;
+                }
+                '\n' => {
+                    line += 1;
+                    indent = 0;
+                    i += 1;
+                    continue;
+                }
+                _ => at_line_start = false,
+            }
+        }
+        if text.trim().is_empty() && !c.is_whitespace() {
+            start_line = line;
+        }
+        match c {
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '\\' if chars.get(i + 1) == Some(&'\n')
+                || (chars.get(i + 1) == Some(&'\r') && chars.get(i + 2) == Some(&'\n')) =>
+            {
+                text.push(' ');
+                line += 1;
+                i += if chars[i + 1] == '\r' { 3 } else { 2 };
+                continue;
+            }
+            '"' | '\'' => {
+                let triple = chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c);
+                let quote_len = if triple { 3 } else { 1 };
+                for _ in 0..quote_len {
+                    text.push(c);
+                }
+                i += quote_len;
+                while i < chars.len() {
+                    let ch = chars[i];
+                    if ch == '\\' && i + 1 < chars.len() {
+                        if chars[i + 1] == '\n' {
+                            line += 1;
+                        }
+                        text.push(ch);
+                        text.push(chars[i + 1]);
+                        i += 2;
+                        continue;
+                    }
+                    if ch == '\n' {
+                        if !triple {
+                            // Unterminated string: let the newline end the statement.
+                            break;
+                        }
+                        line += 1;
+                        text.push(' ');
+                        i += 1;
+                        continue;
+                    }
+                    if ch == c
+                        && (!triple
+                            || (chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c)))
+                    {
+                        for _ in 0..quote_len {
+                            text.push(c);
+                        }
+                        i += quote_len;
+                        break;
+                    }
+                    text.push(ch);
+                    i += 1;
+                }
+                continue;
+            }
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                flush(&mut text, &mut out, start_line, indent);
+                i += 1;
+                continue;
+            }
+            '\n' => {
+                line += 1;
+                if depth > 0 {
+                    text.push(' ');
+                } else {
+                    flush(&mut text, &mut out, start_line, indent);
+                    indent = 0;
+                    at_line_start = true;
+                }
+                i += 1;
+                continue;
+            }
+            '\r' => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        text.push(c);
+        i += 1;
+    }
+    flush(&mut text, &mut out, start_line, indent);
+    out
+}
+
+/// Classifies a block header (a statement ending in `:`). `lines[idx]` must be
+/// the header; the following lines are used to find `except ImportError` for `try:`.
+pub fn classify_block(lines: &[LogicalLine], idx: usize) -> Option<PyBlock> {
+    let text = lines[idx].text.as_str();
+    if !text.ends_with(':') {
+        return None;
+    }
+    let kind =
+        if text.starts_with("def ") || text.starts_with("async def ") || text.starts_with("class ")
+        {
+            PyBlock::Scope
+        } else if is_type_checking_guard(text) {
+            PyBlock::TypeChecking
+        } else if text == "try:" {
+            if try_guards_import_error(lines, idx) {
+                PyBlock::ImportGuard
+            } else {
+                PyBlock::Other
+            }
+        } else if text.starts_with("except") && catches_import_error(text) {
+            PyBlock::ImportGuard
+        } else {
+            PyBlock::Other
+        };
+    Some(kind)
+}
+
+pub fn is_type_checking_guard(text: &str) -> bool {
+    matches!(
+        text.replace(' ', "").as_str(),
+        "ifTYPE_CHECKING:" | "iftyping.TYPE_CHECKING:" | "if_t.TYPE_CHECKING:"
+    )
+}
+
+fn catches_import_error(text: &str) -> bool {
+    text.contains("ImportError") || text.contains("ModuleNotFoundError")
+}
+
+fn try_guards_import_error(lines: &[LogicalLine], try_idx: usize) -> bool {
+    let indent = lines[try_idx].indent;
+    let mut j = try_idx + 1;
+    loop {
+        while j < lines.len() && lines[j].indent > indent {
+            j += 1;
+        }
+        let Some(next) = lines.get(j) else {
+            return false;
+        };
+        if next.indent != indent || !next.text.starts_with("except") {
+            return false;
+        }
+        if catches_import_error(&next.text) {
+            return true;
+        }
+        j += 1;
+    }
+}
+
+fn regex_dunder_all() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"^__all__\s*(?:(?::[^=]+)?=|\+=|\.extend\s*\(|\.append\s*\()(.*)$"#).unwrap()
+    })
+}
+
+fn regex_string_name() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"["']([A-Za-z_][A-Za-z0-9_]*)["']"#).unwrap())
+}
+
+/// Names listed by `__all__ = [...]`/`(...)`, `__all__ += ...`,
+/// `__all__.extend(...)` or `__all__.append(...)`.
+pub fn dunder_all_names(text: &str) -> Option<Vec<String>> {
+    let caps = regex_dunder_all().captures(text)?;
+    let rhs = caps.get(1).map_or("", |m| m.as_str());
+    Some(
+        regex_string_name()
+            .captures_iter(rhs)
+            .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
+            .collect(),
+    )
+}
+
+/// Name defined by a `def`/`class` statement, if the statement is one.
+pub fn definition_name(text: &str) -> Option<(&str, &'static str)> {
+    let (rest, kind) = if let Some(rest) = text.strip_prefix("def ") {
+        (rest, "def")
+    } else if let Some(rest) = text.strip_prefix("async def ") {
+        (rest, "def")
+    } else if let Some(rest) = text.strip_prefix("class ") {
+        (rest, "class")
+    } else {
+        return None;
+    };
+    let rest = rest.trim_start();
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    (end > 0).then(|| (&rest[..end], kind))
+}
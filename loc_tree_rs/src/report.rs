@@ -0,0 +1,108 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Name -> every `(file, line)` it appears at, ordered by name.
+pub type Locations = BTreeMap<String, Vec<(String, usize)>>;
+
+/// Name seen on one side of a frontend/backend pair (command, channel, event,
+/// route) without a counterpart on the other side.
+#[derive(Clone)]
+pub struct CommandGap {
+    /// Normalised name the frontend and backend are matched on.
+    pub name: String,
+    /// Spellings seen in the source (invoke strings, handler names or
+    /// `generate_handler!` paths).
+    pub raw: Vec<String>,
+    pub locations: Vec<(String, usize)>,
+}
+
+impl CommandGap {
+    pub fn new(name: &str, raw: Option<&BTreeSet<String>>, locations: &[(String, usize)]) -> Self {
+        CommandGap {
+            name: name.to_string(),
+            raw: raw.map(|r| r.iter().cloned().collect()).unwrap_or_default(),
+            locations: locations.to_vec(),
+        }
+    }
+
+    /// `raw → normalised` when the source spelling differs from the match key.
+    pub fn label(&self) -> String {
+        if self.raw.is_empty() || self.raw == [self.name.clone()] {
+            self.name.clone()
+        } else {
+            format!("{} → {}", self.raw.join(", "), self.name)
+        }
+    }
+}
+
+/// Names in `from` that `against` does not have.
+pub fn gaps(from: &Locations, against: &Locations) -> Vec<CommandGap> {
+    from.iter()
+        .filter(|(name, _)| !against.contains_key(*name))
+        .map(|(name, locs)| CommandGap::new(name, None, locs))
+        .collect()
+}
+
+/// `file:line, file:line` for the CLI report.
+pub fn locations_list(locations: &[(String, usize)]) -> String {
+    locations
+        .iter()
+        .map(|(f, l)| format!("{}:{}", f, l))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Settings shared by the sections of one root in the HTML report.
+pub struct HtmlContext {
+    /// Base URL of the `--serve` open server, when it runs.
+    pub open_base: Option<String>,
+    /// Rows shown in long tables (`--limit`).
+    pub limit: usize,
+}
+
+impl HtmlContext {
+    /// `file:line`, linked to the open server when there is one.
+    pub fn link(&self, file: &str, line: usize) -> String {
+        if let Some(base) = &self.open_base {
+            let href = format!("{}/open?f={}&l={}", base, url_encode_component(file), line);
+            format!("<a href=\"{}\">{}:{}</a>", href, escape_html(file), line)
+        } else {
+            format!("{}:{}", escape_html(file), line)
+        }
+    }
+
+    pub fn gap_list(&self, gaps: &[CommandGap]) -> String {
+        if gaps.is_empty() {
+            return "<span class=\"muted\">None</span>".to_string();
+        }
+        gaps.iter()
+            .map(|g| {
+                let locs: Vec<String> = g.locations.iter().map(|(f, l)| self.link(f, *l)).collect();
+                format!(
+                    "<code>{}</code> ({})",
+                    escape_html(&g.label()),
+                    locs.join("; ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+pub fn escape_html(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn url_encode_component(input: &str) -> String {
+    input
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
@@ -0,0 +1,822 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::json;
+use syn::visit::{self, Visit};
+
+use crate::analyzer::offset_to_line;
+use crate::non_code::NonCode;
+use crate::report::{escape_html, locations_list, CommandGap, HtmlContext};
+use crate::types::{FileAnalysis, RouteRef};
+
+const HTTP_METHODS: &[&str] = &["get", "post", "put", "delete", "patch", "head", "options"];
+
+/// Method of a route or request that accepts any verb.
+pub const ANY_METHOD: &str = "ANY";
+
+/// Router prefixes are collected per file but not joined with the routes of
+/// the router they mount, which usually lives in another module.
+const UNAPPLIED_PREFIXES: &str =
+    "Router prefixes not applied (routes mounted under them are matched without the prefix)";
+
+/// Normalises a route or request path: drops query and fragment, trailing
+/// slashes and empty segments, and turns every parameter form (`:id`, `{id}`,
+/// `{id:int}`, `<int:id>`, `${id}`) into `{}` and catch-alls into `*`.
+pub fn normalize_path(raw: &str) -> String {
+    let path = raw.split(['?', '#']).next().unwrap_or("");
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|seg| {
+            if seg.starts_with('*') || seg.starts_with("{*") || seg.starts_with("<path:") {
+                "*"
+            } else if seg.starts_with(':')
+                || (seg.starts_with('{') && seg.ends_with('}'))
+                || (seg.starts_with('<') && seg.ends_with('>'))
+                || seg.contains("{}")
+            {
+                "{}"
+            } else {
+                seg
+            }
+        })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Whether a frontend request can be served by a backend route.
+pub fn route_serves(route: &RouteRef, call: &RouteRef) -> bool {
+    let methods_match =
+        route.method == ANY_METHOD || call.method == ANY_METHOD || route.method == call.method;
+    methods_match && paths_match(&route.path, &call.path)
+}
+
+fn paths_match(route: &str, call: &str) -> bool {
+    let route: Vec<&str> = route.split('/').filter(|s| !s.is_empty()).collect();
+    let call: Vec<&str> = call.split('/').filter(|s| !s.is_empty()).collect();
+    for (idx, seg) in route.iter().enumerate() {
+        if *seg == "*" {
+            return true;
+        }
+        match call.get(idx) {
+            Some(c) if c == seg || *seg == "{}" || *c == "{}" => continue,
+            _ => return false,
+        }
+    }
+    route.len() == call.len()
+}
+
+fn route_ref(method: &str, raw: &str, content: &str, offset: usize) -> RouteRef {
+    RouteRef {
+        method: method.to_uppercase(),
+        path: normalize_path(raw),
+        raw: raw.to_string(),
+        line: offset_to_line(content, offset),
+    }
+}
+
+/// Text of a call's argument list: `text` starts right after the `(`.
+pub fn call_args(text: &str) -> &str {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (idx, ch) in text.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => quote = Some(ch),
+            '(' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => return &text[..idx],
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Reads a JS string literal starting at its opening quote; template
+/// substitutions become `{}`. Returns the text and the offset after it.
+pub fn js_string(content: &str, start: usize) -> Option<(String, usize)> {
+    let quote = content[start..].chars().next()?;
+    let mut out = String::new();
+    let mut chars = content[start + 1..].char_indices();
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            c if c == quote => return Some((out, start + 1 + idx + 1)),
+            '$' if quote == '`' && content[start + 1 + idx..].starts_with("${") => {
+                let mut depth = 0usize;
+                for (_, inner) in chars.by_ref() {
+                    match inner {
+                        '{' => depth += 1,
+                        '}' if depth <= 1 => break,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                }
+                out.push_str("{}");
+            }
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+/// Request path a frontend URL targets, or `None` for other hosts. A leading
+/// substitution (`${API_BASE}/users`) is taken to be the API base.
+fn request_path(url: &str) -> Option<String> {
+    if url.starts_with("http://") || url.starts_with("https://") || url.starts_with("//") {
+        return None;
+    }
+    let url = url.strip_prefix("{}").unwrap_or(url);
+    if url.is_empty() || url.starts_with("{}") {
+        return None;
+    }
+    Some(if url.starts_with('/') {
+        url.to_string()
+    } else {
+        format!("/{}", url)
+    })
+}
+
+fn regex_js_method_option() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\bmethod\s*:\s*["'`]([A-Za-z]+)["'`]"#).unwrap())
+}
+
+fn regex_fetch() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"(?:^|[^\w$.])fetch\s*\(\s*["'`]"#).unwrap())
+}
+
+fn regex_member_request() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(?:^|[^\w$.])([A-Za-z_$][\w$]*)\s*\.\s*(get|post|put|delete|patch|head|options|all)\s*(?:<[^()]*?>)?\s*\(\s*["'`]"#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_axios_call() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"(?:^|[^\w$.])axios\s*\(\s*["'`]"#).unwrap())
+}
+
+fn regex_axios_instance() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"([A-Za-z_$][\w$]*)\s*=\s*axios\s*\.\s*create\s*\("#).unwrap())
+}
+
+fn regex_express_app() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"([A-Za-z_$][\w$]*)\s*=\s*(?:express\s*\(\s*\)|(?:express\s*\.\s*)?Router\s*\()"#,
+        )
+        .unwrap()
+    })
+}
+
+/// Express route definitions and frontend requests (`fetch`, `axios` and
+/// `axios.create()` instances) in a JS/TS module. Calls in comments and string
+/// literals are skipped.
+pub fn js_routes(content: &str) -> (Vec<RouteRef>, Vec<RouteRef>) {
+    let mut defs = Vec::new();
+    let mut calls = Vec::new();
+    let non_code = NonCode::js(content);
+    // The call's `(` must be code; its URL argument is a string literal.
+    let in_code = |quote_at: usize| !non_code.contains(quote_at - 1);
+
+    let servers = js_servers(content, &non_code);
+    let mut clients: HashSet<String> = regex_axios_instance()
+        .captures_iter(content)
+        .filter(|c| c.get(0).is_some_and(|m| !non_code.contains(m.start())))
+        .map(|c| c[1].to_string())
+        .collect();
+    clients.insert("axios".to_string());
+
+    for caps in regex_member_request().captures_iter(content) {
+        let receiver = &caps[1];
+        let method = &caps[2];
+        let Some(whole) = caps.get(0) else {
+            continue;
+        };
+        let quote_at = whole.end() - 1;
+        if !in_code(quote_at) {
+            continue;
+        }
+        let Some((url, _)) = js_string(content, quote_at) else {
+            continue;
+        };
+        if servers.contains(receiver) && url.starts_with('/') {
+            let method = if method == "all" { ANY_METHOD } else { method };
+            defs.push(route_ref(method, &url, content, quote_at));
+        } else if clients.contains(receiver) && method != "all" {
+            if let Some(path) = request_path(&url) {
+                calls.push(route_ref(method, &path, content, quote_at));
+            }
+        }
+    }
+
+    for re in [regex_fetch(), regex_axios_call()] {
+        for m in re.find_iter(content) {
+            let quote_at = m.end() - 1;
+            if !in_code(quote_at) {
+                continue;
+            }
+            let Some((url, after)) = js_string(content, quote_at) else {
+                continue;
+            };
+            let Some(path) = request_path(&url) else {
+                continue;
+            };
+            let args = call_args(&content[after..]);
+            let method = regex_js_method_option()
+                .captures(args)
+                .map(|c| c[1].to_string())
+                .unwrap_or_else(|| "GET".to_string());
+            calls.push(route_ref(&method, &path, content, quote_at));
+        }
+    }
+    calls.sort_by_key(|c| c.line);
+    (defs, calls)
+}
+
+/// Express apps and routers declared in the module (`app` and `router` as
+/// well when it uses express).
+fn js_servers(content: &str, non_code: &NonCode) -> HashSet<String> {
+    let mut servers: HashSet<String> = regex_express_app()
+        .captures_iter(content)
+        .filter(|c| c.get(0).is_some_and(|m| !non_code.contains(m.start())))
+        .map(|c| c[1].to_string())
+        .collect();
+    if content.contains("express") {
+        servers.extend(["app".to_string(), "router".to_string()]);
+    }
+    servers
+}
+
+fn regex_express_use() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?:^|[^\w$.])([A-Za-z_$][\w$]*)\s*\.\s*use\s*\(\s*["'`]"#).unwrap()
+    })
+}
+
+/// Prefixes Express routers are mounted under (`app.use("/api", router)`).
+pub fn js_route_mounts(content: &str) -> Vec<RouteRef> {
+    let non_code = NonCode::js(content);
+    let servers = js_servers(content, &non_code);
+    let mut mounts = Vec::new();
+    for caps in regex_express_use().captures_iter(content) {
+        let Some(whole) = caps.get(0) else {
+            continue;
+        };
+        let quote_at = whole.end() - 1;
+        if !servers.contains(&caps[1]) || non_code.contains(quote_at - 1) {
+            continue;
+        }
+        if let Some((prefix, _)) = js_string(content, quote_at) {
+            if prefix.starts_with('/') && normalize_path(&prefix) != "/" {
+                mounts.push(route_ref(ANY_METHOD, &prefix, content, quote_at));
+            }
+        }
+    }
+    mounts
+}
+
+fn regex_rust_route() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\.route\s*\(\s*"([^"]+)"\s*,"#).unwrap())
+}
+
+fn regex_rust_method_call() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\b(get|post|put|delete|patch|head|options|any)\s*\("#).unwrap())
+}
+
+fn regex_actix_attr() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"#\s*\[\s*(?:actix_web\s*::\s*)?(get|post|put|delete|patch|head|options|route)\s*\(\s*"([^"]+)""#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_actix_resource() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\bweb\s*::\s*resource\s*\(\s*"([^"]+)""#).unwrap())
+}
+
+/// axum `.route("/x", get(h).post(h))`, actix `#[get("/x")]`,
+/// `.route("/x", web::get().to(h))` and `web::resource("/x")`. Taken from the
+/// syntax tree when the file parsed; otherwise the text is scanned, skipping
+/// comments and string literals.
+pub fn rust_routes(content: &str, tree: Option<&syn::File>) -> Vec<RouteRef> {
+    if let Some(tree) = tree {
+        let mut visitor = RustRoutes(Vec::new());
+        visitor.visit_file(tree);
+        visitor.0.sort_by_key(|d| d.line);
+        return visitor.0;
+    }
+    let non_code = NonCode::rust(content);
+    let in_code =
+        |caps: &regex::Captures| caps.get(0).is_some_and(|m| !non_code.contains(m.start()));
+    let mut defs = Vec::new();
+    for caps in regex_rust_route().captures_iter(content) {
+        let (Some(path), Some(whole)) = (caps.get(1), caps.get(0)) else {
+            continue;
+        };
+        if !in_code(&caps) {
+            continue;
+        }
+        let args = call_args(&content[whole.end()..]);
+        let mut methods: Vec<String> = regex_rust_method_call()
+            .captures_iter(args)
+            .map(|c| c[1].to_string())
+            .collect();
+        methods.dedup();
+        if methods.is_empty() || methods.iter().any(|m| m == "any") {
+            methods = vec![ANY_METHOD.to_string()];
+        }
+        for method in methods {
+            defs.push(route_ref(&method, path.as_str(), content, path.start()));
+        }
+    }
+    for caps in regex_actix_attr().captures_iter(content) {
+        let Some(path) = caps.get(2).filter(|_| in_code(&caps)) else {
+            continue;
+        };
+        let method = if &caps[1] == "route" {
+            ANY_METHOD
+        } else {
+            &caps[1]
+        };
+        defs.push(route_ref(method, path.as_str(), content, path.start()));
+    }
+    for caps in regex_actix_resource().captures_iter(content) {
+        if let Some(path) = caps.get(1).filter(|_| in_code(&caps)) {
+            defs.push(route_ref(ANY_METHOD, path.as_str(), content, path.start()));
+        }
+    }
+    defs.sort_by_key(|d| d.line);
+    defs
+}
+
+fn regex_rust_mount() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?:\.nest(?:_service)?|\bweb\s*::\s*scope)\s*\(\s*"([^"]+)""#).unwrap()
+    })
+}
+
+/// Prefixes axum routers are nested under (`.nest("/api", router)`) and actix
+/// scopes (`web::scope("/api")`), read like `rust_routes`.
+pub fn rust_route_mounts(content: &str, tree: Option<&syn::File>) -> Vec<RouteRef> {
+    if let Some(tree) = tree {
+        let mut visitor = RustMounts(Vec::new());
+        visitor.visit_file(tree);
+        visitor.0.sort_by_key(|d| d.line);
+        return visitor.0;
+    }
+    let non_code = NonCode::rust(content);
+    regex_rust_mount()
+        .captures_iter(content)
+        .filter(|c| c.get(0).is_some_and(|m| !non_code.contains(m.start())))
+        .filter_map(|c| c.get(1))
+        .map(|path| route_ref(ANY_METHOD, path.as_str(), content, path.start()))
+        .collect()
+}
+
+struct RustMounts(Vec<RouteRef>);
+
+impl<'ast> Visit<'ast> for RustMounts {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "nest" || call.method == "nest_service" {
+            if let Some(path) = call.args.first().and_then(str_literal) {
+                self.0.push(rust_route_ref(ANY_METHOD, path));
+            }
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = call.func.as_ref() {
+            let segments: Vec<String> = func
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            if segments.ends_with(&["web".to_string(), "scope".to_string()]) {
+                if let Some(path) = call.args.first().and_then(str_literal) {
+                    self.0.push(rust_route_ref(ANY_METHOD, path));
+                }
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+}
+
+fn str_literal(expr: &syn::Expr) -> Option<&syn::LitStr> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Some(lit),
+        _ => None,
+    }
+}
+
+fn rust_route_ref(method: &str, lit: &syn::LitStr) -> RouteRef {
+    let raw = lit.value();
+    RouteRef {
+        method: method.to_uppercase(),
+        path: normalize_path(&raw),
+        raw,
+        line: lit.span().start().line,
+    }
+}
+
+struct RustRoutes(Vec<RouteRef>);
+
+impl<'ast> Visit<'ast> for RustRoutes {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "route" && call.args.len() == 2 {
+            if let Some(path) = str_literal(&call.args[0]) {
+                let mut methods = MethodNames(Vec::new());
+                methods.visit_expr(&call.args[1]);
+                let mut names = methods.0;
+                if names.is_empty() || names.iter().any(|m| m == "any") {
+                    names = vec![ANY_METHOD.to_string()];
+                }
+                for method in names {
+                    self.0.push(rust_route_ref(&method, path));
+                }
+            }
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = call.func.as_ref() {
+            let segments: Vec<String> = func
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            if segments.ends_with(&["web".to_string(), "resource".to_string()]) {
+                if let Some(path) = call.args.first().and_then(str_literal) {
+                    self.0.push(rust_route_ref(ANY_METHOD, path));
+                }
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_attribute(&mut self, attr: &'ast syn::Attribute) {
+        let segments: Vec<String> = attr
+            .path()
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        let method = match segments.as_slice() {
+            [method] => Some(method),
+            [krate, method] if krate == "actix_web" => Some(method),
+            _ => None,
+        };
+        let method = method.filter(|m| HTTP_METHODS.contains(&m.as_str()) || *m == "route");
+        if let Some(method) = method {
+            let path = attr.parse_args_with(|input: syn::parse::ParseStream| {
+                let path: syn::LitStr = input.parse()?;
+                input.parse::<proc_macro2::TokenStream>()?;
+                Ok(path)
+            });
+            if let Ok(path) = path {
+                let method = if method == "route" {
+                    ANY_METHOD
+                } else {
+                    method
+                };
+                self.0.push(rust_route_ref(method, &path));
+            }
+        }
+    }
+}
+
+/// HTTP methods named in an axum/actix method router (`get(h).post(h)`,
+/// `web::get().to(h)`), in order, without repeats.
+struct MethodNames(Vec<String>);
+
+impl MethodNames {
+    fn push(&mut self, name: String) {
+        if (HTTP_METHODS.contains(&name.as_str()) || name == "any") && !self.0.contains(&name) {
+            self.0.push(name);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for MethodNames {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = call.func.as_ref() {
+            if let Some(last) = func.path.segments.last() {
+                self.push(last.ident.to_string());
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        // Receiver first so `get(a).post(b)` keeps source order.
+        self.visit_expr(&call.receiver);
+        self.push(call.method.to_string());
+        for arg in &call.args {
+            self.visit_expr(arg);
+        }
+    }
+}
+
+fn regex_py_decorator() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(?m)^[ \t]*@([A-Za-z_][\w]*)\.(get|post|put|delete|patch|head|options|route|api_route)\s*\(\s*["']([^"']*)["']([^\n]*)"#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_py_router_prefix() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"([A-Za-z_][\w]*)\s*=\s*(?:APIRouter|Blueprint)\s*\([^)]*?\b(?:prefix|url_prefix)\s*=\s*["']([^"']+)["']"#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_py_methods() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"methods\s*=\s*[\[(]([^\])]*)[\])]"#).unwrap())
+}
+
+/// FastAPI (`@app.get`, `@router.post`, `@app.api_route`) and Flask
+/// (`@app.route(..., methods=[...])`, `@bp.get`) route decorators, with
+/// `APIRouter(prefix=...)`/`Blueprint(url_prefix=...)` prefixes declared in
+/// the same module. Comments, strings and docstrings are skipped.
+pub fn py_routes(content: &str) -> Vec<RouteRef> {
+    let non_code = NonCode::python(content);
+    let prefixes: HashMap<String, String> = regex_py_router_prefix()
+        .captures_iter(content)
+        .filter(|c| c.get(0).is_some_and(|m| !non_code.contains(m.start())))
+        .map(|c| (c[1].to_string(), c[2].trim_end_matches('/').to_string()))
+        .collect();
+    let mut defs = Vec::new();
+    for caps in regex_py_decorator().captures_iter(content) {
+        let (Some(path), Some(whole)) = (caps.get(3), caps.get(0)) else {
+            continue;
+        };
+        // The match starts at the line start; the `@` is what must be code.
+        let at = whole.start() + whole.as_str().find('@').unwrap_or(0);
+        if non_code.contains(at) {
+            continue;
+        }
+        let full = format!(
+            "{}{}",
+            prefixes.get(&caps[1]).map(String::as_str).unwrap_or(""),
+            path.as_str()
+        );
+        let methods: Vec<String> = match &caps[2] {
+            "route" | "api_route" => match regex_py_methods().captures(&caps[4]) {
+                Some(list) => list[1]
+                    .split(',')
+                    .map(|m| m.trim().trim_matches(['"', '\'']).to_lowercase())
+                    .filter(|m| HTTP_METHODS.contains(&m.as_str()))
+                    .collect(),
+                None if &caps[2] == "route" => vec!["get".to_string()],
+                None => vec![ANY_METHOD.to_string()],
+            },
+            method => vec![method.to_string()],
+        };
+        for method in methods {
+            defs.push(route_ref(&method, &full, content, path.start()));
+        }
+    }
+    defs
+}
+
+fn regex_py_mount() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"\.(?:include_router|register_blueprint)\s*\([^)]*?\b(?:prefix|url_prefix)\s*=\s*["']([^"']+)["']"#,
+        )
+        .unwrap()
+    })
+}
+
+/// Prefixes FastAPI routers and Flask blueprints are mounted under
+/// (`include_router(router, prefix=...)`, `register_blueprint(bp, url_prefix=...)`).
+pub fn py_route_mounts(content: &str) -> Vec<RouteRef> {
+    let non_code = NonCode::python(content);
+    regex_py_mount()
+        .captures_iter(content)
+        .filter(|c| c.get(0).is_some_and(|m| !non_code.contains(m.start())))
+        .filter_map(|c| c.get(1))
+        .map(|prefix| route_ref(ANY_METHOD, prefix.as_str(), content, prefix.start()))
+        .collect()
+}
+
+/// Backend routes and frontend requests gathered across a project, each with
+/// the file it appears in.
+#[derive(Default)]
+pub struct RouteCoverage {
+    backend: Vec<(String, RouteRef)>,
+    frontend: Vec<(String, RouteRef)>,
+    /// Router prefixes; routes mounted under them are matched without it.
+    mounts: Vec<(String, RouteRef)>,
+}
+
+/// Routes grouped by method and normalised path: a representative, the raw
+/// spellings that differ from the normalised path, and every location.
+type RouteGroup = (BTreeSet<String>, Vec<(String, usize)>);
+
+impl RouteCoverage {
+    pub fn add(&mut self, analysis: &FileAnalysis) {
+        for route in &analysis.route_defs {
+            self.backend.push((analysis.path.clone(), route.clone()));
+        }
+        for request in &analysis.route_calls {
+            self.frontend.push((analysis.path.clone(), request.clone()));
+        }
+        for mount in &analysis.route_mounts {
+            self.mounts.push((analysis.path.clone(), mount.clone()));
+        }
+    }
+
+    /// Frontend requests without a backend route, named `METHOD /path`. A
+    /// project without backend routes talks to something outside the tree.
+    fn missing(&self) -> Vec<CommandGap> {
+        if self.backend.is_empty() {
+            return Vec::new();
+        }
+        group_routes(&self.frontend)
+            .iter()
+            .filter(|(call, _)| !self.backend.iter().any(|(_, r)| route_serves(r, call)))
+            .map(|(call, gap)| route_gap(call, gap))
+            .collect()
+    }
+
+    /// Backend routes nothing requests; only meaningful when there are requests.
+    fn unused(&self) -> Vec<CommandGap> {
+        if self.frontend.is_empty() {
+            return Vec::new();
+        }
+        group_routes(&self.backend)
+            .iter()
+            .filter(|(route, _)| {
+                !self
+                    .frontend
+                    .iter()
+                    .any(|(_, call)| route_serves(route, call))
+            })
+            .map(|(route, gap)| route_gap(route, gap))
+            .collect()
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "backend": group_routes(&self.backend).iter().map(|(r, g)| route_json(r, g)).collect::<Vec<_>>(),
+            "frontend": group_routes(&self.frontend).iter().map(|(r, g)| route_json(r, g)).collect::<Vec<_>>(),
+            "missingRoutes": self.missing().iter().map(route_gap_json).collect::<Vec<_>>(),
+            "unusedRoutes": self.unused().iter().map(route_gap_json).collect::<Vec<_>>(),
+            "unappliedPrefixes": group_routes(&self.mounts).iter().map(|(r, (_, locations))| json!({"prefix": r.path, "locations": locations})).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self) {
+        let (missing, unused) = (self.missing(), self.unused());
+        if missing.is_empty() && unused.is_empty() {
+            return;
+        }
+        println!("\nHTTP route coverage:");
+        for (label, gaps) in [
+            ("Requests without a backend route", &missing),
+            ("Routes never requested by the frontend", &unused),
+        ] {
+            if gaps.is_empty() {
+                continue;
+            }
+            println!("  {}:", label);
+            for g in gaps {
+                println!("    - {} ({})", g.label(), locations_list(&g.locations));
+            }
+        }
+        let mounts = group_routes(&self.mounts);
+        if !mounts.is_empty() {
+            println!("  {}:", UNAPPLIED_PREFIXES);
+            for (mount, (_, locations)) in &mounts {
+                println!("    - {} ({})", mount.path, locations_list(locations));
+            }
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        let (missing, unused) = (self.missing(), self.unused());
+        if missing.is_empty() && unused.is_empty() {
+            return;
+        }
+        out.push_str("<h3>HTTP route coverage</h3>");
+        out.push_str(&format!(
+            "<table><tr><th>Requests without a backend route</th><th>Routes never requested</th></tr><tr><td>{}</td><td>{}</td></tr></table>",
+            ctx.gap_list(&missing),
+            ctx.gap_list(&unused)
+        ));
+        let mounts: Vec<String> = group_routes(&self.mounts)
+            .iter()
+            .map(|(mount, (_, locations))| {
+                let locs: Vec<String> = locations.iter().map(|(f, l)| ctx.link(f, *l)).collect();
+                format!(
+                    "<code>{}</code> ({})",
+                    escape_html(&mount.path),
+                    locs.join("; ")
+                )
+            })
+            .collect();
+        if !mounts.is_empty() {
+            out.push_str(&format!(
+                "<p class=\"muted\">{}: {}</p>",
+                UNAPPLIED_PREFIXES,
+                mounts.join(" · ")
+            ));
+        }
+    }
+}
+
+fn group_routes(refs: &[(String, RouteRef)]) -> Vec<(RouteRef, RouteGroup)> {
+    let mut groups: Vec<(RouteRef, RouteGroup)> = Vec::new();
+    for (file, route) in refs {
+        let idx = match groups
+            .iter()
+            .position(|(r, _)| r.method == route.method && r.path == route.path)
+        {
+            Some(idx) => idx,
+            None => {
+                groups.push((route.clone(), Default::default()));
+                groups.len() - 1
+            }
+        };
+        let (raw, locations) = &mut groups[idx].1;
+        if normalize_path(&route.raw) != route.raw {
+            raw.insert(route.raw.clone());
+        }
+        locations.push((file.clone(), route.line));
+    }
+    groups.sort_by(|a, b| (&a.0.path, &a.0.method).cmp(&(&b.0.path, &b.0.method)));
+    groups
+}
+
+fn route_gap(route: &RouteRef, (raw, locations): &RouteGroup) -> CommandGap {
+    CommandGap::new(
+        &format!("{} {}", route.method, route.path),
+        Some(raw),
+        locations,
+    )
+}
+
+fn route_json(route: &RouteRef, (raw, locations): &RouteGroup) -> serde_json::Value {
+    json!({
+        "method": route.method,
+        "path": route.path,
+        "rawPaths": raw,
+        "locations": locations,
+    })
+}
+
+fn route_gap_json(gap: &CommandGap) -> serde_json::Value {
+    let (method, path) = gap.name.split_once(' ').unwrap_or(("", &gap.name));
+    json!({
+        "method": method,
+        "path": path,
+        "rawPaths": gap.raw,
+        "locations": gap.locations,
+    })
+}
@@ -0,0 +1,483 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Item, Token, UseTree, Visibility};
+
+use crate::tauri::{command_rename, command_rename_all, handler_arg};
+use crate::types::{CommandArg, CommandRef, ExportCategory, ExportSymbol, ImportSymbol};
+
+/// One top-level branch of a `use` item (`use {a::b, c::d};` yields two).
+pub struct RustUse {
+    /// The branch rendered back to source form, e.g. `a::{b::{c, d}, e}`.
+    pub source: String,
+    pub symbols: Vec<ImportSymbol>,
+    pub line: usize,
+    pub column: usize,
+    /// `pub use` reachable from the crate's public surface.
+    pub reexported: bool,
+}
+
+pub struct RustModDecl {
+    pub name: String,
+    pub line: usize,
+    pub column: usize,
+    pub test_only: bool,
+}
+
+pub struct RustSyntax {
+    pub uses: Vec<RustUse>,
+    pub mods: Vec<RustModDecl>,
+    pub exports: Vec<ExportSymbol>,
+    /// `#[tauri::command]` functions of any visibility, sync or async, under
+    /// the name they are invoked by (`rename = "..."` applied).
+    pub commands: Vec<CommandRef>,
+    /// The parsed file, for scanners that walk expressions.
+    pub tree: syn::File,
+}
+
+/// Parses a Rust file with `syn`. Returns the parse error when the file does not
+/// parse so the caller can fall back to the regex scanner and report why.
+pub fn parse_rust(content: &str) -> syn::Result<RustSyntax> {
+    let file = syn::parse_file(content)?;
+    let mut out = RustSyntax {
+        uses: Vec::new(),
+        mods: Vec::new(),
+        exports: Vec::new(),
+        commands: Vec::new(),
+        tree: syn::File {
+            shebang: None,
+            attrs: Vec::new(),
+            items: Vec::new(),
+        },
+    };
+    walk_items(&file.items, true, false, &mut out);
+    out.tree = file;
+    Ok(out)
+}
+
+/// `public_path` is false once we are inside a private inline module; items
+/// under `#[cfg(test)]` are skipped except for `mod` declarations, which are
+/// kept (flagged) so test files stay in the module graph.
+fn walk_items(items: &[Item], public_path: bool, test_only: bool, out: &mut RustSyntax) {
+    for item in items {
+        let item_test = test_only || is_cfg_test(item_attrs(item));
+        match item {
+            Item::Use(item_use) => {
+                if item_test {
+                    continue;
+                }
+                let reexported = public_path && is_public(&item_use.vis);
+                let start = item_use.use_token.span.start();
+                let leading = if item_use.leading_colon.is_some() {
+                    "::"
+                } else {
+                    ""
+                };
+                let branches: Vec<&UseTree> = match &item_use.tree {
+                    UseTree::Group(group) => group.items.iter().collect(),
+                    tree => vec![tree],
+                };
+                for tree in branches {
+                    let mut symbols = Vec::new();
+                    flatten_use_tree(tree, &mut Vec::new(), &mut symbols);
+                    out.uses.push(RustUse {
+                        source: format!("{}{}", leading, render_use_tree(tree)),
+                        symbols,
+                        line: start.line,
+                        column: start.column + 1,
+                        reexported,
+                    });
+                }
+            }
+            Item::Mod(module) => {
+                match &module.content {
+                    None => out.mods.push(RustModDecl {
+                        name: module.ident.to_string(),
+                        line: module.ident.span().start().line,
+                        column: module.ident.span().start().column + 1,
+                        test_only: item_test,
+                    }),
+                    Some((_, inner)) => {
+                        walk_items(inner, public_path && is_public(&module.vis), item_test, out)
+                    }
+                }
+                if !item_test && public_path && is_public(&module.vis) {
+                    push_decl(out, &module.ident, "decl", ExportCategory::Module);
+                }
+            }
+            Item::Macro(item_macro) => {
+                // `#[macro_export]` places the macro at the crate root regardless
+                // of the module it is defined in.
+                let exported = item_macro
+                    .attrs
+                    .iter()
+                    .any(|a| a.path().is_ident("macro_export"));
+                if exported && !item_test && item_macro.mac.path.is_ident("macro_rules") {
+                    if let Some(ident) = &item_macro.ident {
+                        push_decl(out, ident, "macro", ExportCategory::Value);
+                    }
+                }
+            }
+            _ => {
+                if let Item::Fn(func) = item {
+                    if let Some(attr) = tauri_command_attr(&func.attrs).filter(|_| !item_test) {
+                        let args = match &attr.meta {
+                            syn::Meta::List(list) => list.tokens.to_string(),
+                            _ => String::new(),
+                        };
+                        let ident = func.sig.ident.to_string();
+                        let rename = command_rename(&args);
+                        out.commands.push(CommandRef {
+                            name: rename.clone().unwrap_or_else(|| ident.clone()),
+                            line: func.sig.ident.span().start().line,
+                            rust_name: rename.map(|_| ident),
+                            args: Some(command_args(&func.sig, command_rename_all(&args))),
+                        });
+                    }
+                }
+                if item_test || !public_path {
+                    continue;
+                }
+                if let Some((vis, ident, category)) = declared_item(item) {
+                    if is_public(vis) {
+                        push_decl(out, ident, "decl", category);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn push_decl(out: &mut RustSyntax, ident: &syn::Ident, kind: &str, category: ExportCategory) {
+    let start = ident.span().start();
+    out.exports.push(ExportSymbol {
+        name: ident.to_string(),
+        kind: kind.to_string(),
+        category,
+        line: start.line,
+        column: start.column + 1,
+    });
+}
+
+fn declared_item(item: &Item) -> Option<(&Visibility, &syn::Ident, ExportCategory)> {
+    use ExportCategory::{Type, Value};
+    let (vis, ident, category) = match item {
+        Item::Fn(i) => (&i.vis, &i.sig.ident, Value),
+        Item::Struct(i) => (&i.vis, &i.ident, Type),
+        Item::Enum(i) => (&i.vis, &i.ident, Type),
+        Item::Trait(i) => (&i.vis, &i.ident, Type),
+        Item::TraitAlias(i) => (&i.vis, &i.ident, Type),
+        Item::Type(i) => (&i.vis, &i.ident, Type),
+        Item::Union(i) => (&i.vis, &i.ident, Type),
+        Item::Const(i) => (&i.vis, &i.ident, Value),
+        Item::Static(i) => (&i.vis, &i.ident, Value),
+        _ => return None,
+    };
+    Some((vis, ident, category))
+}
+
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Const(i) => &i.attrs,
+        Item::Enum(i) => &i.attrs,
+        Item::ExternCrate(i) => &i.attrs,
+        Item::Fn(i) => &i.attrs,
+        Item::ForeignMod(i) => &i.attrs,
+        Item::Impl(i) => &i.attrs,
+        Item::Macro(i) => &i.attrs,
+        Item::Mod(i) => &i.attrs,
+        Item::Static(i) => &i.attrs,
+        Item::Struct(i) => &i.attrs,
+        Item::Trait(i) => &i.attrs,
+        Item::TraitAlias(i) => &i.attrs,
+        Item::Type(i) => &i.attrs,
+        Item::Union(i) => &i.attrs,
+        Item::Use(i) => &i.attrs,
+        _ => &[],
+    }
+}
+
+/// `#[tauri::command]`, `#[tauri::command(...)]` or `#[command]` after `use tauri::command`.
+fn tauri_command_attr(attrs: &[Attribute]) -> Option<&Attribute> {
+    attrs.iter().find(|attr| {
+        let segments: Vec<String> = attr
+            .path()
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        segments == ["tauri", "command"] || segments == ["command"]
+    })
+}
+
+/// Payload keys a command reads; injected parameters (`State`, `AppHandle`,
+/// ...) are left out.
+fn command_args(sig: &syn::Signature, rename_all: Option<String>) -> Vec<CommandArg> {
+    sig.inputs
+        .iter()
+        .filter_map(|input| {
+            let syn::FnArg::Typed(typed) = input else {
+                return None;
+            };
+            let syn::Pat::Ident(pat) = typed.pat.as_ref() else {
+                return None;
+            };
+            let type_name = type_last_ident(&typed.ty).unwrap_or_default();
+            handler_arg(&pat.ident.to_string(), &type_name, rename_all.as_deref())
+        })
+        .collect()
+}
+
+fn type_last_ident(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        syn::Type::Reference(reference) => type_last_ident(&reference.elem),
+        syn::Type::Paren(paren) => type_last_ident(&paren.elem),
+        syn::Type::Group(group) => type_last_ident(&group.elem),
+        _ => None,
+    }
+}
+
+/// Only a bare `pub` is public API; `pub(crate)`, `pub(super)` and
+/// `pub(in path)` are restricted.
+fn is_public(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+/// `#[cfg(test)]`, `#[cfg(all(test, ...))]`; `not(test)` is not test-only.
+fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if !attr.path().is_ident("cfg") {
+            return false;
+        }
+        let mut predicates = Vec::new();
+        let parsed = attr.parse_nested_meta(|meta| {
+            predicates.push(cfg_requires_test(&meta)?);
+            Ok(())
+        });
+        parsed.is_ok() && predicates == [true]
+    })
+}
+
+/// Whether a cfg predicate can only hold when compiling tests: `test` itself,
+/// an `all(...)` containing such a predicate, or an `any(...)` made only of them.
+fn cfg_requires_test(meta: &ParseNestedMeta) -> syn::Result<bool> {
+    let combinator = ["all", "any", "not"]
+        .into_iter()
+        .find(|name| meta.path.is_ident(name));
+    let mut children = Vec::new();
+    if combinator.is_some() {
+        meta.parse_nested_meta(|nested| {
+            children.push(cfg_requires_test(&nested)?);
+            Ok(())
+        })?;
+    } else if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Lit>()?;
+    }
+    Ok(match combinator {
+        Some("all") => children.contains(&true),
+        Some("any") => !children.is_empty() && !children.contains(&false),
+        Some(_) => false,
+        None => meta.path.is_ident("test"),
+    })
+}
+
+/// Collects every leaf of a use-tree as a full path (`a::b::c`), with its
+/// `as` alias; globs become `a::b::*` namespace symbols.
+fn flatten_use_tree(tree: &UseTree, prefix: &mut Vec<String>, out: &mut Vec<ImportSymbol>) {
+    let leaf = |prefix: &[String], ident: &syn::Ident| {
+        if ident == "self" {
+            prefix.join("::")
+        } else {
+            let mut parts = prefix.to_vec();
+            parts.push(ident.to_string());
+            parts.join("::")
+        }
+    };
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            flatten_use_tree(&path.tree, prefix, out);
+            prefix.pop();
+        }
+        UseTree::Name(name) => out.push(ImportSymbol {
+            name: leaf(prefix, &name.ident),
+            alias: None,
+            is_default: false,
+            is_namespace: false,
+            type_only: false,
+        }),
+        UseTree::Rename(rename) => out.push(ImportSymbol {
+            name: leaf(prefix, &rename.ident),
+            alias: Some(rename.rename.to_string()),
+            is_default: false,
+            is_namespace: false,
+            type_only: false,
+        }),
+        UseTree::Glob(_) => {
+            let mut parts = prefix.clone();
+            parts.push("*".to_string());
+            out.push(ImportSymbol {
+                name: parts.join("::"),
+                alias: None,
+                is_default: false,
+                is_namespace: true,
+                type_only: false,
+            });
+        }
+        UseTree::Group(group) => {
+            for item in &group.items {
+                flatten_use_tree(item, prefix, out);
+            }
+        }
+    }
+}
+
+fn render_use_tree(tree: &UseTree) -> String {
+    match tree {
+        UseTree::Path(path) => format!("{}::{}", path.ident, render_use_tree(&path.tree)),
+        UseTree::Name(name) => name.ident.to_string(),
+        UseTree::Rename(rename) => format!("{} as {}", rename.ident, rename.rename),
+        UseTree::Glob(_) => "*".to_string(),
+        UseTree::Group(group) => format!(
+            "{{{}}}",
+            group
+                .items
+                .iter()
+                .map(render_use_tree)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Name a leaf is bound under in the importing module.
+pub fn bound_name(symbol: &ImportSymbol) -> Option<String> {
+    if symbol.is_namespace {
+        return None;
+    }
+    match &symbol.alias {
+        Some(alias) if alias == "_" => None,
+        Some(alias) => Some(alias.clone()),
+        None => symbol.name.rsplit("::").next().map(str::to_string),
+    }
+}
+
+/// Splits a reexporting `use` into the named part, as `(original, exported)`
+/// pairs, and its glob paths.
+pub fn reexport_parts(item: &RustUse) -> (Vec<(String, String)>, Vec<String>) {
+    let names = item
+        .symbols
+        .iter()
+        .filter_map(|s| {
+            let original = s.name.rsplit("::").next().unwrap_or(&s.name);
+            bound_name(s).map(|name| (original.to_string(), name))
+        })
+        .collect();
+    let globs = item
+        .symbols
+        .iter()
+        .filter(|s| s.is_namespace)
+        .map(|s| s.name.clone())
+        .collect();
+    (names, globs)
+}
+
+/// First segments of qualified paths outside `use` items (`serde_json::json!`,
+/// `::tokio::spawn`, `#[derive(serde::Serialize)]`), with their lines. Names the
+/// file binds itself (`use` leaves and aliases, `mod`s) are left out. Works on
+/// tokens, so it sees into macro bodies and attributes, skips comments and
+/// strings, and needs no successful `syn` parse.
+pub fn path_roots(content: &str) -> Vec<(String, usize)> {
+    let Ok(stream) = content.parse::<TokenStream>() else {
+        return Vec::new();
+    };
+    let mut bound = HashSet::new();
+    let mut roots = Vec::new();
+    scan_paths(stream, &mut bound, &mut roots);
+    roots.retain(|(name, _)| !bound.contains(name));
+    roots
+}
+
+fn is_path_sep(tokens: &[TokenTree], i: usize) -> bool {
+    matches!(
+        (tokens.get(i), tokens.get(i + 1)),
+        (Some(TokenTree::Punct(a)), Some(TokenTree::Punct(b)))
+            if a.as_char() == ':' && a.spacing() == Spacing::Joint && b.as_char() == ':'
+    )
+}
+
+fn scan_paths(stream: TokenStream, bound: &mut HashSet<String>, roots: &mut Vec<(String, usize)>) {
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Group(group) => scan_paths(group.stream(), bound, roots),
+            TokenTree::Ident(ident) if ident == "use" => {
+                let end = tokens[i..]
+                    .iter()
+                    .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ';'))
+                    .map_or(tokens.len(), |n| i + n);
+                use_bindings(&tokens[i + 1..end], None, bound);
+                i = end;
+            }
+            TokenTree::Ident(ident) if ident == "mod" => {
+                if let Some(TokenTree::Ident(name)) = tokens.get(i + 1) {
+                    bound.insert(name.to_string());
+                }
+            }
+            TokenTree::Ident(ident) if is_path_sep(&tokens, i + 1) => {
+                // `a::b`, `<T>::b` and `x.b::<T>()` continue a path or call a
+                // method; `::tokio` after a punctuation mark starts one.
+                let continues = if i >= 2 && is_path_sep(&tokens, i - 2) {
+                    i >= 3 && !matches!(&tokens[i - 3], TokenTree::Punct(p) if p.as_char() != '>')
+                } else {
+                    i >= 1
+                        && matches!(&tokens[i - 1], TokenTree::Punct(p) if matches!(p.as_char(), '.' | '$'))
+                };
+                let turbofish =
+                    matches!(tokens.get(i + 3), Some(TokenTree::Punct(p)) if p.as_char() == '<');
+                if !continues && !turbofish {
+                    roots.push((ident.to_string(), ident.span().start().line));
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// Names a `use` tree binds: leaves, `as` aliases and the parent of `self`.
+fn use_bindings(tokens: &[TokenTree], parent: Option<&str>, bound: &mut HashSet<String>) {
+    let mut last: Option<String> = None;
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Ident(ident) if ident == "as" => {
+                if let Some(TokenTree::Ident(alias)) = tokens.get(i + 1) {
+                    bound.insert(alias.to_string());
+                }
+                i += 1;
+            }
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                let renamed = matches!(tokens.get(i + 1), Some(TokenTree::Ident(n)) if n == "as");
+                if !is_path_sep(tokens, i + 1) && !renamed {
+                    match (name.as_str(), parent) {
+                        ("self", Some(parent)) => bound.insert(parent.to_string()),
+                        _ => bound.insert(name.clone()),
+                    };
+                }
+                last = Some(name);
+            }
+            TokenTree::Group(group) => {
+                let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+                use_bindings(&inner, last.as_deref(), bound);
+            }
+            TokenTree::Punct(p) if p.as_char() == ',' => last = None,
+            _ => {}
+        }
+        i += 1;
+    }
+}
@@ -0,0 +1,1056 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::json;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::Token;
+
+use crate::analyzer::offset_to_line;
+use crate::non_code::NonCode;
+use crate::report::{escape_html, gaps, locations_list, CommandGap, HtmlContext, Locations};
+use crate::types::{CommandArg, CommandRef, EventRef, FileAnalysis};
+
+/// Invoke functions recognised when `invokeWrappers` is not set in the config.
+pub const DEFAULT_INVOKE_WRAPPERS: &[&str] = &["safeInvoke", "invokeSnake", "invoke"];
+
+/// Finds frontend command calls made through the configured invoke wrappers:
+/// `invoke("cmd")`, `api.invoke('cmd')`, `invoke<T>(\`cmd\`)`, and calls whose
+/// first argument is a string constant declared in the same module.
+pub struct InvokeMatcher {
+    call: Regex,
+}
+
+impl InvokeMatcher {
+    pub fn new(wrappers: &[String]) -> Self {
+        let names: Vec<String> = wrappers
+            .iter()
+            .map(|w| w.trim())
+            .filter(|w| !w.is_empty())
+            .map(regex::escape)
+            .collect();
+        let names = if names.is_empty() {
+            DEFAULT_INVOKE_WRAPPERS
+                .iter()
+                .map(|w| regex::escape(w))
+                .collect()
+        } else {
+            names
+        };
+        // The wrapper name must not be the tail of a longer identifier; a `.`
+        // before it (member call) is fine. Optional generic arguments follow.
+        let pattern = format!(
+            r#"(?:^|[^\w$])(?:{})\s*(?:<[^()]*?>)?\s*\(\s*(?:["'`]([^"'`$]+)["'`]|([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)?)\s*[,)])"#,
+            names.join("|")
+        );
+        InvokeMatcher {
+            call: Regex::new(&pattern).expect("escaped invoke wrapper names form a valid regex"),
+        }
+    }
+
+    pub fn command_calls(&self, content: &str) -> Vec<CommandRef> {
+        let constants = string_constants(content);
+        let mut calls = Vec::new();
+        for caps in self.call.captures_iter(content) {
+            // `after` points just past the command argument: the closing quote
+            // of a literal, or the `,`/`)` the identifier branch consumed.
+            let (name, offset, after) = if let Some(literal) = caps.get(1) {
+                (
+                    literal.as_str().to_string(),
+                    literal.start(),
+                    literal.end() + 1,
+                )
+            } else if let Some(ident) = caps.get(2) {
+                match constants.get(ident.as_str()) {
+                    Some(value) => {
+                        let call_end = caps.get(0).map_or(ident.end(), |m| m.end());
+                        (value.clone(), ident.start(), call_end - 1)
+                    }
+                    None => continue,
+                }
+            } else {
+                continue;
+            };
+            calls.push(CommandRef {
+                name,
+                line: offset_to_line(content, offset),
+                rust_name: None,
+                args: invoke_payload(content, after),
+            });
+        }
+        calls
+    }
+}
+
+fn regex_string_const() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(?m)(?:^|[^\w$.])(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*["'`]([^"'`$]+)["'`]"#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_object_const() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?m)(?:^|[^\w$.])const\s+([A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*\{([^{}]*)\}"#)
+            .unwrap()
+    })
+}
+
+fn regex_object_entry() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"["']?([A-Za-z_$][\w$]*)["']?\s*:\s*["'`]([^"'`$]+)["'`]"#).unwrap()
+    })
+}
+
+/// String constants declared in a module: `const CMD = "get_user"` and the
+/// members of flat object literals (`const Commands = { get: "get_user" }`
+/// yields `Commands.get`).
+pub fn string_constants(content: &str) -> HashMap<String, String> {
+    let mut constants = HashMap::new();
+    for caps in regex_string_const().captures_iter(content) {
+        constants.insert(caps[1].to_string(), caps[2].to_string());
+    }
+    for caps in regex_object_const().captures_iter(content) {
+        for entry in regex_object_entry().captures_iter(&caps[2]) {
+            constants.insert(format!("{}.{}", &caps[1], &entry[1]), entry[2].to_string());
+        }
+    }
+    constants
+}
+
+fn regex_generate_handler() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"generate_handler\s*!\s*[\[({]"#).unwrap())
+}
+
+/// Paths listed in `tauri::generate_handler![...]`, each with the line it
+/// appears on. Macro calls are taken from the syntax tree when the file parsed;
+/// otherwise the text is scanned, skipping comments and string literals.
+pub fn handler_registrations(content: &str, tree: Option<&syn::File>) -> Vec<CommandRef> {
+    if let Some(tree) = tree {
+        let mut visitor = Registrations(Vec::new());
+        visitor.visit_file(tree);
+        return visitor.0;
+    }
+    let non_code = NonCode::rust(content);
+    let mut out = Vec::new();
+    for m in regex_generate_handler().find_iter(content) {
+        if non_code.contains(m.start()) {
+            continue;
+        }
+        let body_start = m.end();
+        let mut depth = 1usize;
+        let mut idx = body_start;
+        let bytes = content.as_bytes();
+        let mut current = String::new();
+        let mut current_start = None;
+        let mut flush = |current: &mut String, start: &mut Option<usize>| {
+            let path: String = current.chars().filter(|c| !c.is_whitespace()).collect();
+            if let Some(offset) = start.take() {
+                if !path.is_empty() {
+                    out.push(CommandRef {
+                        name: path,
+                        line: offset_to_line(content, offset),
+                        rust_name: None,
+                        args: None,
+                    });
+                }
+            }
+            current.clear();
+        };
+        while idx < bytes.len() && depth > 0 {
+            let rest = &content[idx..];
+            if rest.starts_with("//") {
+                idx += rest.find('\n').unwrap_or(rest.len());
+                continue;
+            }
+            if rest.starts_with("/*") {
+                idx += rest.find("*/").map_or(rest.len(), |end| end + 2);
+                continue;
+            }
+            let ch = rest.chars().next().unwrap_or(' ');
+            match ch {
+                '[' | '(' | '{' => depth += 1,
+                ']' | ')' | '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        flush(&mut current, &mut current_start);
+                    }
+                }
+                ',' if depth == 1 => flush(&mut current, &mut current_start),
+                _ => {
+                    if !ch.is_whitespace() && current_start.is_none() {
+                        current_start = Some(idx);
+                    }
+                    current.push(ch);
+                }
+            }
+            idx += ch.len_utf8();
+        }
+    }
+    out
+}
+
+struct Registrations(Vec<CommandRef>);
+
+impl<'ast> Visit<'ast> for Registrations {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|seg| seg.ident == "generate_handler")
+        {
+            if let Ok(paths) =
+                mac.parse_body_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+            {
+                self.0.extend(paths.iter().map(|path| {
+                    CommandRef {
+                        name: path
+                            .segments
+                            .iter()
+                            .map(|seg| seg.ident.to_string())
+                            .collect::<Vec<_>>()
+                            .join("::"),
+                        line: path.segments[0].ident.span().start().line,
+                        rust_name: None,
+                        args: None,
+                    }
+                }));
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Command name a `generate_handler!` entry registers: its last path segment.
+pub fn registered_name(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Name used to match frontend calls against backend handlers. Tauri looks
+/// commands up by their exact name (`rename_all` only renames argument keys),
+/// so `invoke("loadUser")` does not reach `fn load_user`; `plugin:name|command`
+/// keeps its plugin scope.
+pub fn normalize_command(raw: &str) -> String {
+    match raw.trim().strip_prefix("plugin:") {
+        Some(rest) => match rest.split_once('|') {
+            Some((plugin, command)) => plugin_command(plugin, command),
+            None => format!("plugin:{}", rest),
+        },
+        None => raw.trim().to_string(),
+    }
+}
+
+/// Name a command defined in plugin `plugin` is invoked under.
+pub fn plugin_command(plugin: &str, command: &str) -> String {
+    format!("plugin:{}|{}", plugin.trim(), command.trim())
+}
+
+/// Maps files to the Tauri plugin crate they belong to: the nearest
+/// `Cargo.toml` whose package is named `tauri-plugin-<name>`.
+pub struct PluginCrates {
+    root: PathBuf,
+    cache: HashMap<PathBuf, Option<String>>,
+}
+
+impl PluginCrates {
+    pub fn new(root: &Path) -> Self {
+        PluginCrates {
+            root: root.to_path_buf(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Plugin name for a root-relative file, if it lives in a plugin crate.
+    pub fn plugin_for(&mut self, file: &str) -> Option<String> {
+        let mut dir = self.root.join(file).parent()?.to_path_buf();
+        let mut visited = Vec::new();
+        let found = loop {
+            if let Some(hit) = self.cache.get(&dir) {
+                break hit.clone();
+            }
+            visited.push(dir.clone());
+            let manifest = dir.join("Cargo.toml");
+            if manifest.is_file() {
+                break cargo_package_name(&manifest)
+                    .and_then(|name| name.strip_prefix("tauri-plugin-").map(str::to_string));
+            }
+            if dir == self.root || !dir.starts_with(&self.root) {
+                break None;
+            }
+            match dir.parent() {
+                Some(parent) => dir = parent.to_path_buf(),
+                None => break None,
+            }
+        };
+        for seen in visited {
+            self.cache.insert(seen, found.clone());
+        }
+        found
+    }
+}
+
+fn cargo_package_name(manifest: &Path) -> Option<String> {
+    let raw = fs::read_to_string(manifest).ok()?;
+    let mut in_package = false;
+    for line in raw.lines() {
+        let trimmed = line.split('#').next().unwrap_or("").trim();
+        if trimmed.starts_with('[') {
+            in_package = trimmed == "[package]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once('=') {
+            if key.trim() == "name" {
+                return Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    None
+}
+
+fn regex_command_rename() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\brename\s*=\s*"([^"]+)""#).unwrap())
+}
+
+/// Explicit `rename = "..."` in `#[tauri::command(...)]` arguments.
+pub fn command_rename(args: &str) -> Option<String> {
+    regex_command_rename()
+        .captures(args)
+        .map(|caps| caps[1].to_string())
+}
+
+/// Plugin scope of a normalised command name (`plugin:<name>|...`).
+pub fn plugin_of(command: &str) -> Option<&str> {
+    command
+        .strip_prefix("plugin:")
+        .and_then(|rest| rest.split_once('|'))
+        .map(|(plugin, _)| plugin)
+}
+
+/// Keys of the payload object following the command argument of an invoke
+/// call (`start` is just past that argument). No payload yields an empty list;
+/// anything but an object literal with plain keys is unknown.
+fn invoke_payload(content: &str, start: usize) -> Option<Vec<CommandArg>> {
+    let rest = content.get(start..)?.trim_start();
+    if rest.starts_with(')') {
+        return Some(Vec::new());
+    }
+    let rest = rest.strip_prefix(',')?.trim_start();
+    if rest.starts_with(')') {
+        return Some(Vec::new());
+    }
+    object_keys(rest.strip_prefix('{')?)
+}
+
+/// Top-level keys of an object literal body (the text after its `{`).
+fn object_keys(body: &str) -> Option<Vec<CommandArg>> {
+    let mut keys = Vec::new();
+    let mut entry = String::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut chars = body.chars();
+    while let Some(ch) = chars.next() {
+        entry.push(ch);
+        if let Some(q) = quote {
+            if ch == '\\' {
+                entry.extend(chars.next());
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => quote = Some(ch),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            '}' if depth > 0 => depth -= 1,
+            '}' | ',' if depth == 0 => {
+                entry.pop();
+                if let Some(key) = entry_key(&entry)? {
+                    keys.push(CommandArg {
+                        name: key,
+                        optional: false,
+                    });
+                }
+                entry.clear();
+                if ch == '}' {
+                    return Some(keys);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Key of one object literal entry: `key: v`, `"key": v`, shorthand `key` or
+/// method `key() {}`. Spreads and computed keys make the payload unknown.
+fn entry_key(entry: &str) -> Option<Option<String>> {
+    let entry = entry.trim();
+    if entry.is_empty() {
+        return Some(None);
+    }
+    if entry.starts_with("...") || entry.starts_with('[') {
+        return None;
+    }
+    if let Some(q) = entry.chars().next().filter(|c| matches!(c, '"' | '\'')) {
+        let rest = &entry[1..];
+        return rest.find(q).map(|end| Some(rest[..end].to_string()));
+    }
+    let end = entry
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(entry.len());
+    (end > 0).then(|| Some(entry[..end].to_string()))
+}
+
+/// Parameter types Tauri injects into a command instead of reading them from
+/// the invoke payload.
+const INJECTED_PARAM_TYPES: &[&str] = &[
+    "State",
+    "AppHandle",
+    "Window",
+    "WebviewWindow",
+    "Webview",
+    "Request",
+];
+
+fn regex_command_rename_all() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\brename_all\s*=\s*"([^"]+)""#).unwrap())
+}
+
+/// `rename_all = "..."` in `#[tauri::command(...)]` arguments.
+pub fn command_rename_all(args: &str) -> Option<String> {
+    regex_command_rename_all()
+        .captures(args)
+        .map(|caps| caps[1].to_string())
+}
+
+/// Payload key a handler parameter is read from, or `None` for injected
+/// parameters. `type_name` is the last path segment of the parameter type.
+/// Tauri expects camelCase keys unless the command sets
+/// `rename_all = "snake_case"`.
+pub fn handler_arg(name: &str, type_name: &str, rename_all: Option<&str>) -> Option<CommandArg> {
+    if INJECTED_PARAM_TYPES.contains(&type_name) {
+        return None;
+    }
+    let name = match rename_all {
+        Some("snake_case") => name.to_string(),
+        _ => camel_case(name),
+    };
+    Some(CommandArg {
+        name,
+        optional: type_name == "Option",
+    })
+}
+
+fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for (idx, ch) in name.chars().enumerate() {
+        if ch == '_' && idx > 0 {
+            upper = true;
+        } else if upper {
+            out.extend(ch.to_uppercase());
+            upper = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Handler parameters from the source after a command's name (regex fallback
+/// for files `syn` cannot parse).
+pub fn handler_args_from_source(after_name: &str, rename_all: Option<&str>) -> Vec<CommandArg> {
+    let Some(open) = after_name.find('(') else {
+        return Vec::new();
+    };
+    let mut params = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for ch in after_name[open + 1..].chars() {
+        match ch {
+            '(' | '<' | '[' => depth += 1,
+            ')' if depth == 0 => {
+                params.push(std::mem::take(&mut current));
+                break;
+            }
+            ')' | '>' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                params.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    params
+        .iter()
+        .filter_map(|param| {
+            let (pat, ty) = param.split_once(':')?;
+            let name = pat.trim().trim_start_matches("mut ").trim();
+            let ty = ty.trim().trim_start_matches('&');
+            let ty = ty.split('<').next().unwrap_or(ty);
+            let type_name = ty
+                .rsplit("::")
+                .next()
+                .unwrap_or(ty)
+                .trim_start_matches("mut ")
+                .trim();
+            handler_arg(name, type_name, rename_all)
+        })
+        .collect()
+}
+
+fn regex_rust_emit() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"\.(?:(?:emit|emit_all|emit_str|emit_filter)\s*\(|(?:emit_to|emit_str_to)\s*\(\s*[^,;]+?\s*,)\s*"([^"]+)""#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_rust_listen() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"\.(?:listen|listen_any|listen_global|once|once_any|once_global)\s*\(\s*"([^"]+)""#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_ts_event_call() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(?:^|[^\w$])(listen|once|emit)\s*(?:<[^()]*?>)?\s*\(\s*(?:["'`]([^"'`$]+)["'`]|([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)?)\s*[,)])"#,
+        )
+        .unwrap()
+    })
+}
+
+fn regex_ts_emit_to() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(?:^|[^\w$])(emitTo)\s*(?:<[^()]*?>)?\s*\(\s*(?:["'`][^"'`]*["'`]|[^,()]+?)\s*,\s*(?:["'`]([^"'`$]+)["'`]|([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)?)\s*[,)])"#,
+        )
+        .unwrap()
+    })
+}
+
+const RUST_EMITS: &[&str] = &["emit", "emit_all", "emit_str", "emit_filter"];
+/// Emitters whose first argument is the target, not the event.
+const RUST_EMITS_TO: &[&str] = &["emit_to", "emit_str_to"];
+const RUST_LISTENS: &[&str] = &[
+    "listen",
+    "listen_any",
+    "listen_global",
+    "once",
+    "once_any",
+    "once_global",
+];
+
+/// Events a Rust file emits (`emit`, `emit_to`, `emit_all`, ...) and listens
+/// to (`listen`, `once`, ...). Only string-literal event names are recognised.
+/// Method calls come from the syntax tree when the file parsed; otherwise the
+/// text is scanned, skipping comments and string literals.
+pub fn rust_events(content: &str, tree: Option<&syn::File>) -> (Vec<EventRef>, Vec<EventRef>) {
+    if !content.contains("tauri") {
+        return (Vec::new(), Vec::new());
+    }
+    if let Some(tree) = tree {
+        let mut visitor = Events::default();
+        visitor.visit_file(tree);
+        return (visitor.emits, visitor.listens);
+    }
+    let non_code = NonCode::rust(content);
+    let collect = |re: &Regex| {
+        re.captures_iter(content)
+            .filter(|caps| caps.get(0).is_some_and(|m| !non_code.contains(m.start())))
+            .filter_map(|caps| caps.get(1))
+            .map(|m| EventRef {
+                name: m.as_str().to_string(),
+                line: offset_to_line(content, m.start()),
+            })
+            .collect()
+    };
+    (collect(regex_rust_emit()), collect(regex_rust_listen()))
+}
+
+#[derive(Default)]
+struct Events {
+    emits: Vec<EventRef>,
+    listens: Vec<EventRef>,
+}
+
+impl<'ast> Visit<'ast> for Events {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = call.method.to_string();
+        let method = method.as_str();
+        let (position, list) = if RUST_EMITS.contains(&method) {
+            (0, &mut self.emits)
+        } else if RUST_EMITS_TO.contains(&method) {
+            (1, &mut self.emits)
+        } else if RUST_LISTENS.contains(&method) {
+            (0, &mut self.listens)
+        } else {
+            return visit::visit_expr_method_call(self, call);
+        };
+        if let Some(syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(name),
+            ..
+        })) = call.args.iter().nth(position)
+        {
+            list.push(EventRef {
+                name: name.value(),
+                line: name.span().start().line,
+            });
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+}
+
+/// Events a frontend module emits (`emit`, `emitTo`) and listens to (`listen`,
+/// `once`), as plain or member calls. Only modules importing `@tauri-apps/` are
+/// scanned so Node-style emitters do not count.
+pub fn ts_events(content: &str) -> (Vec<EventRef>, Vec<EventRef>) {
+    if !content.contains("@tauri-apps/") {
+        return (Vec::new(), Vec::new());
+    }
+    let constants = string_constants(content);
+    let mut emits = Vec::new();
+    let mut listens = Vec::new();
+    let calls = regex_ts_event_call()
+        .captures_iter(content)
+        .chain(regex_ts_emit_to().captures_iter(content));
+    for caps in calls {
+        let (name, offset) = if let Some(literal) = caps.get(2) {
+            (literal.as_str().to_string(), literal.start())
+        } else if let Some(ident) = caps.get(3) {
+            match constants.get(ident.as_str()) {
+                Some(value) => (value.clone(), ident.start()),
+                None => continue,
+            }
+        } else {
+            continue;
+        };
+        let event = EventRef {
+            name,
+            line: offset_to_line(content, offset),
+        };
+        match &caps[1] {
+            "emit" | "emitTo" => emits.push(event),
+            _ => listens.push(event),
+        }
+    }
+    emits.sort_by_key(|e| e.line);
+    (emits, listens)
+}
+
+/// Frontend invokes, backend handlers and `generate_handler!` registrations
+/// gathered across a project, keyed by normalised command name.
+pub struct CommandCoverage {
+    pub frontend: Locations,
+    pub backend: Locations,
+    registrations: Locations,
+    // Normalised command name -> spellings seen on each side.
+    frontend_raw: HashMap<String, BTreeSet<String>>,
+    backend_raw: HashMap<String, BTreeSet<String>>,
+    registration_raw: HashMap<String, BTreeSet<String>>,
+    // Invoked name -> names `generate_handler!` must list (they differ on `rename`).
+    registration_keys: HashMap<String, BTreeSet<String>>,
+    pub plugins: PluginCrates,
+    workspace_plugins: HashSet<String>,
+    payloads: Vec<(String, String, usize, Vec<CommandArg>)>,
+    signatures: HashMap<String, (String, usize, Vec<CommandArg>)>,
+}
+
+/// Frontend invoke whose payload keys disagree with the handler's parameters.
+struct ArgMismatch {
+    command: String,
+    file: String,
+    line: usize,
+    /// Required parameters the payload does not pass.
+    missing: Vec<String>,
+    /// Payload keys the handler does not take.
+    extra: Vec<String>,
+    handler: (String, usize),
+}
+
+impl CommandCoverage {
+    pub fn new(root: &Path) -> Self {
+        CommandCoverage {
+            frontend: Locations::new(),
+            backend: Locations::new(),
+            registrations: Locations::new(),
+            frontend_raw: HashMap::new(),
+            backend_raw: HashMap::new(),
+            registration_raw: HashMap::new(),
+            registration_keys: HashMap::new(),
+            plugins: PluginCrates::new(root),
+            workspace_plugins: HashSet::new(),
+            payloads: Vec::new(),
+            signatures: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, analysis: &FileAnalysis) {
+        for call in &analysis.command_calls {
+            let key = normalize_command(&call.name);
+            if let Some(args) = &call.args {
+                self.payloads
+                    .push((key.clone(), analysis.path.clone(), call.line, args.clone()));
+            }
+            self.frontend_raw
+                .entry(key.clone())
+                .or_default()
+                .insert(call.name.clone());
+            self.frontend
+                .entry(key)
+                .or_default()
+                .push((analysis.path.clone(), call.line));
+        }
+        // Commands defined in a `tauri-plugin-*` crate are invoked as
+        // `plugin:<name>|<command>`.
+        let plugin =
+            if analysis.command_handlers.is_empty() && analysis.command_registrations.is_empty() {
+                None
+            } else {
+                self.plugins.plugin_for(&analysis.path)
+            };
+        if let Some(plugin) = &plugin {
+            self.workspace_plugins.insert(plugin.clone());
+        }
+        let backend_key = |name: &str| match &plugin {
+            Some(plugin) => plugin_command(plugin, name),
+            None => normalize_command(name),
+        };
+        for handler in &analysis.command_handlers {
+            let key = backend_key(&handler.name);
+            let rust_name = handler.rust_name.as_deref().unwrap_or(&handler.name);
+            if let Some(args) = &handler.args {
+                self.signatures
+                    .entry(key.clone())
+                    .or_insert_with(|| (analysis.path.clone(), handler.line, args.clone()));
+            }
+            self.registration_keys
+                .entry(key.clone())
+                .or_default()
+                .insert(backend_key(rust_name));
+            self.backend_raw
+                .entry(key.clone())
+                .or_default()
+                .insert(rust_name.to_string());
+            self.backend
+                .entry(key)
+                .or_default()
+                .push((analysis.path.clone(), handler.line));
+        }
+        for reg in &analysis.command_registrations {
+            let key = backend_key(registered_name(&reg.name));
+            self.registration_raw
+                .entry(key.clone())
+                .or_default()
+                .insert(reg.name.clone());
+            self.registrations
+                .entry(key)
+                .or_default()
+                .push((analysis.path.clone(), reg.line));
+        }
+    }
+
+    /// Invokes without a handler. Plugins pulled from crates.io have no
+    /// source to check against.
+    fn missing_handlers(&self) -> Vec<CommandGap> {
+        self.frontend
+            .iter()
+            .filter(|(name, _)| !self.backend.contains_key(*name))
+            .filter(|(name, _)| {
+                plugin_of(name).is_none_or(|plugin| self.workspace_plugins.contains(plugin))
+            })
+            .map(|(name, locs)| CommandGap::new(name, self.frontend_raw.get(name), locs))
+            .collect()
+    }
+
+    fn unused_handlers(&self) -> Vec<CommandGap> {
+        self.backend
+            .iter()
+            .filter(|(name, _)| !self.frontend.contains_key(*name))
+            .map(|(name, locs)| CommandGap::new(name, self.backend_raw.get(name), locs))
+            .collect()
+    }
+
+    /// Handlers `generate_handler!` does not list; the check only makes sense
+    /// once the app lists its handlers.
+    fn unregistered_handlers(&self) -> Vec<CommandGap> {
+        if self.registrations.is_empty() {
+            return Vec::new();
+        }
+        self.backend
+            .iter()
+            .filter(|(name, _)| {
+                self.registration_keys
+                    .get(*name)
+                    .is_none_or(|keys| !keys.iter().any(|k| self.registrations.contains_key(k)))
+            })
+            .map(|(name, locs)| CommandGap::new(name, self.backend_raw.get(name), locs))
+            .collect()
+    }
+
+    fn unknown_registrations(&self) -> Vec<CommandGap> {
+        let registrable: HashSet<&String> = self.registration_keys.values().flatten().collect();
+        self.registrations
+            .iter()
+            .filter(|(name, _)| !registrable.contains(name))
+            .map(|(name, locs)| CommandGap::new(name, self.registration_raw.get(name), locs))
+            .collect()
+    }
+
+    fn arg_mismatches(&self) -> Vec<ArgMismatch> {
+        let mut mismatches = Vec::new();
+        for (command, file, line, payload) in &self.payloads {
+            let Some((handler_file, handler_line, params)) = self.signatures.get(command) else {
+                continue;
+            };
+            let sent: HashSet<&str> = payload.iter().map(|a| a.name.as_str()).collect();
+            let taken: HashSet<&str> = params.iter().map(|a| a.name.as_str()).collect();
+            let missing: Vec<String> = params
+                .iter()
+                .filter(|p| !p.optional && !sent.contains(p.name.as_str()))
+                .map(|p| p.name.clone())
+                .collect();
+            let extra: Vec<String> = payload
+                .iter()
+                .filter(|a| !taken.contains(a.name.as_str()))
+                .map(|a| a.name.clone())
+                .collect();
+            if !missing.is_empty() || !extra.is_empty() {
+                mismatches.push(ArgMismatch {
+                    command: command.clone(),
+                    file: file.clone(),
+                    line: *line,
+                    missing,
+                    extra,
+                    handler: (handler_file.clone(), *handler_line),
+                });
+            }
+        }
+        mismatches.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        mismatches
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "frontend": self.frontend.iter().map(|(k,v)| json!({"name": k, "rawNames": self.frontend_raw.get(k), "locations": v})).collect::<Vec<_>>(),
+            "backend": self.backend.iter().map(|(k,v)| json!({"name": k, "rawNames": self.backend_raw.get(k), "locations": v})).collect::<Vec<_>>(),
+            "missingHandlers": self.missing_handlers().iter().map(|g| json!({"name": g.name, "rawNames": g.raw, "locations": g.locations})).collect::<Vec<_>>(),
+            "unusedHandlers": self.unused_handlers().iter().map(|g| json!({"name": g.name, "rawNames": g.raw, "locations": g.locations})).collect::<Vec<_>>(),
+            "registered": self.registrations.iter().map(|(k,v)| json!({"name": k, "paths": self.registration_raw.get(k), "locations": v})).collect::<Vec<_>>(),
+            "unregisteredHandlers": self.unregistered_handlers().iter().map(|g| json!({"name": g.name, "rawNames": g.raw, "locations": g.locations})).collect::<Vec<_>>(),
+            "registeredWithoutHandler": self.unknown_registrations().iter().map(|g| json!({"name": g.name, "paths": g.raw, "locations": g.locations})).collect::<Vec<_>>(),
+            "argumentMismatches": self.arg_mismatches().iter().map(|m| json!({
+                "name": m.command,
+                "file": m.file,
+                "line": m.line,
+                "missing": m.missing,
+                "extra": m.extra,
+                "handler": m.handler,
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self) {
+        let missing_handlers = self.missing_handlers();
+        let unused_handlers = self.unused_handlers();
+        let unregistered_handlers = self.unregistered_handlers();
+        let unknown_registrations = self.unknown_registrations();
+        let arg_mismatches = self.arg_mismatches();
+        if missing_handlers.is_empty()
+            && unused_handlers.is_empty()
+            && unregistered_handlers.is_empty()
+            && unknown_registrations.is_empty()
+            && arg_mismatches.is_empty()
+        {
+            return;
+        }
+        let labels = |gaps: &[CommandGap]| {
+            gaps.iter()
+                .map(CommandGap::label)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        println!("\nTauri command coverage:");
+        if !missing_handlers.is_empty() {
+            println!(
+                "  Missing handlers (frontend calls without backend): {}",
+                labels(&missing_handlers)
+            );
+        }
+        if !unused_handlers.is_empty() {
+            println!(
+                "  Unused handlers (backend not called by FE): {}",
+                labels(&unused_handlers)
+            );
+        }
+        if !unregistered_handlers.is_empty() {
+            println!(
+                "  Handlers missing from generate_handler!: {}",
+                labels(&unregistered_handlers)
+            );
+        }
+        if !arg_mismatches.is_empty() {
+            println!("  Invoke payload vs command parameters:");
+            for m in &arg_mismatches {
+                println!(
+                    "    - {} at {}:{} (handler {}:{}){}{}",
+                    m.command,
+                    m.file,
+                    m.line,
+                    m.handler.0,
+                    m.handler.1,
+                    if m.missing.is_empty() {
+                        String::new()
+                    } else {
+                        format!("; missing {}", m.missing.join(", "))
+                    },
+                    if m.extra.is_empty() {
+                        String::new()
+                    } else {
+                        format!("; extra {}", m.extra.join(", "))
+                    }
+                );
+            }
+        }
+        if !unknown_registrations.is_empty() {
+            println!(
+                "  Registered without a #[tauri::command] definition: {}",
+                labels(&unknown_registrations)
+            );
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        let missing_handlers = self.missing_handlers();
+        let unused_handlers = self.unused_handlers();
+        out.push_str("<h3>Tauri command coverage</h3>");
+        if missing_handlers.is_empty() && unused_handlers.is_empty() {
+            out.push_str("<p class=\"muted\">All frontend calls have matching handlers.</p>");
+        } else {
+            out.push_str(&format!(
+                "<table><tr><th>Missing handlers (FE→BE)</th><th>Handlers unused by FE</th></tr><tr><td>{}</td><td>{}</td></tr></table>",
+                ctx.gap_list(&missing_handlers),
+                ctx.gap_list(&unused_handlers)
+            ));
+        }
+        let arg_mismatches = self.arg_mismatches();
+        if !arg_mismatches.is_empty() {
+            out.push_str("<table><tr><th>Invoke</th><th>Handler</th><th>Missing args</th><th>Extra args</th></tr>");
+            for m in &arg_mismatches {
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code> ({})</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&m.command),
+                    ctx.link(&m.file, m.line),
+                    ctx.link(&m.handler.0, m.handler.1),
+                    escape_html(&m.missing.join(", ")),
+                    escape_html(&m.extra.join(", "))
+                ));
+            }
+            out.push_str("</table>");
+        }
+        let unregistered_handlers = self.unregistered_handlers();
+        let unknown_registrations = self.unknown_registrations();
+        if !unregistered_handlers.is_empty() || !unknown_registrations.is_empty() {
+            out.push_str(&format!(
+                "<table><tr><th>Not registered in generate_handler!</th><th>Registered without a handler</th></tr><tr><td>{}</td><td>{}</td></tr></table>",
+                ctx.gap_list(&unregistered_handlers),
+                ctx.gap_list(&unknown_registrations)
+            ));
+        }
+    }
+}
+
+/// Events emitted and listened to across a project.
+#[derive(Default)]
+pub struct EventCoverage {
+    emitted: Locations,
+    listened: Locations,
+}
+
+impl EventCoverage {
+    pub fn add(&mut self, analysis: &FileAnalysis) {
+        for event in &analysis.event_emits {
+            self.emitted
+                .entry(event.name.clone())
+                .or_default()
+                .push((analysis.path.clone(), event.line));
+        }
+        for event in &analysis.event_listens {
+            self.listened
+                .entry(event.name.clone())
+                .or_default()
+                .push((analysis.path.clone(), event.line));
+        }
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        json!({
+            "emitted": self.emitted.iter().map(|(k,v)| json!({"name": k, "locations": v})).collect::<Vec<_>>(),
+            "listened": self.listened.iter().map(|(k,v)| json!({"name": k, "locations": v})).collect::<Vec<_>>(),
+            "emittedNotListened": gaps(&self.emitted, &self.listened).iter().map(|g| json!({"name": g.name, "locations": g.locations})).collect::<Vec<_>>(),
+            "listenedNotEmitted": gaps(&self.listened, &self.emitted).iter().map(|g| json!({"name": g.name, "locations": g.locations})).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn print(&self) {
+        let unheard = gaps(&self.emitted, &self.listened);
+        let unemitted = gaps(&self.listened, &self.emitted);
+        if unheard.is_empty() && unemitted.is_empty() {
+            return;
+        }
+        println!("\nTauri events:");
+        for (label, gaps) in [
+            ("Emitted but never listened to", &unheard),
+            ("Listened to but never emitted", &unemitted),
+        ] {
+            if gaps.is_empty() {
+                continue;
+            }
+            println!("  {}:", label);
+            for g in gaps {
+                println!("    - {} ({})", g.name, locations_list(&g.locations));
+            }
+        }
+    }
+
+    pub fn html(&self, out: &mut String, ctx: &HtmlContext) {
+        let unheard = gaps(&self.emitted, &self.listened);
+        let unemitted = gaps(&self.listened, &self.emitted);
+        if unheard.is_empty() && unemitted.is_empty() {
+            return;
+        }
+        out.push_str("<h3>Tauri events</h3>");
+        out.push_str(&format!(
+            "<table><tr><th>Emitted, never listened to</th><th>Listened to, never emitted</th></tr><tr><td>{}</td><td>{}</td></tr></table>",
+            ctx.gap_list(&unheard),
+            ctx.gap_list(&unemitted)
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
+use serde::{Deserialize, Serialize};
+
 pub const DEFAULT_LOC_THRESHOLD: usize = 1000;
 pub const COLOR_RED: &str = "\u{001b}[31m";
 pub const COLOR_RESET: &str = "\u{001b}[0m";
@@ -18,6 +20,14 @@ pub enum OutputMode {
     Jsonl,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    GraphMl,
+    Mermaid,
+    Json,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Mode {
     Tree,
@@ -70,53 +80,223 @@ pub struct Collectors<'a> {
     pub stats: &'a mut Stats,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ImportEntry {
     pub source: String,
     pub kind: ImportKind,
+    pub resolved: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub symbols: Vec<ImportSymbol>,
+    /// TS `import type` or a Python import under `if TYPE_CHECKING:`.
+    pub type_only: bool,
+    /// Python import under `if TYPE_CHECKING:` or guarded by `except ImportError`.
+    pub conditional: bool,
 }
 
-#[derive(Clone)]
+/// A name bound by an import. `name` is the exported name (`default` for a
+/// default import, `*` for a namespace import); `alias` is the local binding
+/// when it differs.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ImportSymbol {
+    pub name: String,
+    pub alias: Option<String>,
+    pub is_default: bool,
+    pub is_namespace: bool,
+    pub type_only: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub enum ImportKind {
     Static,
     SideEffect,
+    Module,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ReexportEntry {
     pub source: String,
     pub kind: ReexportKind,
     pub resolved: Option<String>,
+    pub line: usize,
+    pub column: usize,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum ReexportKind {
     Star,
-    Named(Vec<String>),
+    /// `(name in the source module, name exported here)`; they differ for
+    /// renamed re-exports (`a as b`).
+    Named(Vec<(String, String)>),
 }
 
-#[derive(Clone)]
+/// Namespace an export lives in, so that a TS `type Foo` next to a
+/// `const Foo`, or `mod utils` in two crates, is not taken for a duplicate.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ExportCategory {
+    Type,
+    Value,
+    Module,
+    Default,
+}
+
+impl ExportCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExportCategory::Type => "type",
+            ExportCategory::Value => "value",
+            ExportCategory::Module => "module",
+            ExportCategory::Default => "default",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ExportSymbol {
     pub name: String,
     pub kind: String,
+    pub category: ExportCategory,
+    pub line: usize,
+    pub column: usize,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DynamicImportKind {
+    /// `import("./x")`, `importlib.import_module("x")`, `__import__("x")`.
+    Import,
+    /// `React.lazy(() => import("./x"))`.
+    Lazy,
+    /// Vite `import.meta.glob("./pages/*.tsx")`.
+    Glob,
+    /// `new Worker(new URL("./w.ts", import.meta.url))`.
+    Worker,
+}
+
+impl DynamicImportKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DynamicImportKind::Import => "import",
+            DynamicImportKind::Lazy => "lazy",
+            DynamicImportKind::Glob => "glob",
+            DynamicImportKind::Worker => "worker",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DynamicImport {
+    /// The specifier, or the argument expression when it is not a literal.
+    pub source: String,
+    pub kind: DynamicImportKind,
+    /// Files the import loads; a glob can match several.
+    pub resolved: Vec<String>,
+    /// `source` is a template literal or variable that cannot be resolved statically.
+    pub unresolvable: bool,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CommandRef {
     pub name: String,
     pub line: usize,
+    /// Rust function behind a handler whose invoked name was changed with
+    /// `#[tauri::command(rename = "...")]`.
+    pub rust_name: Option<String>,
+    /// Payload keys: the object literal passed to `invoke` on the frontend, the
+    /// parameters (under their wire names) of a handler. `None` when unknown,
+    /// e.g. the frontend passes a variable.
+    pub args: Option<Vec<CommandArg>>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CommandArg {
+    pub name: String,
+    /// `Option<T>` handler parameter the frontend may leave out.
+    pub optional: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EventRef {
+    pub name: String,
+    pub line: usize,
+}
+
+/// HTTP route defined by a backend or requested by a frontend.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RouteRef {
+    /// Upper-case verb, or `ANY`.
+    pub method: String,
+    /// Path with parameters normalised to `{}` (see `routes::normalize_path`).
+    pub path: String,
+    pub raw: String,
+    pub line: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FileAnalysis {
     pub path: String,
+    pub loc: usize,
     pub imports: Vec<ImportEntry>,
     pub reexports: Vec<ReexportEntry>,
-    pub dynamic_imports: Vec<String>,
+    pub dynamic_imports: Vec<DynamicImport>,
     pub exports: Vec<ExportSymbol>,
     pub command_calls: Vec<CommandRef>,
     pub command_handlers: Vec<CommandRef>,
+    /// Entries of `tauri::generate_handler![...]`, as written (may include a module path).
+    pub command_registrations: Vec<CommandRef>,
+    pub event_emits: Vec<EventRef>,
+    pub event_listens: Vec<EventRef>,
+    /// Electron channels sent from the renderer/preload and handled in main.
+    pub ipc_calls: Vec<CommandRef>,
+    pub ipc_handlers: Vec<CommandRef>,
+    pub route_defs: Vec<RouteRef>,
+    pub route_calls: Vec<RouteRef>,
+    /// Prefixes routers are mounted under (`.nest`, `include_router(prefix=...)`).
+    pub route_mounts: Vec<RouteRef>,
+    /// Problems that left the analysis of this file partial.
+    pub diagnostics: Vec<FileDiagnostic>,
+    /// Normalised tokens and their lines for clone detection; empty unless
+    /// clones are reported.
+    pub clone_tokens: Vec<(String, usize)>,
+    /// Bridge wrappers a preload script exposes (see `electron::preload_bridge`).
+    pub bridge_forwarders: Vec<(String, String)>,
+    /// Rust: roots of qualified paths used without a `use` (`serde_json::json!`),
+    /// with lines (see `rust_syntax::path_roots`).
+    pub path_roots: Vec<(String, usize)>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticKind {
+    /// The file could not be read; it is left out of the analysis.
+    Unreadable,
+    /// Not valid UTF-8; analysed with invalid bytes replaced.
+    InvalidEncoding,
+    /// Over the size limit (usually generated or bundled); left out.
+    TooLarge,
+    /// The parser rejected the file; the regex scanner was used instead.
+    ParseFallback,
+}
+
+impl DiagnosticKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticKind::Unreadable => "unreadable",
+            DiagnosticKind::InvalidEncoding => "invalid-encoding",
+            DiagnosticKind::TooLarge => "too-large",
+            DiagnosticKind::ParseFallback => "parse-fallback",
+        }
+    }
+}
+
+/// Something that went wrong analysing one file, reported instead of
+/// aborting the run.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FileDiagnostic {
+    pub path: String,
+    pub kind: DiagnosticKind,
+    pub message: String,
 }
 
 // Convenience type aliases reused across modules
-pub type ExportIndex = HashMap<String, Vec<String>>;
+pub type ExportIndex = HashMap<String, Vec<(String, ExportCategory)>>;
@@ -0,0 +1,3 @@
+import { formatTimestamp } from './index';
+
+export const stamp = formatTimestamp(new Date());
@@ -0,0 +1 @@
+export { formatDate as fmt } from './format';
@@ -0,0 +1 @@
+export { fmt as formatTimestamp } from './core';
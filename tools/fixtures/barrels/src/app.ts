@@ -0,0 +1,3 @@
+import { Button } from './index';
+
+export const app = Button();
@@ -0,0 +1,2 @@
+export * from './buttons';
+export { Card } from './Card';
@@ -0,0 +1,2 @@
+export * from './components';
+export { formatDate } from './utils/format';
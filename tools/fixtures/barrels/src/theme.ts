@@ -0,0 +1,5 @@
+export const theme = {
+  primary: '#336699',
+  secondary: '#993366',
+  spacing: 4,
+};
@@ -0,0 +1,15 @@
+import { Invoice } from './types';
+
+// Copied from orders.ts and renamed.
+export function summarizeInvoices(invoices: Invoice[]) {
+  let sum = 0;
+  const perState: Record<string, number> = {};
+  for (const invoice of invoices) {
+    if (invoice.voided) {
+      continue;
+    }
+    sum += invoice.net * 1.08;
+    perState[invoice.state] = (perState[invoice.state] ?? 0) + 1;
+  }
+  return { total: Math.round(sum * 100) / 100, byStatus: perState, label: 'invoices' };
+}
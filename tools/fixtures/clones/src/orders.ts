@@ -0,0 +1,14 @@
+import { Order } from './types';
+
+export function summarizeOrders(orders: Order[]) {
+  let total = 0;
+  const byStatus: Record<string, number> = {};
+  for (const order of orders) {
+    if (order.cancelled) {
+      continue;
+    }
+    total += order.amount * 1.23;
+    byStatus[order.status] = (byStatus[order.status] ?? 0) + 1;
+  }
+  return { total: Math.round(total * 100) / 100, byStatus, label: 'orders' };
+}
@@ -0,0 +1,11 @@
+export interface Order {
+  amount: number;
+  status: string;
+  cancelled: boolean;
+}
+
+export interface Invoice {
+  net: number;
+  state: string;
+  voided: boolean;
+}
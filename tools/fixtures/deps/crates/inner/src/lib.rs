@@ -0,0 +1,3 @@
+pub fn encode(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
@@ -0,0 +1,8 @@
+import os
+import requests
+import yaml
+import numpy as np
+
+
+def load(path):
+    return yaml.safe_load(requests.get(path).text), np.zeros(1), os.sep
@@ -0,0 +1,7 @@
+import React from 'react'
+import { map } from 'lodash/fp'
+import axios from 'axios'
+import { readFileSync } from 'node:fs'
+import { helper } from './helper'
+
+export const App = () => React.createElement('div', null, map(helper)([readFileSync, axios]))
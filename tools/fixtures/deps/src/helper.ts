@@ -0,0 +1 @@
+export const helper = (value: unknown) => value
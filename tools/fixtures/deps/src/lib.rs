@@ -0,0 +1,9 @@
+mod model;
+
+use model::Item;
+use rand::Rng;
+use serde::Deserialize;
+
+pub fn pick(items: &[Item]) -> usize {
+    rand::thread_rng().gen_range(0..items.len())
+}
@@ -0,0 +1,9 @@
+#[derive(serde::Deserialize)]
+pub struct Item;
+
+// regex::Regex is only mentioned in this comment.
+pub fn describe(item: &Item) -> serde_json::Value {
+    let _ = item;
+    ::tokio::spawn(async {});
+    serde_json::json!({ "kind": "item", "note": "once_cell::sync::Lazy" })
+}
@@ -0,0 +1 @@
+export function fmt(n: number) { return String(n); }
@@ -0,0 +1,3 @@
+export type Session = { token: string };
+export const Session = { empty: { token: '' } };
+export const User = 'user';
@@ -0,0 +1 @@
+export interface User { id: string }
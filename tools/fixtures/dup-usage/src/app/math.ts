@@ -0,0 +1 @@
+export const clamp = (n: number) => Math.min(1, n);
@@ -0,0 +1 @@
+export function formatDate(d: Date) { return d.toDateString(); }
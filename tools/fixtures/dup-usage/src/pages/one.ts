@@ -0,0 +1,4 @@
+import { formatDate } from '../features/a/format';
+import { clamp } from '../app/math';
+
+export const one = formatDate(new Date(clamp(1)));
@@ -0,0 +1,4 @@
+import { formatDate } from '../shared/format';
+import { clamp } from '../shared/math';
+
+export const three = formatDate(new Date(clamp(3)));
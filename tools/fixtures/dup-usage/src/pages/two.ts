@@ -0,0 +1,3 @@
+import { formatDate } from '../features/a/format';
+
+export const two = formatDate(new Date());
@@ -0,0 +1,6 @@
+import importlib
+
+
+def load(name):
+    base = importlib.import_module("app.plugins")
+    return base, importlib.import_module(f"app.{name}")
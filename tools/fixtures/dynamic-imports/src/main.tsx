@@ -0,0 +1,18 @@
+import React from 'react';
+
+const Home = React.lazy(() => import('./pages/Home'));
+const pages = import.meta.glob('./pages/*.tsx');
+const plugins = import.meta.glob(['./plugins/*.ts', '!./plugins/legacy.ts'], { eager: true });
+const worker = new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' });
+
+export async function boot(lang: string, name: string) {
+  const util = await import('./util');
+  const messages = await import(`./locales/${lang}.json`);
+  const plugin = await import(name);
+  return { Home, pages, plugins, worker, util, messages, plugin };
+}
+
+// Kept for reference: const legacy = await import('./util');
+/* const old = import.meta.glob('./pages/*.tsx'); */
+export const hint = "new Worker(new URL('./worker.ts', import.meta.url))";
+export const loaderHint = 'await import(`./locales/${lang}.json`)';
@@ -0,0 +1 @@
+export default function About() { return null; }
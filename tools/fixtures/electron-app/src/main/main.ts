@@ -0,0 +1,9 @@
+import { app, ipcMain } from 'electron';
+
+const Channels = { save: 'save-settings' } as const;
+
+app.whenReady().then(() => {
+  ipcMain.handle('get-user', async (_event, id: string) => ({ id }));
+  ipcMain.on(Channels.save, (_event, settings) => console.log(settings));
+  ipcMain.handle('legacy-export', async () => null);
+});
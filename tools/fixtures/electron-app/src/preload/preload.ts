@@ -0,0 +1,9 @@
+import { contextBridge, ipcRenderer } from 'electron';
+
+contextBridge.exposeInMainWorld('electron', {
+  invoke: (channel: string, ...args: unknown[]) => ipcRenderer.invoke(channel, ...args),
+  send(channel: string, payload?: unknown) {
+    return ipcRenderer.send(channel, payload);
+  },
+  getUser: (id: string) => ipcRenderer.invoke('get-user', id),
+});
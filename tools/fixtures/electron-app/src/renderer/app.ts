@@ -0,0 +1,11 @@
+declare const electron: { invoke(channel: string, ...args: unknown[]): Promise<unknown> };
+
+export async function saveSettings(settings: Record<string, unknown>) {
+  await window.electron.invoke('save-settings', settings);
+}
+
+export function openExternal(url: string) {
+  window.electron.send('open-external', url);
+}
+
+export const refreshUser = (id: string) => electron.invoke('get-user', id);
@@ -0,0 +1 @@
+pub fn unreachable_helper() {}
@@ -0,0 +1,5 @@
+mod commands;
+
+fn main() {
+    commands::list::run();
+}
@@ -0,0 +1,5 @@
+from pkg import helpers
+
+
+def main():
+    return helpers.greet()
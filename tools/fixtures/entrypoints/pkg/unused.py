@@ -0,0 +1,2 @@
+def forgotten():
+    return None
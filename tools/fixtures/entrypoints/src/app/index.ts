@@ -0,0 +1,3 @@
+export class App {
+  constructor(public name: string) {}
+}
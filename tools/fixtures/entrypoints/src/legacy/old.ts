@@ -0,0 +1,3 @@
+export function oldHelper() {
+  return 'left behind after a refactor'
+}
@@ -0,0 +1,6 @@
+import { App } from './app'
+import { formatName } from './util.service'
+
+export function boot() {
+  return new App(formatName('loctree'))
+}
@@ -0,0 +1 @@
+export const formatName = (name: string) => name.trim()
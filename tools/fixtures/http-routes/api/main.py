@@ -0,0 +1,15 @@
+"""Application entry point.
+
+Routes used to live here, e.g.:
+
+    @app.get("/api/doc-only")
+    def doc_only(): ...
+"""
+from fastapi import FastAPI
+
+from .reports import router
+
+app = FastAPI()
+
+# @app.get("/api/py-commented")
+app.include_router(router, prefix="/v1")
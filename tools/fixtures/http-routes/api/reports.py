@@ -0,0 +1,13 @@
+from fastapi import APIRouter
+
+router = APIRouter(prefix="/api/reports")
+
+
+@router.get("/{report_id}")
+def read_report(report_id: int):
+    return {"id": report_id}
+
+
+@router.api_route("/export", methods=["POST"])
+def export_reports():
+    return {}
@@ -0,0 +1,6 @@
+const express = require('express');
+
+const app = express();
+
+app.delete('/api/sessions/:sid', (req, res) => res.sendStatus(204));
+app.get('/api/legacy', (req, res) => res.json({}));
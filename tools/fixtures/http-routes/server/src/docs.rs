@@ -0,0 +1,14 @@
+/// Mount with:
+///
+/// ```ignore
+/// Router::new().route("/api/doc-only", get(handler).post(handler));
+/// ```
+#[get("/api/users")]
+pub async fn users_page() -> &'static str {
+    r#".route("/api/string-only", get(handler))"#
+}
+
+/* #[post("/api/commented-out")] */
+pub fn scope() -> &'static str {
+    "web::resource(\"/api/string-only\")"
+}
@@ -0,0 +1,18 @@
+use axum::{routing::get, Router};
+
+pub fn app() -> Router {
+    Router::new()
+        .route("/api/users", get(list_users).post(create_user))
+        .route("/api/users/:id", get(get_user))
+        .route("/api/health", get(health))
+        .nest("/admin", admin())
+}
+
+fn admin() -> Router {
+    Router::new()
+}
+
+async fn list_users() {}
+async fn create_user() {}
+async fn get_user() {}
+async fn health() {}
@@ -0,0 +1,4 @@
+// Work in progress: `.route("/api/wip", get(wip))` once the handler exists.
+pub fn wip( {
+    let hint = ".route(\"/api/hint\", get(hint))";
+}
@@ -0,0 +1,20 @@
+import axios from 'axios';
+
+const api = axios.create({ baseURL: '/' });
+
+export async function load(id: string) {
+  await fetch(`/api/users/${id}`);
+  await fetch('/api/users', { method: 'POST', body: '{}' });
+  await axios.get(`/api/reports/${id}`);
+  await api.post('/api/reports/export');
+  await api.delete(`/api/sessions/${id}`);
+  await fetch('/api/orders');
+  await fetch('https://example.com/api/external');
+}
+
+export async function retired() {
+  // await fetch('/api/legacy');
+  /* await axios.get('/api/health'); */
+  const hint = "fetch('/api/in-string')";
+  return hint.replace(/fetch\('/g, '');
+}
@@ -0,0 +1 @@
+export const query = (table: string) => table
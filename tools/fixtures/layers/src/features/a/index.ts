@@ -0,0 +1,4 @@
+import { format } from '../../shared/format'
+import { helperB } from '../b'
+
+export const featureA = () => format(helperB())
@@ -0,0 +1,6 @@
+import { query } from '../db/client'
+import { format } from '../shared/format'
+
+export const Button = () => format(query('buttons'))
+
+export const loadAdmin = () => import('../db/client')
@@ -0,0 +1,10 @@
+def load(path):
+    return path
+
+
+def dump(value):
+    return value
+
+
+def parse(value):
+    return value
@@ -0,0 +1,47 @@
+"""Service module.
+
+import fake_in_docstring
+from nowhere import nothing
+"""
+from typing import TYPE_CHECKING
+from helpers import (
+    load,
+    dump as save,  # trailing comment
+)
+from helpers import \
+    parse
+
+try:
+    import ujson as json
+except ImportError:
+    import json
+
+if TYPE_CHECKING:
+    from models import Record
+
+__all__ = ("Service", "build")
+__all__ += ["helper_alias"]
+__all__.extend(["extra"])
+
+TEMPLATE = """
+def not_a_function():
+    import not_real
+"""
+
+
+class Service:
+    def method(self):
+        from helpers import load as lazy_load
+
+        def nested():
+            pass
+
+        return lazy_load, nested
+
+
+def build(record: "Record") -> Service:
+    return Service()
+
+
+async def fetch():
+    return json.loads(save(parse(load("x"))))
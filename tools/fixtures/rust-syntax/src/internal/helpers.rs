@@ -0,0 +1,2 @@
+pub fn format_name() {}
+pub fn parse() {}
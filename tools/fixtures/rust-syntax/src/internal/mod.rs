@@ -0,0 +1,4 @@
+pub mod helpers;
+pub mod prelude;
+
+pub struct Widget;
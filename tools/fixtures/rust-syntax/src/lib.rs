@@ -0,0 +1,66 @@
+//! Doc comment mentioning `pub fn fake_doc_fn()` should not count.
+
+mod internal;
+#[cfg(test)]
+mod tests;
+
+pub use internal::{
+    helpers::{format_name, parse as parse_name},
+    Widget,
+};
+pub use internal::prelude::*;
+use std::collections::{BTreeMap, HashMap as Map};
+
+pub fn public_api() -> &'static str {
+    "pub fn fake_string_fn() {}"
+}
+
+pub(crate) fn crate_only() {}
+
+pub(super) struct SuperOnly;
+
+pub struct Exposed {
+    pub field: u32,
+}
+
+impl Exposed {
+    pub fn method(&self) -> u32 {
+        self.field
+    }
+}
+
+#[macro_export]
+macro_rules! shout {
+    ($e:expr) => {
+        $e
+    };
+}
+
+macro_rules! quiet {
+    () => {};
+}
+
+#[cfg(test)]
+pub fn test_helper() {}
+
+mod private_inline {
+    pub fn hidden() {}
+}
+
+pub mod public_inline {
+    pub fn visible() {}
+}
+
+pub fn uses_map(_: Map<u8, u8>, _: BTreeMap<u8, u8>) {}
+
+#[cfg(all(test, feature = "annotation"))]
+pub fn annotation_test_helper() {}
+
+#[cfg(all(feature = "company", test))]
+pub fn company_test_helper() {}
+
+#[cfg(not(test))]
+pub fn release_only() {}
+
+#[cfg(any(test, feature = "testing"))]
+pub fn shared_fixture() {}
@@ -0,0 +1,6 @@
+use super::public_api;
+
+#[test]
+fn works() {
+    assert!(!public_api().is_empty());
+}
@@ -0,0 +1,10 @@
+import type { Config } from './types';
+import React, { useState as useLocalState, type FC } from 'react';
+import * as utils from './utils';
+import { type Theme, type Palette } from './types';
+import Default from './utils';
+
+export const App: FC<Config> = () => {
+  const [theme] = useLocalState<Theme | Palette | null>(null);
+  return utils.render(theme, Default, React);
+};
@@ -0,0 +1,6 @@
+def load(path):
+    return path
+
+
+def dump(value):
+    return value
@@ -0,0 +1,13 @@
+from __future__ import annotations
+
+import os.path as osp
+from typing import TYPE_CHECKING
+
+from helpers import load as load_file, dump
+
+if TYPE_CHECKING:
+    from collections.abc import Iterable
+    import helpers
+
+def read(paths: Iterable[str]) -> list[str]:
+    return [load_file(osp.join("data", p)) for p in paths]
@@ -0,0 +1,5 @@
+export interface Config {
+  debug: boolean;
+}
+export type Theme = 'light' | 'dark';
+export type Palette = string[];
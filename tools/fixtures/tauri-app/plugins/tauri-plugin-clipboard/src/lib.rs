@@ -0,0 +1,21 @@
+use tauri::plugin::{Builder, TauriPlugin};
+use tauri::Runtime;
+
+#[tauri::command]
+async fn read_text() -> String {
+    String::new()
+}
+
+#[tauri::command(rename = "write_text")]
+fn store_text(text: String) {
+    let _ = text;
+}
+
+#[tauri::command]
+fn clear() {}
+
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    Builder::new("clipboard")
+        .invoke_handler(tauri::generate_handler![read_text, store_text, clear])
+        .build()
+}
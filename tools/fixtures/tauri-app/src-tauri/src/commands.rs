@@ -0,0 +1,45 @@
+#[tauri::command]
+pub async fn load_user(id: String) -> Result<String, String> {
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn save_user(user: String) -> Result<(), String> {
+    let _ = user;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_user(id: String) -> Result<(), String> {
+    let _ = id;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn ping() -> String {
+    "pong".into()
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn typed_cmd() -> u32 {
+    1
+}
+
+#[tauri::command]
+pub async fn update_profile(
+    state: tauri::State<'_, String>,
+    user_id: String,
+    display_name: Option<String>,
+    window: tauri::Window,
+) -> Result<(), String> {
+    let _ = (state, user_id, display_name, window);
+    Ok(())
+}
+
+pub fn wire_events(app: &tauri::AppHandle, window: &tauri::Window) {
+    app.emit("user-updated", "42").unwrap();
+    window.emit_to("main", "sync-done", ()).unwrap();
+    app.emit_all("orphan-event", ()).unwrap();
+    app.listen_any("ui-ready", |_| {});
+    app.listen("refresh", |_| {});
+}
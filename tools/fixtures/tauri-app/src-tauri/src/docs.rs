@@ -0,0 +1,12 @@
+//! Registering commands with tauri:
+//!
+//! ```ignore
+//! tauri::Builder::default().invoke_handler(tauri::generate_handler![doc_only_cmd, ...]);
+//! app.emit_all("doc-only-event", ());
+//! ```
+
+/// Listens with `app.listen("doc-only-listener", |_| {})`.
+pub fn describe(app: &tauri::AppHandle) -> &'static str {
+    let _ = app;
+    "call tauri::generate_handler![string_only_cmd] and app.emit(\"string-only-event\", ())"
+}
@@ -0,0 +1,22 @@
+mod commands;
+
+#[tauri::command]
+fn app_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+fn main() {
+    tauri::Builder::default()
+        .invoke_handler(tauri::generate_handler![
+            commands::load_user,
+            commands::save_user,
+            commands::delete_user,
+            // commands::typed_cmd,
+            commands::ping,
+            app_version,
+            commands::export_data,
+            commands::update_profile,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
@@ -0,0 +1,35 @@
+import { invoke } from '@tauri-apps/api/core';
+import { api, safeInvoke, callBackend } from './client';
+
+type User = { id: string };
+
+const LOAD_USER = 'load_user';
+const Commands = { save: 'save_user', remove: "delete_user" } as const;
+
+export async function loadUser(id: string) {
+  return invoke<User>(LOAD_USER, { id });
+}
+
+export const saveUser = (user: User) => api.invoke(Commands.save, { user });
+
+export const removeUser = (id: string) =>
+  invoke<Record<string, unknown>>(Commands.remove, { id });
+
+export const ping = () => safeInvoke(`ping`);
+
+export const typed = () => callBackend('typed_cmd');
+
+export const unrelated = () => reinvoke('not_a_command');
+
+export const appVersion = () => invoke<string>('app_version');
+
+export const removeUserLegacy = (id: string) => invoke('deleteUser', { id });
+
+export const readClipboard = () => invoke<string>('plugin:clipboard|read_text');
+
+export const writeClipboard = (text: string) => invoke('plugin:clipboard|write_text', { text });
+
+export const openDialog = () => invoke('plugin:dialog|open');
+
+export const updateProfile = (userId: string, nickname: string) =>
+  invoke('update_profile', { user_id: userId, nickname, 'displayName': nickname });
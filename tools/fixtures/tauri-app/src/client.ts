@@ -0,0 +1,11 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export const api = { invoke };
+
+export async function safeInvoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
+  return invoke<T>(cmd, args);
+}
+
+export async function callBackend<T>(cmd: string): Promise<T> {
+  return invoke<T>(cmd);
+}
@@ -0,0 +1,11 @@
+import { emit, emitTo, listen, once } from '@tauri-apps/api/event';
+
+const SYNC_DONE = 'sync-done';
+
+export async function wireEvents() {
+  await listen<string>('user-updated', (event) => console.log(event.payload));
+  await once(SYNC_DONE, () => {});
+  await listen('never-fired', () => {});
+  await emit('ui-ready');
+  await emitTo('main', 'refresh', {});
+}
@@ -0,0 +1,3 @@
+import { Bar, type Baz, type Foo } from './index';
+
+export const pick = (foo: Foo, baz: Baz) => [foo.id, baz.id, Bar];
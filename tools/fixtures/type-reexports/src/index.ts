@@ -0,0 +1,2 @@
+export { type Foo, Bar } from './types';
+export type { Baz } from './types';
@@ -0,0 +1,5 @@
+export type Foo = { id: string };
+export interface Baz {
+  id: string;
+}
+export const Bar = 1;
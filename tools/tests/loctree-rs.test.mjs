@@ -1,6 +1,8 @@
 import assert from 'node:assert/strict';
-import { execFileSync } from 'node:child_process';
-import { dirname, resolve } from 'node:path';
+import { execFileSync, spawnSync } from 'node:child_process';
+import { existsSync, mkdirSync, mkdtempSync, readdirSync, readFileSync, rmSync, writeFileSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import { dirname, join, resolve } from 'node:path';
 import { fileURLToPath } from 'node:url';
 
 const __filename = fileURLToPath(import.meta.url);
@@ -8,11 +10,14 @@ const __dirname = dirname(__filename);
 const repoRoot = resolve(__dirname, '..', '..');
 const fixtureRoot = resolve(repoRoot, 'tools', 'fixtures', 'basic-tree');
 const cliDir = resolve(repoRoot, 'loc_tree_rs');
+// Keep the analysis cache out of the fixtures and the user's cache directory.
+const testEnv = { ...process.env, XDG_CACHE_HOME: mkdtempSync(join(tmpdir(), 'loctree-xdg-')) };
 
 function run(args) {
   return execFileSync('cargo', ['run', '--quiet', '--', ...args], {
     cwd: cliDir,
     encoding: 'utf8',
+    env: testEnv,
   });
 }
 
@@ -53,6 +58,17 @@ assert.ok(analysis.dynamicImports.some((d) => d.file.endsWith('d.ts')));
 assert.ok(Array.isArray(analysis.duplicateExportsRanked));
 const rankedShared = analysis.duplicateExportsRanked.find((d) => d.name === 'shared');
 assert.ok(rankedShared && rankedShared.canonical);
+assert.equal(rankedShared.canonicalLine, 2);
+assert.ok(rankedShared.locations.some((l) => l.file === 'd.ts' && l.line === 1));
+assert.ok(analysis.reexportCascades.some((c) => c.from === 'a.ts' && c.line === 2));
+assert.deepEqual(analysis.dynamicImports.find((d) => d.file === 'd.ts').locations, [
+  { source: './b', kind: 'import', resolved: ['b.ts'], unresolvable: false, line: 4, column: 18 },
+]);
+const bExports = analysis.files.find((f) => f.path === 'b.ts').exports;
+assert.deepEqual(bExports.map((e) => [e.name, e.line, e.column]), [['bar', 1, 14], ['shared', 2, 14]]);
+const aReexport = analysis.files.find((f) => f.path === 'a.ts').reexports.find((r) => r.kind === 'star');
+assert.equal(aReexport.line, 2);
+assert.ok(aReexport.column > 0);
 
 const jsonlOut = run([importGraphRoot, '-A', '--jsonl', '--ext', 'ts']);
 const lines = jsonlOut.trim().split('\n');
@@ -60,4 +76,502 @@ assert.equal(lines.length, 1);
 const parsedJsonl = JSON.parse(lines[0]);
 assert.equal(parsedJsonl.root.endsWith('import-graph'), true);
 
+const entryRoot = resolve(repoRoot, 'tools', 'fixtures', 'entrypoints');
+const reach = JSON.parse(
+  run([entryRoot, '-A', '--json', '--entry', 'src/main.ts', '--entry', 'crate/src/main.rs']),
+).reachability;
+assert.ok(reach);
+const tsEntry = reach.entries.find((e) => e.path === 'src/main.ts');
+assert.equal(tsEntry.reachableFiles, 3);
+assert.ok(reach.entries.some((e) => e.path === 'pkg/cli.py' && e.origin === 'pyproject'));
+assert.ok(reach.entries.some((e) => e.path === 'crate/src/main.rs' && e.reachableFiles === 3));
+const orphanPaths = reach.orphans.map((o) => o.path).sort();
+assert.deepEqual(orphanPaths, ['crate/src/dead.rs', 'pkg/unused.py', 'src/legacy/old.ts']);
+// Orphans are limited to the languages that have an entry point.
+const tsOnlyReach = JSON.parse(run([entryRoot, '-A', '--json', '--entry', 'src/main.ts'])).reachability;
+assert.deepEqual(tsOnlyReach.orphans.map((o) => o.path).sort(), ['pkg/unused.py', 'src/legacy/old.ts']);
+const pyOnlyReach = JSON.parse(run([entryRoot, '-A', '--json'])).reachability;
+assert.deepEqual(pyOnlyReach.orphans.map((o) => o.path), ['pkg/unused.py']);
+
+const layersRoot = resolve(repoRoot, 'tools', 'fixtures', 'layers');
+let layersFailed = false;
+try {
+  run([layersRoot, '-A', '--json']);
+} catch (err) {
+  layersFailed = true;
+  assert.equal(err.status, 2);
+  const violations = JSON.parse(err.stdout).ruleViolations;
+  assert.equal(violations.length, 3);
+  assert.ok(violations.some((v) => v.rule === 'ui-no-db' && v.from === 'src/ui/Button.ts' && v.line === 1));
+  assert.ok(violations.some((v) => v.rule === 'ui-no-db' && v.from === 'src/ui/Button.ts' && v.line === 6));
+  assert.ok(violations.some((v) => v.rule === 'feature-a-isolation' && v.to === 'src/features/b/index.ts'));
+}
+assert.ok(layersFailed, 'dependency rule violations should exit non-zero');
+const badGlobConfig = join(mkdtempSync(join(tmpdir(), 'loctree-')), 'loctree.json');
+writeFileSync(
+  badGlobConfig,
+  JSON.stringify({ rules: [{ name: 'ui-no-db', from: 'src/ui/**', forbid: ['src/{db,infra/**'] }] }),
+);
+const badGlob = spawnSync('cargo', ['run', '--quiet', '--', layersRoot, '-A', '--config', badGlobConfig], {
+  cwd: cliDir,
+  encoding: 'utf8',
+  env: testEnv,
+});
+assert.equal(badGlob.status, 1);
+assert.ok(badGlob.stderr.includes("rule 'ui-no-db' has an invalid pattern 'src/{db,infra/**': unclosed `{`"), badGlob.stderr);
+
+const depsRoot = resolve(repoRoot, 'tools', 'fixtures', 'deps');
+const deps = JSON.parse(run([depsRoot, '-A', '--json'])).externalDependencies;
+assert.ok(deps.packages.some((p) => p.name === 'lodash' && p.ecosystem === 'npm' && p.declaredIn === 'package.json'));
+assert.ok(!deps.packages.some((p) => p.name === 'node:fs' || p.name === 'fs' || p.name === 'model'));
+const npmManifest = deps.manifests.find((m) => m.path === 'package.json');
+assert.deepEqual(npmManifest.undeclared.map((d) => d.name), ['axios']);
+assert.deepEqual(npmManifest.unused.map((d) => d.name), ['left-pad']);
+const cargoManifest = deps.manifests.find((m) => m.path === 'Cargo.toml');
+assert.deepEqual(cargoManifest.undeclared.map((d) => d.name), ['rand']);
+assert.deepEqual(cargoManifest.unused.map((d) => d.name), ['anyhow']);
+// Crates used only through paths and macros count; comments and strings do not.
+const cargoPkgs = deps.packages.filter((p) => p.ecosystem === 'cargo').map((p) => p.name);
+assert.ok(cargoPkgs.includes('tokio') && cargoPkgs.includes('serde_json'), cargoPkgs.join());
+assert.ok(!cargoPkgs.includes('regex') && !cargoPkgs.includes('once_cell') && !cargoPkgs.includes('model'), cargoPkgs.join());
+// Each file is checked against its nearest manifest: serde_json is missing from
+// the inner crate but declared by the root one.
+const innerManifest = deps.manifests.find((m) => m.path === 'crates/inner/Cargo.toml');
+assert.deepEqual(innerManifest.undeclared.map((d) => d.name), ['serde_json']);
+assert.equal(deps.packages.find((p) => p.name === 'serde_json').declaredIn, 'Cargo.toml');
+const pyManifest = deps.manifests.find((m) => m.path === 'requirements.txt');
+assert.deepEqual(pyManifest.undeclared.map((d) => d.name), ['numpy']);
+assert.deepEqual(pyManifest.unused.map((d) => d.name), ['unusedpkg']);
+// Python projects below the analysed root: packages next to their pyproject.toml
+// and modules next to the importing file are first-party.
+const nested = JSON.parse(run([resolve(repoRoot, 'tools', 'fixtures'), '-A', '--json', '--ext', 'py']));
+const nestedPypi = nested.externalDependencies.packages.filter((p) => p.ecosystem === 'pypi').map((p) => p.name);
+assert.ok(!nestedPypi.includes('pkg') && !nestedPypi.includes('helpers') && !nestedPypi.includes('models'), nestedPypi.join());
+// No manifest above the importing file is not the same as an undeclared package.
+const fastapi = nested.externalDependencies.packages.find((p) => p.name === 'fastapi');
+assert.ok(fastapi.declaredIn === null && fastapi.hasManifest === false);
+assert.ok(nested.externalDependencies.packages.find((p) => p.name === 'numpy').hasManifest);
+const nestedCli = nested.files.find((f) => f.path === 'entrypoints/pkg/cli.py');
+assert.ok(nestedCli.imports.some((i) => i.source === 'pkg.helpers' && i.resolved === 'entrypoints/pkg/helpers.py'));
+
+const dotOut = run([importGraphRoot, '-A', '--ext', 'ts', '--graph-format', 'dot']);
+assert.ok(dotOut.startsWith('digraph'));
+assert.ok(dotOut.includes('"a.ts" -> "c.ts" [kind="reexport"'));
+const mermaidOut = run([importGraphRoot, '-A', '--ext', 'ts', '--graph-format', 'mermaid']);
+assert.ok(mermaidOut.includes('graph LR'));
+const graphmlOut = run([importGraphRoot, '-A', '--ext', 'ts', '--graph-format', 'graphml']);
+assert.ok(graphmlOut.includes('<graphml') && graphmlOut.includes('<data key="kind">reexport</data>'));
+// Two roots that both contain src/index.ts: one diagram, document-unique ids.
+const multiRoots = ['barrels', 'basic-tree'].map((d) => resolve(repoRoot, 'tools', 'fixtures', d));
+const multiMermaid = run([...multiRoots, '-A', '--ext', 'ts', '--graph-format', 'mermaid']);
+assert.equal(multiMermaid.match(/^graph LR$/gm).length, 1);
+assert.equal(multiMermaid.match(/^  subgraph r\d+\[/gm).length, 2);
+const multiGraphml = run([...multiRoots, '-A', '--ext', 'ts', '--graph-format', 'graphml']);
+const graphmlIds = [...multiGraphml.matchAll(/<node id="([^"]+)">/g)].map((m) => m[1]);
+assert.equal(new Set(graphmlIds).size, graphmlIds.length);
+assert.ok(graphmlIds.includes('r0:src/index.ts') && graphmlIds.includes('r1:src/index.ts'));
+assert.ok(multiGraphml.includes('<edge source="r0:src/app.ts"'));
+const jsonGraph = JSON.parse(run([entryRoot, '-A', '--graph-format', 'json'])).graph;
+assert.equal(jsonGraph.nodes['src/main.ts'].metadata.language, 'typescript');
+assert.ok(jsonGraph.edges.some((e) => e.source === 'src/main.ts' && e.target === 'src/app/index.ts' && e.relation === 'import'));
+
+const symbolsRoot = resolve(repoRoot, 'tools', 'fixtures', 'symbols');
+const symbolFiles = JSON.parse(run([symbolsRoot, '-A', '--json'])).files;
+const appImports = symbolFiles.find((f) => f.path === 'app.ts').imports;
+assert.ok(appImports.find((i) => i.source === './types' && i.line === 1).typeOnly);
+const reactImport = appImports.find((i) => i.source === 'react');
+assert.equal(reactImport.typeOnly, false);
+assert.ok(reactImport.symbols.some((s) => s.default && s.alias === 'React'));
+assert.ok(reactImport.symbols.some((s) => s.name === 'useState' && s.alias === 'useLocalState' && !s.typeOnly));
+assert.ok(reactImport.symbols.some((s) => s.name === 'FC' && s.typeOnly));
+assert.ok(appImports.find((i) => i.source === './types' && i.line === 4).typeOnly);
+assert.ok(appImports.some((i) => i.source === './utils' && i.symbols[0].namespace && i.symbols[0].alias === 'utils'));
+const modelImports = symbolFiles.find((f) => f.path === 'models.py').imports;
+assert.ok(modelImports.find((i) => i.source === 'collections.abc').typeOnly);
+const runtimeHelpers = modelImports.find((i) => i.source === 'helpers' && !i.typeOnly);
+assert.deepEqual(runtimeHelpers.symbols.map((s) => [s.name, s.alias]), [['load', 'load_file'], ['dump', null]]);
+assert.ok(modelImports.some((i) => i.source === 'helpers' && i.typeOnly));
+assert.ok(modelImports.some((i) => i.source === 'os.path' && i.symbols[0].alias === 'osp'));
+const pyScanRoot = resolve(repoRoot, 'tools', 'fixtures', 'python-scan');
+const service = JSON.parse(run([pyScanRoot, '-A', '--json'])).files.find((f) => f.path === 'service.py');
+const serviceSources = service.imports.map((i) => i.source);
+assert.ok(!serviceSources.includes('fake_in_docstring') && !serviceSources.includes('not_real'));
+const multiLine = service.imports.find((i) => i.source === 'helpers' && i.line === 7);
+assert.deepEqual(multiLine.symbols.map((s) => [s.name, s.alias]), [['load', null], ['dump', 'save']]);
+assert.ok(service.imports.some((i) => i.source === 'helpers' && i.line === 11 && i.symbols[0].name === 'parse'));
+assert.ok(service.imports.find((i) => i.source === 'ujson').conditional);
+assert.ok(service.imports.find((i) => i.source === 'json').conditional);
+const recordImport = service.imports.find((i) => i.source === 'models');
+assert.ok(recordImport.conditional && recordImport.typeOnly);
+assert.equal(service.imports.find((i) => i.source === 'typing').conditional, false);
+const serviceExports = service.exports.map((e) => `${e.kind}:${e.name}`);
+assert.deepEqual(serviceExports.filter((e) => e.startsWith('__all__')), ['__all__:Service', '__all__:build', '__all__:helper_alias', '__all__:extra']);
+assert.ok(serviceExports.includes('class:Service') && serviceExports.includes('def:build') && serviceExports.includes('def:fetch'));
+assert.ok(!serviceExports.some((e) => e.endsWith(':method') || e.endsWith(':nested') || e.endsWith(':not_a_function')));
+const rustSyntaxRoot = resolve(repoRoot, 'tools', 'fixtures', 'rust-syntax');
+const rustFiles = JSON.parse(run([rustSyntaxRoot, '-A', '--json'])).files;
+const libRs = rustFiles.find((f) => f.path === 'src/lib.rs');
+const libExports = libRs.exports.map((e) => `${e.kind}:${e.name}`);
+for (const expected of ['decl:public_api', 'decl:Exposed', 'macro:shout', 'reexport:format_name', 'reexport:parse_name', 'reexport:Widget']) {
+  assert.ok(libExports.includes(expected), expected);
+}
+for (const expected of ['decl:release_only', 'decl:shared_fixture']) {
+  assert.ok(libExports.includes(expected), expected);
+}
+for (const hidden of ['crate_only', 'SuperOnly', 'method', 'quiet', 'test_helper', 'hidden', 'fake_doc_fn', 'fake_string_fn', 'annotation_test_helper', 'company_test_helper']) {
+  assert.ok(!libExports.some((e) => e.endsWith(`:${hidden}`)), hidden);
+}
+const nestedUse = libRs.imports.find((i) => i.line === 7);
+assert.deepEqual(nestedUse.symbols.map((s) => [s.name, s.alias]), [
+  ['internal::helpers::format_name', null],
+  ['internal::helpers::parse', 'parse_name'],
+  ['internal::Widget', null],
+]);
+assert.ok(libRs.reexports.some((r) => r.kind === 'star' && r.source === 'internal::prelude::*'));
+const testsMod = libRs.imports.find((i) => i.kind === 'mod' && i.source === 'tests');
+assert.ok(testsMod.conditional && testsMod.resolved === 'src/tests.rs');
+const tauriRoot = resolve(repoRoot, 'tools', 'fixtures', 'tauri-app');
+const tauriDefault = JSON.parse(run([tauriRoot, '-A', '--json'])).commands;
+const feNames = tauriDefault.frontend.map((c) => c.name).sort();
+assert.deepEqual(feNames, [
+  'app_version',
+  'deleteUser',
+  'delete_user',
+  'load_user',
+  'ping',
+  'plugin:clipboard|read_text',
+  'plugin:clipboard|write_text',
+  'plugin:dialog|open',
+  'save_user',
+  'update_profile',
+]);
+assert.deepEqual(tauriDefault.frontend.find((c) => c.name === 'load_user').locations, [['src/api.ts', 10]]);
+assert.deepEqual(tauriDefault.unusedHandlers.map((g) => g.name).sort(), ['plugin:clipboard|clear', 'typed_cmd']);
+const tauriEvents = JSON.parse(run([tauriRoot, '-A', '--json'])).events;
+assert.deepEqual(tauriEvents.emittedNotListened, [{ name: 'orphan-event', locations: [['src-tauri/src/commands.rs', 42]] }]);
+assert.deepEqual(tauriEvents.listenedNotEmitted, [{ name: 'never-fired', locations: [['src/events.ts', 8]] }]);
+assert.ok(tauriEvents.listened.some((e) => e.name === 'sync-done'));
+assert.ok(tauriEvents.emitted.some((e) => e.name === 'refresh' && e.locations[0][0] === 'src/events.ts'));
+for (const ghost of ['doc-only-event', 'string-only-event', 'doc-only-listener']) {
+  assert.ok(![...tauriEvents.emitted, ...tauriEvents.listened].some((e) => e.name === ghost), ghost);
+}
+// Files are analysed in parallel; the merged output must not depend on scheduling.
+const tauriRun = run([tauriRoot, '-A', '--json', '--no-cache']);
+assert.equal(run([tauriRoot, '-A', '--json', '--no-cache']), tauriRun);
+assert.deepEqual(JSON.parse(tauriRun).commands.frontend.map((c) => c.name), feNames);
+const tauriCaps = JSON.parse(run([tauriRoot, '-A', '--json'])).capabilities;
+assert.deepEqual(tauriCaps.files, [{ identifier: 'default', file: 'src-tauri/capabilities/default.json' }]);
+assert.deepEqual(tauriCaps.ungrantedCommands.map((c) => c.name), ['app_version', 'plugin:dialog|open']);
+assert.deepEqual(
+  tauriCaps.danglingPermissions.map((p) => [p.command, p.permission, p.file, p.line]),
+  [
+    ['ghost_command', 'allow-ghost-command', 'src-tauri/capabilities/default.json', 10],
+    ['drop_user', 'users', 'src-tauri/permissions/app.toml', 10],
+  ],
+);
+assert.deepEqual(
+  tauriCaps.unusedGrants.map((p) => [p.capability, p.command]),
+  [['default', 'plugin:clipboard|clear']],
+);
+assert.equal(JSON.parse(run([importGraphRoot, '-A', '--json'])).capabilities, null);
+const tauriReportPath = join(mkdtempSync(join(tmpdir(), 'loctree-')), 'tauri.html');
+run([tauriRoot, '-A', '--html-report', tauriReportPath]);
+const tauriReport = readFileSync(tauriReportPath, 'utf8');
+assert.ok(tauriReport.includes('<h3>Tauri events</h3>'));
+assert.ok(tauriReport.includes('<h3>Tauri capabilities</h3>'));
+assert.ok(tauriReport.includes('<code>never-fired</code> (src/events.ts:8)'));
+const tauriCustom = JSON.parse(
+  run([tauriRoot, '-A', '--json', '--config', resolve(tauriRoot, 'wrappers.loctree.json')]),
+).commands;
+assert.ok(tauriCustom.frontend.some((c) => c.name === 'typed_cmd'));
+assert.deepEqual(tauriCustom.unusedHandlers.map((g) => g.name), ['plugin:clipboard|clear']);
+assert.ok(tauriDefault.backend.some((c) => c.name === 'app_version'));
+assert.deepEqual(tauriDefault.unregisteredHandlers.map((g) => g.name), ['typed_cmd']);
+// `generate_handler!` in docs.rs sits in a doc comment and a string literal.
+assert.deepEqual(tauriDefault.registeredWithoutHandler, [
+  { name: 'export_data', paths: ['commands::export_data'], locations: [['src-tauri/src/main.rs', 17]] },
+]);
+// Tauri looks commands up by their exact name: camelCase spellings do not reach snake_case handlers.
+assert.deepEqual(tauriDefault.missingHandlers, [
+  { name: 'deleteUser', rawNames: ['deleteUser'], locations: [['src/api.ts', 26]] },
+]);
+assert.deepEqual(tauriDefault.backend.find((c) => c.name === 'plugin:clipboard|write_text').rawNames, ['store_text']);
+assert.deepEqual(tauriDefault.argumentMismatches, [
+  {
+    name: 'update_profile',
+    file: 'src/api.ts',
+    line: 35,
+    missing: ['userId'],
+    extra: ['user_id', 'nickname'],
+    handler: ['src-tauri/src/commands.rs', 29],
+  },
+]);
+
+const electronRoot = resolve(repoRoot, 'tools', 'fixtures', 'electron-app');
+const electron = JSON.parse(run([electronRoot, '-A', '--json'])).commands;
+assert.deepEqual(electron.electron.missingHandlers, [{ name: 'open-external', locations: [['src/renderer/app.ts', 8]] }]);
+assert.deepEqual(electron.electron.unusedHandlers, [{ name: 'legacy-export', locations: [['src/main/main.ts', 8]] }]);
+assert.ok(electron.electron.main.some((c) => c.name === 'save-settings'));
+assert.equal(electron.frontend.length, 0);
+
+const routesRoot = resolve(repoRoot, 'tools', 'fixtures', 'http-routes');
+const routes = JSON.parse(run([routesRoot, '-A', '--json'])).routes;
+assert.deepEqual(routes.missingRoutes, [
+  { method: 'GET', path: '/api/orders', rawPaths: [], locations: [['web/client.ts', 11]] },
+]);
+assert.deepEqual(
+  routes.unusedRoutes.map((r) => `${r.method} ${r.path}`),
+  ['GET /api/health', 'GET /api/legacy', 'GET /api/users'],
+);
+assert.ok(routes.backend.some((r) => r.path === '/api/reports/{}' && r.rawPaths.includes('/api/reports/{report_id}')));
+assert.ok(routes.backend.some((r) => r.method === 'DELETE' && r.locations[0][0] === 'node/server.js'));
+assert.ok(!routes.frontend.some((r) => r.path.includes('external')));
+// Routes that only appear in comments or string literals are not routes; the
+// same holds when the file fails to parse and is scanned as text.
+for (const ghost of ['/api/doc-only', '/api/string-only', '/api/commented-out', '/api/wip', '/api/hint', '/api/py-commented']) {
+  assert.ok(!routes.backend.some((r) => r.path === ghost), ghost);
+}
+// Requests in JS comments and strings are not requests either (web/client.ts
+// comments out calls to /api/legacy and /api/health, which stay unused).
+assert.ok(!routes.frontend.some((r) => r.path === '/api/in-string'));
+assert.deepEqual(routes.unappliedPrefixes, [
+  { prefix: '/admin', locations: [['server/src/main.rs', 8]] },
+  { prefix: '/v1', locations: [['api/main.py', 15]] },
+]);
+const routesCli = run([routesRoot, '-A']);
+assert.ok(routesCli.includes('Router prefixes not applied'));
+assert.ok(routesCli.includes('- /v1 (api/main.py:15)'));
+assert.deepEqual(
+  routes.backend.find((r) => r.path === '/api/users' && r.method === 'GET').locations,
+  [['server/src/docs.rs', 6], ['server/src/main.rs', 5]],
+);
+
+const dynamicRoot = resolve(repoRoot, 'tools', 'fixtures', 'dynamic-imports');
+const dynamicOut = JSON.parse(run([dynamicRoot, '-A', '--json']));
+// main.tsx ends with dynamic imports in comments and strings; none are reported.
+const mainDynamic = dynamicOut.dynamicImports.find((d) => d.file === 'src/main.tsx').locations;
+assert.deepEqual(
+  mainDynamic.filter((d) => !d.unresolvable).map((d) => [d.kind, d.resolved]),
+  [
+    ['lazy', ['src/pages/Home.tsx']],
+    ['glob', ['src/pages/About.tsx', 'src/pages/Home.tsx']],
+    ['glob', ['src/plugins/a.ts']],
+    ['worker', ['src/worker.ts']],
+    ['import', ['src/util.ts']],
+  ],
+);
+assert.deepEqual(
+  dynamicOut.unresolvableDynamicImports.map((d) => [d.file, d.line, d.expression]),
+  [
+    ['app/loader.py', 6, 'f"app.{name}"'],
+    ['src/main.tsx', 10, '`./locales/${lang}.json`'],
+    ['src/main.tsx', 11, 'name'],
+  ],
+);
+const dynamicGraph = JSON.parse(run([dynamicRoot, '-A', '--graph-format', 'json'])).graph;
+assert.ok(dynamicGraph.edges.some((e) => e.source === 'src/main.tsx' && e.target === 'src/worker.ts' && e.relation === 'dynamic'));
+assert.ok(dynamicGraph.edges.some((e) => e.source === 'app/loader.py' && e.target === 'app/plugins.py' && e.relation === 'dynamic'));
+
+const typeReexportsRoot = resolve(repoRoot, 'tools', 'fixtures', 'type-reexports');
+const typeReexports = JSON.parse(run([typeReexportsRoot, '-A', '--json']));
+// `export { type Foo, Bar } from` and `export type { Baz } from` re-export; they declare nothing in the barrel.
+assert.deepEqual(
+  typeReexports.files.find((f) => f.path === 'src/index.ts').exports.map((e) => [e.name, e.kind]),
+  [['Foo', 'reexport'], ['Bar', 'reexport'], ['Baz', 'reexport']],
+);
+assert.deepEqual(
+  typeReexports.duplicateExportsRanked.map((d) => [d.name, d.kind]),
+  [['Bar', 'value'], ['Baz', 'type'], ['Foo', 'type']],
+);
+// The barrel has the importers, but types.ts declares the symbols.
+assert.ok(typeReexports.duplicateExportsRanked.every((d) => d.canonical === 'src/types.ts'));
+
+const dupUsageRoot = resolve(repoRoot, 'tools', 'fixtures', 'dup-usage');
+const dupUsage = JSON.parse(run([dupUsageRoot, '-A', '--json'])).duplicateExportsRanked;
+const formatDup = dupUsage.find((d) => d.name === 'formatDate');
+assert.equal(formatDup.canonical, 'src/features/a/format.ts');
+assert.deepEqual(formatDup.refactorTargets, ['src/features/b/helpers.ts', 'src/shared/format.ts']);
+assert.deepEqual(
+  formatDup.copies.find((c) => c.file === 'src/features/a/format.ts').importers,
+  ['src/pages/one.ts', 'src/pages/two.ts'],
+);
+assert.equal(dupUsage.find((d) => d.name === 'clamp').canonical, 'src/shared/math.ts');
+
+const dupKindsRoot = resolve(repoRoot, 'tools', 'fixtures', 'dup-kinds');
+const dupKinds = JSON.parse(run([dupKindsRoot, '-A', '--json'])).duplicateExports;
+assert.deepEqual(
+  dupKinds.map((d) => [d.name, d.kind, d.files]),
+  [
+    ['Button', 'default', ['src/components/Button.tsx', 'src/legacy/Button.tsx']],
+    ['fmt', 'value', ['src/a/format.ts', 'src/b/format.ts']],
+  ],
+);
+const dupKindsConfig = join(mkdtempSync(join(tmpdir(), 'loctree-')), 'loctree.json');
+writeFileSync(dupKindsConfig, JSON.stringify({ duplicateKinds: ['module', 'mixed'] }));
+const dupKindsCustom = JSON.parse(run([dupKindsRoot, '-A', '--json', '--config', dupKindsConfig])).duplicateExports;
+assert.deepEqual(
+  dupKindsCustom.map((d) => [d.name, d.kind]),
+  [
+    ['User', 'mixed'],
+    ['utils', 'module'],
+  ],
+);
+
+const barrelsRoot = resolve(repoRoot, 'tools', 'fixtures', 'barrels');
+const barrelsOut = JSON.parse(run([barrelsRoot, '-A', '--json']));
+assert.equal(barrelsOut.maxReexportDepth, 3);
+assert.deepEqual(barrelsOut.reexportChains[0].chain, [
+  'src/index.ts',
+  'src/components/index.ts',
+  'src/components/buttons/index.ts',
+  'src/components/buttons/Button.tsx',
+]);
+assert.ok(barrelsOut.reexportChains.some((c) => c.symbol === 'Card' && c.declaredIn === 'src/components/Card.tsx' && c.depth === 2));
+assert.deepEqual(
+  barrelsOut.barrels.map((b) => [b.path, b.symbols, b.files, b.loc]),
+  [
+    ['src/index.ts', 4, 7, 21],
+    ['src/components/index.ts', 3, 5, 16],
+    ['src/components/buttons/index.ts', 2, 3, 11],
+  ],
+);
+// The declaring module is canonical even when the barrel has as many importers.
+assert.deepEqual(
+  barrelsOut.duplicateExportsRanked.map((d) => [d.name, d.canonical, d.refactorTargets]),
+  [
+    ['Button', 'src/components/buttons/Button.tsx', ['src/components/buttons/index.ts']],
+    ['Card', 'src/components/Card.tsx', ['src/components/index.ts']],
+    ['IconButton', 'src/components/buttons/IconButton.tsx', ['src/components/buttons/index.ts']],
+    ['formatDate', 'src/utils/format.ts', ['src/index.ts']],
+  ],
+);
+// Renamed re-exports (`a as b`) are followed under the original name.
+const renamesRoot = resolve(repoRoot, 'tools', 'fixtures', 'barrel-renames');
+const renamesOut = JSON.parse(run([renamesRoot, '-A', '--json']));
+assert.deepEqual(renamesOut.reexportChains, [
+  {
+    symbol: 'formatTimestamp',
+    barrel: 'src/index.ts',
+    declaredIn: 'src/core/format.ts',
+    chain: ['src/index.ts', 'src/core/index.ts', 'src/core/format.ts'],
+    depth: 2,
+    line: 1,
+  },
+]);
+assert.deepEqual(renamesOut.files.find((f) => f.path === 'src/index.ts').reexports[0].symbols, [
+  { name: 'fmt', alias: 'formatTimestamp' },
+]);
+
+const clonesRoot = resolve(repoRoot, 'tools', 'fixtures', 'clones');
+assert.deepEqual(JSON.parse(run([clonesRoot, '-A', '--json'])).clones, []);
+const clonesOut = JSON.parse(run([clonesRoot, '-A', '--json', '--clones'])).clones;
+assert.equal(clonesOut.length, 1);
+assert.deepEqual(clonesOut[0].copies, [
+  { file: 'src/invoices.ts', startLine: 4, endLine: 14 },
+  { file: 'src/orders.ts', startLine: 3, endLine: 13 },
+]);
+assert.equal(clonesOut[0].duplicatedLoc, 11);
+assert.deepEqual(JSON.parse(run([clonesRoot, '-A', '--json', '--clone-tokens', '0'])).clones, []);
+assert.deepEqual(JSON.parse(run([clonesRoot, '-A', '--json', '--clone-tokens', '50'])).clones, clonesOut);
+assert.deepEqual(JSON.parse(run([clonesRoot, '-A', '--json', '--clone-tokens', '200'])).clones, []);
+
+const cacheRoot = mkdtempSync(join(tmpdir(), 'loctree-cache-'));
+const cacheDir = mkdtempSync(join(tmpdir(), 'loctree-cache-dir-'));
+mkdirSync(join(cacheRoot, 'src'));
+writeFileSync(join(cacheRoot, 'src', 'a.ts'), "import { b } from './b';\nexport const a = b;\n");
+writeFileSync(join(cacheRoot, 'src', 'b.ts'), 'export const b = 1;\n');
+function runCached(extra = []) {
+  const res = spawnSync('cargo', ['run', '--quiet', '--', cacheRoot, '-A', '--json', '-v', '--cache-dir', cacheDir, ...extra], {
+    cwd: cliDir,
+    encoding: 'utf8',
+    env: testEnv,
+  });
+  assert.equal(res.status, 0, res.stderr);
+  return { out: JSON.parse(res.stdout), log: res.stderr };
+}
+const coldRun = runCached();
+assert.ok(coldRun.log.includes('cache: 0 hit(s), 2 miss(es)'), coldRun.log);
+assert.ok(readdirSync(cacheDir).some((d) => existsSync(join(cacheDir, d, 'analysis.json'))));
+assert.ok(!existsSync(join(cacheRoot, '.loctree')));
+const warmRun = runCached();
+assert.ok(warmRun.log.includes('cache: 2 hit(s), 0 miss(es)'), warmRun.log);
+assert.deepEqual(warmRun.out.duplicateExports, coldRun.out.duplicateExports);
+writeFileSync(join(cacheRoot, 'src', 'b.ts'), 'export const b = 1;\nexport const a = 2;\n');
+const editedRun = runCached();
+assert.ok(editedRun.log.includes('cache: 1 hit(s), 1 miss(es)'), editedRun.log);
+assert.equal(editedRun.out.duplicateExports.length, 1);
+assert.ok(runCached(['--no-cache']).log.includes('cache: disabled'));
+// Adding or removing files only drops the entries whose imports may resolve differently.
+writeFileSync(join(cacheRoot, 'src', 'c.ts'), "import { e } from './e';\nexport const c = e;\n");
+assert.ok(runCached().log.includes('cache: 2 hit(s), 1 miss(es)'));
+writeFileSync(join(cacheRoot, 'src', 'e.ts'), 'export const e = 1;\n');
+const addedRun = runCached();
+assert.ok(addedRun.log.includes('cache: 2 hit(s), 2 miss(es), 1 dropped after file set change'), addedRun.log);
+const importOf = (out, file) => out.files.find((f) => f.path === file).imports[0].resolved;
+assert.equal(importOf(addedRun.out, 'src/c.ts'), 'src/e.ts');
+rmSync(join(cacheRoot, 'src', 'b.ts'));
+const removedRun = runCached();
+assert.ok(removedRun.log.includes('cache: 2 hit(s), 1 miss(es), 1 dropped after file set change'), removedRun.log);
+assert.equal(importOf(removedRun.out, 'src/a.ts'), null);
+// Renderer analyses are redone when a preload script changes the bridge wrappers.
+writeFileSync(join(cacheRoot, 'src', 'renderer.ts'), "window.bridge.call('sync');\n");
+assert.deepEqual(runCached().out.commands.electron.renderer, []);
+writeFileSync(
+  join(cacheRoot, 'src', 'preload.ts'),
+  "contextBridge.exposeInMainWorld('bridge', {\n  call: (channel) => ipcRenderer.invoke(channel),\n});\n",
+);
+const bridgeRun = runCached();
+assert.deepEqual(bridgeRun.out.commands.electron.renderer.map((c) => c.name), ['sync']);
+assert.ok(bridgeRun.log.includes('cache: 0 hit(s), 5 miss(es)'), bridgeRun.log);
+// Manifests are part of the cache key: a new or edited one resets the cache.
+assert.ok(runCached().log.includes('cache: 5 hit(s), 0 miss(es)'));
+writeFileSync(join(cacheRoot, 'package.json'), '{"name": "cache-test"}\n');
+const manifestRun = runCached();
+assert.ok(manifestRun.log.includes('cache: 0 hit(s), 5 miss(es), reset after option or manifest change'), manifestRun.log);
+writeFileSync(join(cacheRoot, 'package.json'), '{"name": "cache-test", "private": true}\n');
+assert.ok(runCached().log.includes('reset after option or manifest change'));
+
+const diagRoot = mkdtempSync(join(tmpdir(), 'loctree-diag-'));
+mkdirSync(join(diagRoot, 'src'));
+writeFileSync(join(diagRoot, 'src', 'ok.ts'), 'export const ok = 1;\n');
+writeFileSync(join(diagRoot, 'src', 'latin1.ts'), Buffer.from('export const a = 1;\n// caf\xe9\n', 'latin1'));
+writeFileSync(join(diagRoot, 'src', 'broken.rs'), 'pub struct Kept;\n\npub fn x() { let = ; }\n');
+writeFileSync(join(diagRoot, 'src', 'bundle.js'), 'x'.repeat(5 * 1024 * 1024));
+const diagReport = join(diagRoot, 'report.html');
+for (let pass = 0; pass < 2; pass += 1) {
+  // The second run reads analyses from the cache; diagnostics must survive it.
+  const diagOut = JSON.parse(run([diagRoot, '-A', '--json', '--html-report', diagReport]));
+  assert.equal(diagOut.filesAnalyzed, 3);
+  assert.deepEqual(
+    diagOut.diagnostics.map((d) => [d.file, d.kind]),
+    [
+      ['src/broken.rs', 'parse-fallback'],
+      ['src/bundle.js', 'too-large'],
+      ['src/latin1.ts', 'invalid-encoding'],
+    ],
+  );
+  assert.ok(diagOut.diagnostics[0].message.includes('line 3'));
+  assert.ok(diagOut.diagnostics[2].message.includes('line 2'));
+  const diagFiles = Object.fromEntries(diagOut.files.map((f) => [f.path, f.exports.map((e) => e.name)]));
+  assert.deepEqual(diagFiles['src/latin1.ts'], ['a']);
+  assert.deepEqual(diagFiles['src/broken.rs'].sort(), ['Kept', 'x']);
+}
+const diagHtml = readFileSync(diagReport, 'utf8');
+assert.ok(diagHtml.includes('<h3>Warnings</h3>'));
+assert.ok(diagHtml.includes('<code>src/bundle.js</code></td><td>too-large'));
+const diagCli = run([diagRoot, '-A']);
+assert.ok(diagCli.includes('Warnings (3):'));
+assert.ok(diagCli.includes('  - src/latin1.ts [invalid-encoding] invalid UTF-8 at line 2'));
+
+const reportPath = join(mkdtempSync(join(tmpdir(), 'loctree-')), 'report.html');
+run([importGraphRoot, '-A', '--ext', 'ts', '--graph', '--html-report', reportPath]);
+const report = readFileSync(reportPath, 'utf8');
+assert.ok(!/<script[^>]+src=/.test(report));
+assert.ok(!/<link[^>]+href="https?:/.test(report));
+assert.ok(report.includes('<code>a.ts:2</code> → <code>c.ts</code>'));
+assert.ok(report.includes('<code>./b</code> → <code>b.ts</code> (d.ts:4)'));
+const graphData = report.match(/<script type="application\/json" data-loctree-graph="[^"]+">(.*?)<\/script>/s);
+assert.ok(graphData);
+assert.ok(JSON.parse(graphData[1]).edges.some(([from, to, kind]) => from === 'a.ts' && to === 'c.ts' && kind === 'reexport'));
+
 console.log('loc_tree_rs basic tests passed');